@@ -0,0 +1,79 @@
+use crate::engine::ecs::world::World;
+use crate::engine::save::{self, SaveApi};
+use crate::engine::GameId;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a. Chosen over `DefaultHasher` because its output is fixed
+/// across Rust versions and targets.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Hashes everything a save state captures: components, the entity allocator,
+/// the simulation RNG and the game's own resources. Floats are hashed by bit
+/// pattern, so any divergence between two runs shows up.
+pub fn world_checksum(game_id: GameId, world: &World, api: &SaveApi) -> u64 {
+    fnv1a(&save::encode(game_id, world, api))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ecs::components::{Transform, Velocity};
+    use crate::engine::ecs::resources::SimRng;
+    use rand::RngCore;
+
+    const GAME: GameId = GameId(0);
+
+    fn seeded_world() -> World {
+        let mut world = World::new(100.0, 100.0);
+        world.rng = SimRng::new(1234);
+        let entity = world.spawn();
+        world.set_transform(entity, Transform::at(10.0, 20.0));
+        world.set_velocity(entity, Velocity::new(1.0, -1.0));
+        world
+    }
+
+    #[test]
+    fn fnv1a_matches_reference_vectors() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn identical_worlds_have_identical_checksums() {
+        let api = SaveApi::unsupported();
+        assert_eq!(
+            world_checksum(GAME, &seeded_world(), &api),
+            world_checksum(GAME, &seeded_world(), &api)
+        );
+    }
+
+    #[test]
+    fn component_changes_alter_checksum() {
+        let api = SaveApi::unsupported();
+        let before = world_checksum(GAME, &seeded_world(), &api);
+
+        let mut world = seeded_world();
+        let position = &mut world.transforms[0].as_mut().unwrap().position;
+        position.set_x(position.x() + f32::EPSILON * 16.0);
+
+        assert_ne!(world_checksum(GAME, &world, &api), before);
+    }
+
+    #[test]
+    fn rng_draws_alter_checksum() {
+        let api = SaveApi::unsupported();
+        let before = world_checksum(GAME, &seeded_world(), &api);
+
+        let mut world = seeded_world();
+        world.rng.next_u32();
+
+        assert_ne!(world_checksum(GAME, &world, &api), before);
+    }
+}
@@ -0,0 +1,326 @@
+//! Continuous (swept) collision queries, so fast bodies cannot skip over thin
+//! colliders between steps.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Circle {
+    pub x: f32,
+    pub y: f32,
+    pub radius: f32,
+}
+
+/// Axis-aligned rectangle given by its center and half extents.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub center_x: f32,
+    pub center_y: f32,
+    pub half_width: f32,
+    pub half_height: f32,
+}
+
+impl Aabb {
+    pub fn new(center_x: f32, center_y: f32, half_width: f32, half_height: f32) -> Self {
+        Self {
+            center_x,
+            center_y,
+            half_width,
+            half_height,
+        }
+    }
+
+    pub fn from_min_max(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Self {
+        Self::new(
+            (min_x + max_x) * 0.5,
+            (min_y + max_y) * 0.5,
+            (max_x - min_x) * 0.5,
+            (max_y - min_y) * 0.5,
+        )
+    }
+
+    /// Smallest rectangle containing both.
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb::from_min_max(
+            self.min_x().min(other.min_x()),
+            self.min_y().min(other.min_y()),
+            self.max_x().max(other.max_x()),
+            self.max_y().max(other.max_y()),
+        )
+    }
+
+    /// True if the rectangles overlap or touch.
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.min_x() <= other.max_x()
+            && other.min_x() <= self.max_x()
+            && self.min_y() <= other.max_y()
+            && other.min_y() <= self.max_y()
+    }
+
+    pub fn min_x(&self) -> f32 {
+        self.center_x - self.half_width
+    }
+
+    pub fn max_x(&self) -> f32 {
+        self.center_x + self.half_width
+    }
+
+    pub fn min_y(&self) -> f32 {
+        self.center_y - self.half_height
+    }
+
+    pub fn max_y(&self) -> f32 {
+        self.center_y + self.half_height
+    }
+
+    /// Point of the rectangle closest to `(x, y)`.
+    pub fn closest_point(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            x.clamp(self.min_x(), self.max_x()),
+            y.clamp(self.min_y(), self.max_y()),
+        )
+    }
+}
+
+/// First contact of a swept circle with a rectangle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SweepHit {
+    /// Fraction of the motion covered before contact, `0.0..=1.0`. Zero means
+    /// the shapes already overlapped at the start.
+    pub time: f32,
+    /// Unit normal on the rectangle's surface, pointing toward the circle.
+    pub normal_x: f32,
+    pub normal_y: f32,
+    /// Circle center at the moment of contact.
+    pub center_x: f32,
+    pub center_y: f32,
+    /// Contact point on the rectangle's surface.
+    pub point_x: f32,
+    pub point_y: f32,
+}
+
+/// Sweeps `circle` by `motion` against `rect`, which moves by `rect_motion`
+/// over the same interval. Returns the earliest contact, if any.
+pub fn sweep_circle_aabb(
+    circle: Circle,
+    motion: (f32, f32),
+    rect: Aabb,
+    rect_motion: (f32, f32),
+) -> Option<SweepHit> {
+    let radius = circle.radius;
+    let (x, y) = (circle.x, circle.y);
+
+    if let Some((normal_x, normal_y)) = overlap_normal(circle, rect) {
+        return Some(hit_at(circle, motion, 0.0, normal_x, normal_y));
+    }
+
+    // Work in the rectangle's frame, where only the circle moves.
+    let dx = motion.0 - rect_motion.0;
+    let dy = motion.1 - rect_motion.1;
+    if dx == 0.0 && dy == 0.0 {
+        return None;
+    }
+
+    // Ray against the rectangle grown by the radius on every side.
+    let (enter_x, exit_x) = slab(x, dx, rect.min_x() - radius, rect.max_x() + radius)?;
+    let (enter_y, exit_y) = slab(y, dy, rect.min_y() - radius, rect.max_y() + radius)?;
+    let enter = enter_x.max(enter_y);
+    let exit = exit_x.min(exit_y);
+    if enter > exit || enter > 1.0 || exit < 0.0 {
+        return None;
+    }
+
+    let t = enter.max(0.0);
+    let px = x + dx * t;
+    let py = y + dy * t;
+    let beside_x = px >= rect.min_x() && px <= rect.max_x();
+    let beside_y = py >= rect.min_y() && py <= rect.max_y();
+    if enter >= 0.0 && (beside_x || beside_y) {
+        let (normal_x, normal_y) = if enter_x > enter_y {
+            (-dx.signum(), 0.0)
+        } else {
+            (0.0, -dy.signum())
+        };
+        return Some(hit_at(circle, motion, t, normal_x, normal_y));
+    }
+
+    // Entered through a grown corner: the real shape there is a quarter
+    // circle around the rectangle's corner.
+    let corner_x = if px < rect.center_x {
+        rect.min_x()
+    } else {
+        rect.max_x()
+    };
+    let corner_y = if py < rect.center_y {
+        rect.min_y()
+    } else {
+        rect.max_y()
+    };
+    let (ox, oy) = (x - corner_x, y - corner_y);
+    let a = dx * dx + dy * dy;
+    let b = 2.0 * (ox * dx + oy * dy);
+    let c = ox * ox + oy * oy - radius * radius;
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+    let normal_x = (ox + dx * t) / radius;
+    let normal_y = (oy + dy * t) / radius;
+    Some(hit_at(circle, motion, t, normal_x, normal_y))
+}
+
+/// Entry and exit times of a 1D ray against `[min, max]`, or `None` if a
+/// ray that does not move on this axis lies outside it.
+fn slab(origin: f32, delta: f32, min: f32, max: f32) -> Option<(f32, f32)> {
+    if delta == 0.0 {
+        return (origin >= min && origin <= max).then_some((f32::NEG_INFINITY, f32::INFINITY));
+    }
+    let t1 = (min - origin) / delta;
+    let t2 = (max - origin) / delta;
+    Some((t1.min(t2), t1.max(t2)))
+}
+
+/// Separation normal for shapes that already overlap, or `None` if they don't.
+fn overlap_normal(circle: Circle, rect: Aabb) -> Option<(f32, f32)> {
+    let (closest_x, closest_y) = rect.closest_point(circle.x, circle.y);
+    let (ox, oy) = (circle.x - closest_x, circle.y - closest_y);
+    let distance_sq = ox * ox + oy * oy;
+    if distance_sq >= circle.radius * circle.radius {
+        return None;
+    }
+    if distance_sq > 0.0 {
+        let distance = distance_sq.sqrt();
+        return Some((ox / distance, oy / distance));
+    }
+
+    // Center inside the rectangle: push out along the shallowest axis.
+    let left = circle.x - rect.min_x();
+    let right = rect.max_x() - circle.x;
+    let top = circle.y - rect.min_y();
+    let bottom = rect.max_y() - circle.y;
+    let shallowest = left.min(right).min(top).min(bottom);
+    Some(if shallowest == left {
+        (-1.0, 0.0)
+    } else if shallowest == right {
+        (1.0, 0.0)
+    } else if shallowest == top {
+        (0.0, -1.0)
+    } else {
+        (0.0, 1.0)
+    })
+}
+
+fn hit_at(circle: Circle, motion: (f32, f32), time: f32, normal_x: f32, normal_y: f32) -> SweepHit {
+    let center_x = circle.x + motion.0 * time;
+    let center_y = circle.y + motion.1 * time;
+    SweepHit {
+        time,
+        normal_x,
+        normal_y,
+        center_x,
+        center_y,
+        point_x: center_x - normal_x * circle.radius,
+        point_y: center_y - normal_y * circle.radius,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: f32 = 1e-4;
+
+    fn approx(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() <= EPS,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn ball(x: f32, y: f32) -> Circle {
+        Circle { x, y, radius: 5.0 }
+    }
+
+    fn paddle() -> Aabb {
+        Aabb::new(100.0, 100.0, 5.0, 30.0)
+    }
+
+    #[test]
+    fn face_hit_reports_time_normal_and_point() {
+        let hit = sweep_circle_aabb(ball(50.0, 100.0), (80.0, 0.0), paddle(), (0.0, 0.0))
+            .expect("should hit the left face");
+
+        approx(hit.time, 0.5);
+        assert_eq!((hit.normal_x, hit.normal_y), (-1.0, 0.0));
+        approx(hit.center_x, 90.0);
+        approx(hit.point_x, 95.0);
+        approx(hit.point_y, 100.0);
+    }
+
+    #[test]
+    fn thin_rectangles_cannot_be_skipped() {
+        let hit = sweep_circle_aabb(ball(0.0, 100.0), (400.0, 0.0), paddle(), (0.0, 0.0));
+
+        assert!(hit.is_some());
+    }
+
+    #[test]
+    fn misses_when_passing_beside_the_rectangle() {
+        let hit = sweep_circle_aabb(ball(50.0, 140.0), (100.0, 0.0), paddle(), (0.0, 0.0));
+
+        assert_eq!(hit, None);
+    }
+
+    #[test]
+    fn misses_when_motion_stops_short() {
+        let hit = sweep_circle_aabb(ball(50.0, 100.0), (30.0, 0.0), paddle(), (0.0, 0.0));
+
+        assert_eq!(hit, None);
+    }
+
+    #[test]
+    fn corner_hit_uses_a_rounded_normal() {
+        // Diagonal approach toward the top-left corner at (95, 70).
+        let hit = sweep_circle_aabb(ball(80.0, 55.0), (20.0, 20.0), paddle(), (0.0, 0.0))
+            .expect("should clip the corner");
+
+        let length = hit.normal_x.hypot(hit.normal_y);
+        approx(length, 1.0);
+        approx(hit.normal_x, -std::f32::consts::FRAC_1_SQRT_2);
+        approx(hit.normal_y, -std::f32::consts::FRAC_1_SQRT_2);
+        approx(hit.point_x, 95.0);
+        approx(hit.point_y, 70.0);
+    }
+
+    #[test]
+    fn grazing_a_corner_without_touching_misses() {
+        // Cuts through the grown corner square, but stays 5.66 px from the
+        // corner at (95, 70).
+        let hit = sweep_circle_aabb(ball(81.0, 76.0), (20.0, -20.0), paddle(), (0.0, 0.0));
+
+        assert_eq!(hit, None);
+    }
+
+    #[test]
+    fn moving_rectangle_is_swept_too() {
+        // The ball is still; the paddle slides into it.
+        let hit = sweep_circle_aabb(ball(120.0, 100.0), (0.0, 0.0), paddle(), (20.0, 0.0))
+            .expect("moving paddle should reach the ball");
+
+        approx(hit.time, 0.5);
+        assert_eq!((hit.normal_x, hit.normal_y), (1.0, 0.0));
+        approx(hit.center_x, 120.0);
+    }
+
+    #[test]
+    fn initial_overlap_reports_time_zero() {
+        let hit = sweep_circle_aabb(ball(103.0, 100.0), (10.0, 0.0), paddle(), (0.0, 0.0))
+            .expect("overlapping shapes should report a hit");
+
+        assert_eq!(hit.time, 0.0);
+        assert_eq!((hit.normal_x, hit.normal_y), (1.0, 0.0));
+    }
+}
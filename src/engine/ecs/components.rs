@@ -1,13 +1,100 @@
+use super::layers::{ALL_LAYERS, DEFAULT_LAYER};
+use crate::engine::math::Vec2;
+
+/// Where an entity is, which way it faces and how large it is drawn.
+/// Colliders follow `position` only; they do not turn or scale with it.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Transform {
-    pub x: f32,
-    pub y: f32,
+    pub position: SimVec2,
+    /// Radians, turning from +x toward +y.
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+
+impl Transform {
+    /// Unrotated and unscaled at `(x, y)`.
+    pub fn at(x: f32, y: f32) -> Self {
+        Self {
+            position: SimVec2::new(x, y),
+            rotation: 0.0,
+            scale: Vec2::ONE,
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: Vec2) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Unit vector the entity faces: +x turned by `rotation`.
+    pub fn forward(&self) -> Vec2 {
+        Vec2::from_angle(self.rotation)
+    }
+
+    /// Maps a point from the entity's own frame into the world: scaled,
+    /// then rotated, then moved to `position`.
+    pub fn transform_point(&self, local: Vec2) -> Vec2 {
+        self.position.to_vec2() + local.scale(self.scale).rotate(self.rotation)
+    }
 }
 
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::at(0.0, 0.0)
+    }
+}
+
+/// Vector type `Transform::position` and `Velocity` are stored in: `Vec2`,
+/// or `FixedVec2` with the `fixed-point` feature. Read and write it through
+/// `x()`, `set_x()`, `to_vec2()` and `from_vec2()`, which both types have.
+#[cfg(not(feature = "fixed-point"))]
+pub type SimVec2 = Vec2;
+#[cfg(feature = "fixed-point")]
+pub type SimVec2 = crate::engine::fixed::FixedVec2;
+
+pub type Velocity = SimVec2;
+
+/// Added to the velocity every second by `apply_acceleration`; input systems
+/// set it each frame to steer a body instead of setting its velocity.
+pub type Acceleration = Vec2;
+
+/// Exponential slowdown: velocity falls by a factor of `e` every
+/// `1 / rate` seconds, so a body coasts to rest once nothing accelerates it.
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Velocity {
-    pub x: f32,
-    pub y: f32,
+pub struct Drag {
+    pub rate: f32,
+}
+
+/// Cap on a body's speed, applied after acceleration and drag.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MaxSpeed {
+    pub value: f32,
+}
+
+/// Body moved by more than its `Velocity`, e.g. by input or clamping.
+/// `track_kinematic_bodies` sets `velocity` from how far it actually moved
+/// during the current physics step, for collision response to read.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct KinematicBody {
+    pub velocity: Vec2,
+    /// Share of `velocity` from moves made in Control this frame, which
+    /// `track_kinematic_input` spreads over the frame's sub-steps.
+    pub input_velocity: Vec2,
+    /// Position at the last tracking; `None` until the first one, so a new
+    /// body starts at rest.
+    pub last_position: Option<Vec2>,
+}
+
+impl KinematicBody {
+    /// Forgets the last position, so a teleport doesn't read as movement.
+    pub fn teleported(&mut self) {
+        *self = KinematicBody::default();
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -19,3 +106,176 @@ pub struct BounceCollider {
 pub struct Spin {
     pub value: f32,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColliderShape {
+    Circle { radius: f32 },
+    Aabb { half_width: f32, half_height: f32 },
+}
+
+impl ColliderShape {
+    /// Half width and half height of the shape's bounding box.
+    pub fn half_extents(&self) -> (f32, f32) {
+        match *self {
+            ColliderShape::Circle { radius } => (radius, radius),
+            ColliderShape::Aabb {
+                half_width,
+                half_height,
+            } => (half_width, half_height),
+        }
+    }
+}
+
+/// Surface response applied when the engine resolves a bounce. Restitution
+/// scales the speed along the normal (1 bounces fully, 0 sticks), friction is
+/// the share of sliding speed lost (0 slides freely, 1 stops dead), and the
+/// speed multiplier scales whatever hits this surface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Material {
+    pub restitution: f32,
+    pub friction: f32,
+    pub speed_multiplier: f32,
+}
+
+impl Material {
+    /// Perfect reflection: no energy lost, nothing added.
+    pub const ELASTIC: Material = Material {
+        restitution: 1.0,
+        friction: 0.0,
+        speed_multiplier: 1.0,
+    };
+
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    pub fn with_speed_multiplier(mut self, speed_multiplier: f32) -> Self {
+        self.speed_multiplier = speed_multiplier;
+        self
+    }
+
+    /// Restitution and friction for two surfaces meeting: restitutions
+    /// multiply and the rougher surface wins.
+    pub fn mix(&self, other: &Material) -> (f32, f32) {
+        (
+            self.restitution * other.restitution,
+            self.friction.max(other.friction),
+        )
+    }
+
+    /// New velocity for a body that bounces off a surface with unit `normal`
+    /// pointing toward the body, where `self` is the body's material and
+    /// `surface` the one it hit. Bodies already moving away are returned
+    /// unchanged.
+    pub fn bounce(&self, surface: &Material, velocity: Vec2, normal: Vec2) -> Vec2 {
+        let approach = velocity.dot(normal);
+        if approach >= 0.0 {
+            return velocity;
+        }
+        let (restitution, friction) = self.mix(surface);
+        let tangent = velocity - normal * approach;
+        let slide = (1.0 - friction).clamp(0.0, 1.0);
+        (tangent * slide - normal * (approach * restitution)) * surface.speed_multiplier
+    }
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Material::ELASTIC
+    }
+}
+
+/// Shape used by contact detection, centered on the entity's transform plus
+/// `offset`. Two colliders only touch if each one's `mask` includes the
+/// other's `layer`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Collider {
+    pub shape: ColliderShape,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// Layer bits this collider is on.
+    pub layer: u32,
+    /// Layer bits this collider collides with.
+    pub mask: u32,
+    /// Set to have `resolve_contacts` bounce bodies off this collider;
+    /// without one, contacts are left for game systems to script.
+    pub material: Option<Material>,
+}
+
+impl Collider {
+    pub fn circle(radius: f32) -> Self {
+        Self {
+            shape: ColliderShape::Circle { radius },
+            offset_x: 0.0,
+            offset_y: 0.0,
+            layer: DEFAULT_LAYER,
+            mask: ALL_LAYERS,
+            material: None,
+        }
+    }
+
+    pub fn aabb(half_width: f32, half_height: f32) -> Self {
+        Self {
+            shape: ColliderShape::Aabb {
+                half_width,
+                half_height,
+            },
+            offset_x: 0.0,
+            offset_y: 0.0,
+            layer: DEFAULT_LAYER,
+            mask: ALL_LAYERS,
+            material: None,
+        }
+    }
+
+    pub fn with_offset(mut self, offset_x: f32, offset_y: f32) -> Self {
+        self.offset_x = offset_x;
+        self.offset_y = offset_y;
+        self
+    }
+
+    pub fn on_layer(mut self, layer: u32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub fn with_mask(mut self, mask: u32) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    pub fn with_material(mut self, material: Material) -> Self {
+        self.material = Some(material);
+        self
+    }
+
+    pub fn collides_with(&self, other: &Collider) -> bool {
+        self.mask & other.layer != 0 && other.mask & self.layer != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn transform_maps_local_points_through_scale_rotation_and_position() {
+        let transform = Transform::at(10.0, 20.0)
+            .with_rotation(FRAC_PI_2)
+            .with_scale(Vec2::new(2.0, 3.0));
+
+        let nose = transform.transform_point(Vec2::new(1.0, 0.0));
+        let wing = transform.transform_point(Vec2::new(0.0, 1.0));
+
+        assert!(nose.distance(Vec2::new(10.0, 22.0)) < 1e-5);
+        assert!(wing.distance(Vec2::new(7.0, 20.0)) < 1e-5);
+        assert!(transform.forward().distance(Vec2::new(0.0, 1.0)) < 1e-5);
+    }
+}
@@ -0,0 +1,792 @@
+use super::components::{Collider, ColliderShape, Material, SimVec2};
+use super::entity::EntityId;
+use super::world::World;
+use crate::engine::collision::{sweep_circle_aabb, Aabb, Circle};
+use crate::engine::math::Vec2;
+
+/// Two colliders touching during a physics step. `a` always has the lower
+/// entity index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contact {
+    pub a: EntityId,
+    pub b: EntityId,
+    /// Unit normal pointing from `a` toward `b`.
+    pub normal_x: f32,
+    pub normal_y: f32,
+    /// How far the shapes overlap along the normal at the end of the step if
+    /// nothing resolves the contact.
+    pub depth: f32,
+    /// Contact point at the moment of first touch.
+    pub point_x: f32,
+    pub point_y: f32,
+    /// Fraction of the step covered before first touch, `0.0..=1.0`. Zero
+    /// means the shapes already overlapped when the step began.
+    pub time: f32,
+}
+
+impl Contact {
+    pub fn point(&self) -> Vec2 {
+        Vec2::new(self.point_x, self.point_y)
+    }
+
+    pub fn involves(&self, entity: EntityId) -> bool {
+        self.a == entity || self.b == entity
+    }
+
+    /// The other body in the contact, or `None` if `entity` isn't part of it.
+    pub fn other(&self, entity: EntityId) -> Option<EntityId> {
+        if entity == self.a {
+            Some(self.b)
+        } else if entity == self.b {
+            Some(self.a)
+        } else {
+            None
+        }
+    }
+
+    /// Normal pointing toward `entity`, i.e. the direction to push it out.
+    pub fn normal_toward(&self, entity: EntityId) -> Vec2 {
+        let normal = Vec2::new(self.normal_x, self.normal_y);
+        if entity == self.a {
+            -normal
+        } else {
+            normal
+        }
+    }
+}
+
+/// Collider placed in the world for one step: where it ended up and how far
+/// it moved to get there.
+#[derive(Clone, Copy, Debug)]
+struct Body {
+    entity: EntityId,
+    shape: ColliderShape,
+    layer: u32,
+    mask: u32,
+    x: f32,
+    y: f32,
+    motion_x: f32,
+    motion_y: f32,
+    moving: bool,
+}
+
+impl Body {
+    fn start(&self) -> (f32, f32) {
+        (self.x - self.motion_x, self.y - self.motion_y)
+    }
+
+    fn shifted(&self, dx: f32, dy: f32) -> Body {
+        Body {
+            x: self.x + dx,
+            y: self.y + dy,
+            ..*self
+        }
+    }
+}
+
+/// Engine system: replaces `world.contacts` with every pair of colliders that
+/// touched during the step, in `(a, b)` order. Runs after `integrate_velocity`,
+/// so a body's start position is its transform minus `velocity * dt`, and
+/// after `update_spatial_hash`, which supplies the candidate pairs. Moving
+/// circles are swept; pairs where neither body has a velocity, or whose layers
+/// and masks rule each other out, are skipped. On wrapping axes, bodies also
+/// touch across the seam, and contact points are wrapped back into the field.
+pub fn detect_contacts(world: &mut World, dt: f32) {
+    let mut contacts = std::mem::take(&mut world.contacts);
+    contacts.clear();
+
+    let field = world.field;
+    let hash = &world.spatial_hash;
+    let bodies = collect_bodies(world, dt);
+    for a in bodies.iter().flatten().filter(|body| body.moving) {
+        let Some(area) = hash.bounds(a.entity) else {
+            continue;
+        };
+        let found_from = contacts.len();
+        for (ox, oy) in field.seam_offsets(&area, hash.largest_extent()) {
+            let shifted = Aabb::new(
+                area.center_x + ox,
+                area.center_y + oy,
+                area.half_width,
+                area.half_height,
+            );
+            for other in hash.query_aabb(&shifted, a.mask) {
+                let Some(b) = bodies.get(other.0 as usize).and_then(Option::as_ref) else {
+                    continue;
+                };
+                if b.mask & a.layer == 0 {
+                    continue;
+                }
+                // Pairs of moving bodies are found from both sides; keep one.
+                if b.entity == a.entity || (b.moving && b.entity.0 < a.entity.0) {
+                    continue;
+                }
+                // A small field can show the same pair through two seams.
+                let seen = contacts[found_from..]
+                    .iter()
+                    .any(|contact| contact.involves(b.entity));
+                if seen {
+                    continue;
+                }
+                // Bring b next to a, as seen through the seam.
+                let b = b.shifted(-ox, -oy);
+                let (first, second) = if a.entity.0 < b.entity.0 {
+                    (a, &b)
+                } else {
+                    (&b, a)
+                };
+                if let Some(mut contact) = contact_between(first, second) {
+                    (contact.point_x, contact.point_y) =
+                        field.wrap_point(contact.point_x, contact.point_y);
+                    contacts.push(contact);
+                }
+            }
+        }
+    }
+    contacts.sort_by_key(|contact| (contact.a.0, contact.b.0));
+
+    world.contacts = contacts;
+}
+
+/// Engine system: bounces apart the bodies in `world.contacts` when at least
+/// one of their colliders has a `Material`; a collider without one counts as
+/// `Material::ELASTIC`. Bodies with a velocity move and split the response
+/// evenly, bodies without one stay put. Each moving body is pushed out along
+/// the normal by the contact depth and, if it was approaching, gets its
+/// velocity bounced and then scaled by the other material's speed multiplier.
+/// Contacts where neither collider has a material are left to game systems.
+pub fn resolve_contacts(world: &mut World, _dt: f32) {
+    for index in 0..world.contacts.len() {
+        let contact = world.contacts[index];
+        let (Some(collider_a), Some(collider_b)) = (
+            world.colliders[contact.a.0 as usize],
+            world.colliders[contact.b.0 as usize],
+        ) else {
+            continue;
+        };
+        if collider_a.material.is_none() && collider_b.material.is_none() {
+            continue;
+        }
+        let material_a = collider_a.material.unwrap_or_default();
+        let material_b = collider_b.material.unwrap_or_default();
+        let velocity_a = world.velocities[contact.a.0 as usize].map(SimVec2::to_vec2);
+        let velocity_b = world.velocities[contact.b.0 as usize].map(SimVec2::to_vec2);
+        let weight_a = if velocity_a.is_some() { 1.0 } else { 0.0 };
+        let weight_b = if velocity_b.is_some() { 1.0 } else { 0.0 };
+        let total = weight_a + weight_b;
+        if total == 0.0 {
+            continue;
+        }
+        let (share_a, share_b) = (weight_a / total, weight_b / total);
+        let normal = Vec2::new(contact.normal_x, contact.normal_y);
+
+        let push = normal * contact.depth;
+        let transform_a = world.transform_mut(contact.a);
+        transform_a.position -= SimVec2::from_vec2(push * share_a);
+        let transform_b = world.transform_mut(contact.b);
+        transform_b.position += SimVec2::from_vec2(push * share_b);
+
+        // Bounce b's velocity relative to a, then hand the change out.
+        let relative = velocity_b.unwrap_or_default() - velocity_a.unwrap_or_default();
+        if relative.dot(normal) >= 0.0 {
+            continue;
+        }
+        let surface = Material {
+            speed_multiplier: 1.0,
+            ..material_a
+        };
+        let change = material_b.bounce(&surface, relative, normal) - relative;
+        if let Some(velocity) = world.velocities[contact.a.0 as usize].as_mut() {
+            *velocity =
+                (*velocity - SimVec2::from_vec2(change * share_a)) * material_b.speed_multiplier;
+        }
+        if let Some(velocity) = world.velocities[contact.b.0 as usize].as_mut() {
+            *velocity =
+                (*velocity + SimVec2::from_vec2(change * share_b)) * material_a.speed_multiplier;
+        }
+    }
+}
+
+/// Bodies indexed by entity id.
+fn collect_bodies(world: &World, dt: f32) -> Vec<Option<Body>> {
+    world
+        .colliders
+        .iter()
+        .zip(world.transforms.iter())
+        .enumerate()
+        .map(|(idx, (collider, transform))| {
+            let (collider, transform): (&Collider, _) = (collider.as_ref()?, transform.as_ref()?);
+            let velocity = world.velocities.get(idx).and_then(|v| v.as_ref());
+            let (motion_x, motion_y) = velocity.map_or((0.0, 0.0), |v| (v.x() * dt, v.y() * dt));
+            Some(Body {
+                entity: EntityId(idx as u32),
+                shape: collider.shape,
+                layer: collider.layer,
+                mask: collider.mask,
+                x: transform.position.x() + collider.offset_x,
+                y: transform.position.y() + collider.offset_y,
+                motion_x,
+                motion_y,
+                moving: velocity.is_some(),
+            })
+        })
+        .collect()
+}
+
+fn contact_between(a: &Body, b: &Body) -> Option<Contact> {
+    match (a.shape, b.shape) {
+        (ColliderShape::Circle { radius }, ColliderShape::Aabb { .. }) => {
+            // The sweep's normal points from the rectangle (b) to the circle (a).
+            let contact = circle_aabb(a, radius, b)?;
+            Some(Contact {
+                normal_x: -contact.normal_x,
+                normal_y: -contact.normal_y,
+                ..contact
+            })
+        }
+        (ColliderShape::Aabb { .. }, ColliderShape::Circle { radius }) => {
+            let contact = circle_aabb(b, radius, a)?;
+            Some(Contact {
+                a: a.entity,
+                b: b.entity,
+                ..contact
+            })
+        }
+        (ColliderShape::Circle { radius: ra }, ColliderShape::Circle { radius: rb }) => {
+            circle_circle(a, ra, b, rb)
+        }
+        (
+            ColliderShape::Aabb {
+                half_width: wa,
+                half_height: ha,
+            },
+            ColliderShape::Aabb {
+                half_width: wb,
+                half_height: hb,
+            },
+        ) => aabb_aabb(a, wa, ha, b, wb, hb),
+    }
+}
+
+/// Sweeps `circle` against `rect`. The returned contact has `a` = circle and
+/// its normal pointing from the rectangle toward the circle.
+fn circle_aabb(circle: &Body, radius: f32, rect: &Body) -> Option<Contact> {
+    let ColliderShape::Aabb {
+        half_width,
+        half_height,
+    } = rect.shape
+    else {
+        return None;
+    };
+    let (cx, cy) = circle.start();
+    let (rx, ry) = rect.start();
+    let hit = sweep_circle_aabb(
+        Circle {
+            x: cx,
+            y: cy,
+            radius,
+        },
+        (circle.motion_x, circle.motion_y),
+        Aabb::new(rx, ry, half_width, half_height),
+        (rect.motion_x, rect.motion_y),
+    )?;
+
+    // Distance of the circle's final center from the surface plane it hit,
+    // with that plane carried along by the rectangle's remaining motion.
+    let remaining = 1.0 - hit.time;
+    let plane_x = hit.point_x + rect.motion_x * remaining;
+    let plane_y = hit.point_y + rect.motion_y * remaining;
+    let distance = (circle.x - plane_x) * hit.normal_x + (circle.y - plane_y) * hit.normal_y;
+
+    Some(Contact {
+        a: circle.entity,
+        b: rect.entity,
+        normal_x: hit.normal_x,
+        normal_y: hit.normal_y,
+        depth: (radius - distance).max(0.0),
+        point_x: hit.point_x,
+        point_y: hit.point_y,
+        time: hit.time,
+    })
+}
+
+fn circle_circle(a: &Body, ra: f32, b: &Body, rb: f32) -> Option<Contact> {
+    let reach = ra + rb;
+    let (ax, ay) = a.start();
+    let (bx, by) = b.start();
+    // Work in b's frame, where only a moves.
+    let (ox, oy) = (ax - bx, ay - by);
+    let (dx, dy) = (a.motion_x - b.motion_x, a.motion_y - b.motion_y);
+
+    let time = if ox * ox + oy * oy < reach * reach {
+        0.0
+    } else {
+        let qa = dx * dx + dy * dy;
+        if qa == 0.0 {
+            return None;
+        }
+        let qb = 2.0 * (ox * dx + oy * dy);
+        let qc = ox * ox + oy * oy - reach * reach;
+        let discriminant = qb * qb - 4.0 * qa * qc;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let t = (-qb - discriminant.sqrt()) / (2.0 * qa);
+        if !(0.0..=1.0).contains(&t) {
+            return None;
+        }
+        t
+    };
+
+    // Normal from a toward b at the moment of contact.
+    let (sx, sy) = (-(ox + dx * time), -(oy + dy * time));
+    let separation = Vec2::new(sx, sy).length();
+    let (normal_x, normal_y) = if separation > 0.0 {
+        (sx / separation, sy / separation)
+    } else {
+        (1.0, 0.0)
+    };
+    let end_distance = Vec2::new(b.x - a.x, b.y - a.y).length();
+
+    Some(Contact {
+        a: a.entity,
+        b: b.entity,
+        normal_x,
+        normal_y,
+        depth: (reach - end_distance).max(0.0),
+        point_x: ax + a.motion_x * time + normal_x * ra,
+        point_y: ay + a.motion_y * time + normal_y * ra,
+        time,
+    })
+}
+
+/// Rectangles are tested only where they ended up, and report `time` 0.
+fn aabb_aabb(a: &Body, wa: f32, ha: f32, b: &Body, wb: f32, hb: f32) -> Option<Contact> {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let overlap_x = wa + wb - dx.abs();
+    let overlap_y = ha + hb - dy.abs();
+    if overlap_x <= 0.0 || overlap_y <= 0.0 {
+        return None;
+    }
+
+    let (normal_x, normal_y, depth) = if overlap_x < overlap_y {
+        (if dx < 0.0 { -1.0 } else { 1.0 }, 0.0, overlap_x)
+    } else {
+        (0.0, if dy < 0.0 { -1.0 } else { 1.0 }, overlap_y)
+    };
+    // Middle of the overlapping region.
+    let left = (a.x - wa).max(b.x - wb);
+    let right = (a.x + wa).min(b.x + wb);
+    let top = (a.y - ha).max(b.y - hb);
+    let bottom = (a.y + ha).min(b.y + hb);
+
+    Some(Contact {
+        a: a.entity,
+        b: b.entity,
+        normal_x,
+        normal_y,
+        depth,
+        point_x: (left + right) * 0.5,
+        point_y: (top + bottom) * 0.5,
+        time: 0.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ecs::components::{BounceCollider, Transform, Velocity};
+    use crate::engine::ecs::layers::{CollisionLayers, ALL_LAYERS};
+    use crate::engine::ecs::resources::{EdgeBehavior, FieldEdge, FieldEdges, SimRng};
+    use crate::engine::ecs::schedule::Schedule;
+    use crate::engine::ecs::spatial_hash::update_spatial_hash;
+    use crate::engine::with_engine_systems;
+    use rand::Rng;
+
+    const EPS: f32 = 1e-4;
+
+    fn approx(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() <= EPS,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn detect(world: &mut World, dt: f32) {
+        update_spatial_hash(world, dt);
+        detect_contacts(world, dt);
+    }
+
+    /// Every pair, no broadphase: the reference the hashed path must match.
+    fn brute_force_contacts(world: &World, dt: f32) -> Vec<Contact> {
+        let bodies: Vec<Body> = collect_bodies(world, dt).into_iter().flatten().collect();
+        let mut contacts = Vec::new();
+        for (i, a) in bodies.iter().enumerate() {
+            for b in &bodies[i + 1..] {
+                let layers_match = a.mask & b.layer != 0 && b.mask & a.layer != 0;
+                if (a.moving || b.moving) && layers_match {
+                    contacts.extend(contact_between(a, b));
+                }
+            }
+        }
+        contacts
+    }
+
+    /// Circles and rectangles scattered at random, two thirds of them moving,
+    /// spread over three layers with a few picky masks.
+    fn crowded_world(count: usize) -> World {
+        let mut world = World::new(4000.0, 4000.0);
+        let mut rng = SimRng::new(41);
+        for i in 0..count {
+            let entity = world.spawn();
+            let x = rng.gen_range(0.0..4000.0);
+            let y = rng.gen_range(0.0..4000.0);
+            world.set_transform(entity, Transform::at(x, y));
+            let collider = if i % 2 == 0 {
+                Collider::circle(rng.gen_range(2.0..12.0))
+            } else {
+                Collider::aabb(rng.gen_range(4.0..30.0), rng.gen_range(4.0..12.0))
+            };
+            let mask = if i % 5 == 0 { 0b011 } else { ALL_LAYERS };
+            world.set_collider(entity, collider.on_layer(1 << (i % 3)).with_mask(mask));
+            if i % 3 != 0 {
+                let velocity =
+                    Velocity::new(rng.gen_range(-600.0..600.0), rng.gen_range(-600.0..600.0));
+                world.set_velocity(entity, velocity);
+            }
+        }
+        world
+    }
+
+    fn body(world: &mut World, x: f32, y: f32, collider: Collider) -> EntityId {
+        let entity = world.spawn();
+        world.set_transform(entity, Transform::at(x, y));
+        world.set_collider(entity, collider);
+        entity
+    }
+
+    #[test]
+    fn fast_circle_reports_contact_with_thin_rectangle() {
+        let mut world = World::new(800.0, 600.0);
+        let wall = body(&mut world, 100.0, 100.0, Collider::aabb(5.0, 30.0));
+        // Ended the step 200 px past the wall after moving 400 px.
+        let ball = body(&mut world, 300.0, 100.0, Collider::circle(5.0));
+        world.set_velocity(ball, Velocity::new(400.0, 0.0));
+
+        detect(&mut world, 1.0);
+
+        assert_eq!(world.contacts.len(), 1);
+        let contact = world.contacts[0];
+        assert_eq!((contact.a, contact.b), (wall, ball));
+        assert_eq!((contact.normal_x, contact.normal_y), (-1.0, 0.0));
+        assert_eq!(contact.normal_toward(ball), Vec2::new(-1.0, 0.0));
+        approx(contact.time, 0.475);
+        approx(contact.point_x, 95.0);
+        approx(contact.depth, 210.0);
+    }
+
+    #[test]
+    fn circles_touching_mid_step_are_reported() {
+        let mut world = World::new(800.0, 600.0);
+        let a = body(&mut world, 120.0, 0.0, Collider::circle(10.0));
+        let b = body(&mut world, 120.0, 0.0, Collider::circle(10.0));
+        world.set_velocity(a, Velocity::new(20.0, 0.0));
+        world.set_velocity(b, Velocity::new(-20.0, 0.0));
+
+        detect(&mut world, 1.0);
+
+        // Started 40 apart, closing at 40: first touch halfway through.
+        let contact = world.contacts[0];
+        approx(contact.time, 0.5);
+        assert_eq!((contact.normal_x, contact.normal_y), (1.0, 0.0));
+        approx(contact.point_x, 120.0);
+        approx(contact.depth, 20.0);
+    }
+
+    #[test]
+    fn overlapping_rectangles_separate_along_shallowest_axis() {
+        let mut world = World::new(800.0, 600.0);
+        let a = body(&mut world, 0.0, 0.0, Collider::aabb(10.0, 10.0));
+        let b = body(&mut world, 16.0, 4.0, Collider::aabb(10.0, 10.0));
+        world.set_velocity(b, Velocity::new(0.0, 0.0));
+
+        detect(&mut world, 1.0);
+
+        let contact = world.contacts[0];
+        assert_eq!((contact.a, contact.b), (a, b));
+        assert_eq!((contact.normal_x, contact.normal_y), (1.0, 0.0));
+        approx(contact.depth, 4.0);
+        approx(contact.point_x, 8.0);
+        approx(contact.point_y, 2.0);
+    }
+
+    #[test]
+    fn offset_moves_the_shape() {
+        let mut world = World::new(800.0, 600.0);
+        body(
+            &mut world,
+            0.0,
+            0.0,
+            Collider::circle(5.0).with_offset(100.0, 0.0),
+        );
+        let probe = body(&mut world, 108.0, 0.0, Collider::circle(5.0));
+        world.set_velocity(probe, Velocity::new(0.0, 0.0));
+
+        detect(&mut world, 1.0);
+
+        assert_eq!(world.contacts.len(), 1);
+        approx(world.contacts[0].depth, 2.0);
+    }
+
+    #[test]
+    fn masks_let_layers_pass_through_each_other() {
+        let layers = CollisionLayers::new()
+            .with_layer("ball")
+            .with_layer("brick")
+            .with_layer("capsule");
+        let mut world = World::new(800.0, 600.0);
+        let brick = body(
+            &mut world,
+            100.0,
+            100.0,
+            Collider::aabb(20.0, 10.0)
+                .on_layer(layers.layer("brick"))
+                .with_mask(layers.mask(&["ball"])),
+        );
+        let capsule = body(
+            &mut world,
+            100.0,
+            100.0,
+            Collider::aabb(8.0, 4.0)
+                .on_layer(layers.layer("capsule"))
+                .with_mask(ALL_LAYERS),
+        );
+        world.set_velocity(capsule, Velocity::new(0.0, 50.0));
+        let ball = body(
+            &mut world,
+            100.0,
+            108.0,
+            Collider::circle(5.0).on_layer(layers.layer("ball")),
+        );
+        world.set_velocity(ball, Velocity::new(0.0, 0.0));
+
+        detect(&mut world, 1.0);
+
+        // The capsule's mask allows bricks, but the brick's mask doesn't
+        // allow capsules, so only the ball's contacts remain.
+        let pairs: Vec<_> = world.contacts.iter().map(|c| (c.a, c.b)).collect();
+        assert_eq!(pairs, vec![(brick, ball), (capsule, ball)]);
+    }
+
+    #[test]
+    fn bodies_straddling_a_wrapping_seam_touch() {
+        let mut world = World::new(100.0, 100.0);
+        world.field.edges = FieldEdges::all(EdgeBehavior::Wrap);
+        let rock = body(&mut world, 98.0, 50.0, Collider::circle(5.0));
+        let ship = body(&mut world, 3.0, 50.0, Collider::circle(5.0));
+        world.set_velocity(ship, Velocity::new(0.0, 0.0));
+
+        detect(&mut world, 1.0);
+
+        assert_eq!(world.contacts.len(), 1);
+        let contact = world.contacts[0];
+        assert_eq!((contact.a, contact.b), (rock, ship));
+        // From the rock, the ship lies just across the right edge.
+        assert_eq!((contact.normal_x, contact.normal_y), (1.0, 0.0));
+        approx(contact.depth, 5.0);
+        approx(contact.point_x, 3.0);
+    }
+
+    #[test]
+    fn a_body_crossing_the_seam_hits_what_waits_on_the_far_side() {
+        let mut schedule = with_engine_systems(Schedule::new()).build().unwrap();
+        let mut world = World::new(100.0, 100.0);
+        world.field.edges = FieldEdges::all(EdgeBehavior::Wrap);
+        let rock = body(&mut world, 12.0, 50.0, Collider::circle(5.0));
+        let ship = body(
+            &mut world,
+            85.0,
+            50.0,
+            Collider::circle(5.0).with_material(Material::ELASTIC),
+        );
+        world.set_velocity(ship, Velocity::new(100.0, 0.0));
+        world.set_wall_bounce_collider(ship, BounceCollider { radius: 5.0 });
+
+        schedule.run(&mut world, 0.1);
+        assert!(world.contacts.is_empty());
+        // Crosses the right edge and meets the rock just past the left one.
+        schedule.run(&mut world, 0.1);
+
+        let pairs: Vec<_> = world.contacts.iter().map(|c| (c.a, c.b)).collect();
+        assert_eq!(pairs, vec![(rock, ship)]);
+        approx(world.contacts[0].point_x, 7.0);
+        assert_eq!(world.velocity(ship).x(), -100.0);
+        approx(world.transform(ship).position.x(), 2.0);
+        assert_eq!(world.transform(rock).position.x(), 12.0);
+
+        // Bounced back over the seam it just crossed.
+        schedule.run(&mut world, 0.1);
+        approx(world.transform(ship).position.x(), 92.0);
+    }
+
+    #[test]
+    fn seams_are_ignored_unless_both_edges_wrap() {
+        let mut world = World::new(100.0, 100.0);
+        world.field.edges = FieldEdges::default().with(FieldEdge::Left, EdgeBehavior::Wrap);
+        body(&mut world, 98.0, 50.0, Collider::circle(5.0));
+        let ship = body(&mut world, 3.0, 50.0, Collider::circle(5.0));
+        world.set_velocity(ship, Velocity::new(0.0, 0.0));
+
+        detect(&mut world, 1.0);
+
+        assert!(world.contacts.is_empty());
+    }
+
+    fn resolve(world: &mut World, dt: f32) {
+        detect(world, dt);
+        resolve_contacts(world, dt);
+    }
+
+    #[test]
+    fn damped_wall_stops_the_tunnel_and_halves_the_bounce() {
+        let mut world = World::new(800.0, 600.0);
+        let damped = Material::ELASTIC.with_restitution(0.5);
+        body(
+            &mut world,
+            100.0,
+            100.0,
+            Collider::aabb(5.0, 30.0).with_material(damped),
+        );
+        let ball = body(&mut world, 300.0, 100.0, Collider::circle(5.0));
+        world.set_velocity(ball, Velocity::new(400.0, 0.0));
+
+        resolve(&mut world, 1.0);
+
+        approx(world.transform(ball).position.x(), 90.0);
+        assert_eq!(*world.velocity(ball), Velocity::new(-200.0, 0.0));
+    }
+
+    #[test]
+    fn bumper_speeds_up_what_hits_it() {
+        let mut world = World::new(800.0, 600.0);
+        let bumper = Material::ELASTIC.with_speed_multiplier(1.5);
+        body(
+            &mut world,
+            100.0,
+            100.0,
+            Collider::circle(10.0).with_material(bumper),
+        );
+        let ball = body(&mut world, 100.0, 88.0, Collider::circle(5.0));
+        world.set_velocity(ball, Velocity::new(0.0, 40.0));
+
+        resolve(&mut world, 0.1);
+
+        approx(world.transform(ball).position.y(), 85.0);
+        assert_eq!(*world.velocity(ball), Velocity::new(0.0, -60.0));
+    }
+
+    #[test]
+    fn moving_bodies_share_the_response() {
+        let mut world = World::new(800.0, 600.0);
+        let sticky = Material::ELASTIC.with_restitution(0.0);
+        let left = body(
+            &mut world,
+            96.0,
+            100.0,
+            Collider::circle(5.0).with_material(sticky),
+        );
+        let right = body(&mut world, 104.0, 100.0, Collider::circle(5.0));
+        world.set_velocity(left, Velocity::new(20.0, 0.0));
+        world.set_velocity(right, Velocity::new(-20.0, 0.0));
+
+        resolve(&mut world, 0.1);
+
+        approx(world.transform(left).position.x(), 95.0);
+        approx(world.transform(right).position.x(), 105.0);
+        approx(world.velocity(left).x(), 0.0);
+        approx(world.velocity(right).x(), 0.0);
+    }
+
+    #[test]
+    fn contacts_without_materials_are_left_alone() {
+        let mut world = World::new(800.0, 600.0);
+        body(&mut world, 100.0, 100.0, Collider::aabb(5.0, 30.0));
+        let ball = body(&mut world, 300.0, 100.0, Collider::circle(5.0));
+        world.set_velocity(ball, Velocity::new(400.0, 0.0));
+
+        resolve(&mut world, 1.0);
+
+        assert_eq!(world.contacts.len(), 1);
+        assert_eq!(world.transform(ball).position.x(), 300.0);
+        assert_eq!(world.velocity(ball).x(), 400.0);
+    }
+
+    #[test]
+    fn static_pairs_and_misses_are_skipped() {
+        let mut world = World::new(800.0, 600.0);
+        body(&mut world, 0.0, 0.0, Collider::aabb(10.0, 10.0));
+        body(&mut world, 5.0, 0.0, Collider::aabb(10.0, 10.0));
+        let far = body(&mut world, 300.0, 300.0, Collider::circle(5.0));
+        world.set_velocity(far, Velocity::new(10.0, 0.0));
+
+        detect(&mut world, 1.0);
+
+        assert!(world.contacts.is_empty());
+    }
+
+    #[test]
+    fn contacts_are_replaced_every_step() {
+        let mut world = World::new(800.0, 600.0);
+        body(&mut world, 0.0, 0.0, Collider::circle(10.0));
+        let mover = body(&mut world, 15.0, 0.0, Collider::circle(10.0));
+        world.set_velocity(mover, Velocity::new(0.0, 0.0));
+        detect(&mut world, 1.0);
+        assert_eq!(world.contacts.len(), 1);
+
+        world.transform_mut(mover).position.set_x(100.0);
+        detect(&mut world, 1.0);
+
+        assert!(world.contacts.is_empty());
+    }
+
+    #[test]
+    fn broadphase_matches_brute_force_with_thousands_of_colliders() {
+        let mut world = crowded_world(3000);
+        let dt = 1.0 / 60.0;
+
+        detect(&mut world, dt);
+
+        let expected = brute_force_contacts(&world, dt);
+        assert!(!expected.is_empty());
+        assert_eq!(world.contacts, expected);
+    }
+
+    /// `cargo test --release bench_contacts -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_contacts_with_thousands_of_colliders() {
+        let mut world = crowded_world(5000);
+        let dt = 1.0 / 60.0;
+        let steps = 60;
+
+        let start = std::time::Instant::now();
+        for _ in 0..steps {
+            detect(&mut world, dt);
+        }
+        let hashed = start.elapsed() / steps;
+
+        let start = std::time::Instant::now();
+        let expected = brute_force_contacts(&world, dt);
+        let brute = start.elapsed();
+
+        assert_eq!(world.contacts, expected);
+        println!(
+            "{} colliders: spatial hash {:?}/step, all pairs {:?}/step",
+            world.colliders.len(),
+            hashed,
+            brute
+        );
+    }
+}
@@ -27,6 +27,18 @@ impl EntityAllocator {
     pub fn free(&mut self, entity: EntityId) {
         self.free.push(entity.0);
     }
+
+    pub fn from_parts(next: u32, free: Vec<u32>) -> Self {
+        Self { next, free }
+    }
+
+    pub fn next_id(&self) -> u32 {
+        self.next
+    }
+
+    pub fn free_ids(&self) -> &[u32] {
+        &self.free
+    }
 }
 
 #[cfg(test)]
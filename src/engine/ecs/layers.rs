@@ -0,0 +1,100 @@
+/// Bit of the layer every collider starts on.
+pub const DEFAULT_LAYER: u32 = 1;
+pub const ALL_LAYERS: u32 = u32::MAX;
+pub const MAX_COLLISION_LAYERS: usize = 32;
+
+/// Named collision layers, one bit each. Bit 0 is always `"default"`. Games
+/// declare theirs with `with_layer` when building the world, then build
+/// collider layers and masks from the names.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CollisionLayers {
+    names: Vec<&'static str>,
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CollisionLayers {
+    pub fn new() -> Self {
+        Self {
+            names: vec!["default"],
+        }
+    }
+
+    /// Adds a layer on the next free bit.
+    pub fn with_layer(mut self, name: &'static str) -> Self {
+        assert!(
+            !self.names.contains(&name),
+            "collision layer {} declared twice",
+            name
+        );
+        assert!(
+            self.names.len() < MAX_COLLISION_LAYERS,
+            "at most {} collision layers",
+            MAX_COLLISION_LAYERS
+        );
+        self.names.push(name);
+        self
+    }
+
+    /// Bit for `name`. Panics on unknown names, which are always a typo.
+    pub fn layer(&self, name: &str) -> u32 {
+        let index = self
+            .names
+            .iter()
+            .position(|&known| known == name)
+            .unwrap_or_else(|| panic!("unknown collision layer {}", name));
+        1 << index
+    }
+
+    /// Union of the bits for `names`.
+    pub fn mask(&self, names: &[&str]) -> u32 {
+        names.iter().fold(0, |mask, name| mask | self.layer(name))
+    }
+
+    pub fn names(&self) -> &[&'static str] {
+        &self.names
+    }
+
+    /// Names of the layers set in `bits`.
+    pub fn names_in(&self, bits: u32) -> Vec<&'static str> {
+        self.names
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| bits & (1 << index) != 0)
+            .map(|(_, &name)| name)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layers_get_consecutive_bits_after_default() {
+        let layers = CollisionLayers::new()
+            .with_layer("ball")
+            .with_layer("brick");
+
+        assert_eq!(layers.layer("default"), DEFAULT_LAYER);
+        assert_eq!(layers.layer("ball"), 0b010);
+        assert_eq!(layers.mask(&["ball", "brick"]), 0b110);
+        assert_eq!(layers.names_in(0b101), vec!["default", "brick"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown collision layer laser")]
+    fn unknown_layer_names_panic() {
+        CollisionLayers::new().layer("laser");
+    }
+
+    #[test]
+    #[should_panic(expected = "declared twice")]
+    fn duplicate_layer_names_panic() {
+        let _ = CollisionLayers::new().with_layer("ball").with_layer("ball");
+    }
+}
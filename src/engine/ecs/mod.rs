@@ -1,6 +1,14 @@
 pub mod components;
+pub mod contacts;
 pub mod entity;
+pub mod layers;
+pub mod profiler;
+pub mod raycast;
 pub mod resources;
 pub mod schedule;
+pub mod spatial_hash;
+pub mod state;
 pub mod systems;
+pub mod time;
+pub mod timer;
 pub mod world;
@@ -0,0 +1,250 @@
+use std::collections::VecDeque;
+
+use super::schedule::SystemPhase;
+
+pub const DEFAULT_PROFILE_WINDOW: usize = 120;
+
+/// Rolling window of durations in milliseconds.
+#[derive(Clone, Debug)]
+pub struct TimingWindow {
+    samples: VecDeque<f64>,
+    capacity: usize,
+}
+
+impl TimingWindow {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&mut self, ms: f64) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(ms);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn min(&self) -> f64 {
+        self.samples.iter().copied().fold(f64::INFINITY, f64::min)
+    }
+
+    pub fn max(&self) -> f64 {
+        self.samples.iter().copied().fold(0.0, f64::max)
+    }
+
+    pub fn avg(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingReport {
+    pub phase: SystemPhase,
+    /// `None` for the phase total.
+    pub system: Option<&'static str>,
+    pub samples: usize,
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+}
+
+struct PhaseTimings {
+    phase: SystemPhase,
+    total: TimingWindow,
+    systems: Vec<Option<(&'static str, TimingWindow)>>,
+}
+
+/// Per-system and per-phase wall-clock timings collected by
+/// [`Schedule::run_profiled`](super::schedule::Schedule::run_profiled).
+/// Phases are reported in the order they were first recorded.
+pub struct Profiler {
+    window: usize,
+    phases: Vec<PhaseTimings>,
+}
+
+impl Profiler {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            phases: Vec::new(),
+        }
+    }
+
+    fn phase_timings(&mut self, phase: SystemPhase) -> &mut PhaseTimings {
+        let index = match self
+            .phases
+            .iter()
+            .position(|timings| timings.phase == phase)
+        {
+            Some(index) => index,
+            None => {
+                self.phases.push(PhaseTimings {
+                    phase,
+                    total: TimingWindow::new(self.window),
+                    systems: Vec::new(),
+                });
+                self.phases.len() - 1
+            }
+        };
+        &mut self.phases[index]
+    }
+
+    pub fn record_system(
+        &mut self,
+        phase: SystemPhase,
+        position: usize,
+        name: &'static str,
+        ms: f64,
+    ) {
+        let window = self.window;
+        let systems = &mut self.phase_timings(phase).systems;
+        if systems.len() <= position {
+            systems.resize_with(position + 1, || None);
+        }
+        let entry = systems[position].get_or_insert_with(|| (name, TimingWindow::new(window)));
+        entry.0 = name;
+        entry.1.push(ms);
+    }
+
+    pub fn record_phase(&mut self, phase: SystemPhase, ms: f64) {
+        self.phase_timings(phase).total.push(ms);
+    }
+
+    pub fn clear(&mut self) {
+        self.phases.clear();
+    }
+
+    /// Phase totals followed by their systems, in execution order.
+    pub fn report(&self) -> Vec<TimingReport> {
+        fn entry(
+            phase: SystemPhase,
+            system: Option<&'static str>,
+            window: &TimingWindow,
+        ) -> TimingReport {
+            TimingReport {
+                phase,
+                system,
+                samples: window.len(),
+                min_ms: window.min(),
+                avg_ms: window.avg(),
+                max_ms: window.max(),
+            }
+        }
+
+        let mut report = Vec::new();
+        for timings in &self.phases {
+            if !timings.total.is_empty() {
+                report.push(entry(timings.phase, None, &timings.total));
+            }
+            for (name, window) in timings.systems.iter().flatten() {
+                report.push(entry(timings.phase, Some(name), window));
+            }
+        }
+        report
+    }
+
+    /// The report as a JSON array for the web perf overlay.
+    pub fn report_json(&self) -> String {
+        let rows: Vec<String> = self
+            .report()
+            .iter()
+            .map(|row| {
+                let system = match row.system {
+                    Some(name) => format!("\"{}\"", name.escape_default()),
+                    None => "null".to_string(),
+                };
+                format!(
+                    "{{\"phase\":\"{}\",\"system\":{},\"samples\":{},\"min\":{:.4},\"avg\":{:.4},\"max\":{:.4}}}",
+                    row.phase, system, row.samples, row.min_ms, row.avg_ms, row.max_ms
+                )
+            })
+            .collect();
+        format!("[{}]", rows.join(","))
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROFILE_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_reports_min_avg_max() {
+        let mut window = TimingWindow::new(4);
+        for ms in [2.0, 4.0, 6.0] {
+            window.push(ms);
+        }
+
+        assert_eq!(window.min(), 2.0);
+        assert_eq!(window.avg(), 4.0);
+        assert_eq!(window.max(), 6.0);
+    }
+
+    #[test]
+    fn window_drops_oldest_samples() {
+        let mut window = TimingWindow::new(2);
+        for ms in [10.0, 1.0, 3.0] {
+            window.push(ms);
+        }
+
+        assert_eq!(window.len(), 2);
+        assert_eq!(window.max(), 3.0);
+        assert_eq!(window.avg(), 2.0);
+    }
+
+    #[test]
+    fn report_groups_systems_under_their_phase() {
+        let mut profiler = Profiler::new(8);
+        profiler.record_system(SystemPhase::Control, 0, "input", 1.0);
+        profiler.record_system(SystemPhase::Resolve, 1, "scoring", 0.5);
+        profiler.record_system(SystemPhase::Resolve, 0, "walls", 0.25);
+        profiler.record_phase(SystemPhase::Resolve, 0.75);
+
+        let labels: Vec<_> = profiler
+            .report()
+            .iter()
+            .map(|row| (row.phase, row.system))
+            .collect();
+
+        assert_eq!(
+            labels,
+            vec![
+                (SystemPhase::Control, Some("input")),
+                (SystemPhase::Resolve, None),
+                (SystemPhase::Resolve, Some("walls")),
+                (SystemPhase::Resolve, Some("scoring")),
+            ]
+        );
+    }
+
+    #[test]
+    fn report_json_is_flat_array() {
+        let mut profiler = Profiler::new(8);
+        profiler.record_phase(SystemPhase::Physics, 1.0);
+        profiler.record_system(SystemPhase::Physics, 0, "integrate_velocity", 1.0);
+
+        assert_eq!(
+            profiler.report_json(),
+            "[{\"phase\":\"Physics\",\"system\":null,\"samples\":1,\"min\":1.0000,\"avg\":1.0000,\"max\":1.0000},\
+{\"phase\":\"Physics\",\"system\":\"integrate_velocity\",\"samples\":1,\"min\":1.0000,\"avg\":1.0000,\"max\":1.0000}]"
+        );
+    }
+}
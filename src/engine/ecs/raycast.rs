@@ -0,0 +1,601 @@
+use super::components::ColliderShape;
+use super::entity::EntityId;
+use super::layers::ALL_LAYERS;
+use super::resources::{EdgeBehavior, FieldEdge};
+use super::world::World;
+use crate::engine::collision::{sweep_circle_aabb, Aabb, Circle};
+use crate::engine::math::Vec2;
+
+/// A ray, or a circle swept along one, to test against the world's colliders.
+/// Reflecting field edges either stop the cast or, while `reflections`
+/// remain, bounce it. Other edges let it leave the field; wrapping edges are
+/// not followed across the seam.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cast {
+    pub x: f32,
+    pub y: f32,
+    /// Unit direction of travel.
+    pub dir_x: f32,
+    pub dir_y: f32,
+    /// Zero for a ray.
+    pub radius: f32,
+    /// Total path length, counting every reflected segment.
+    pub max_distance: f32,
+    /// Layer bits of the colliders the cast can hit.
+    pub mask: u32,
+    pub reflections: u32,
+    /// Collider to pass through, usually the one casting.
+    pub ignore: Option<EntityId>,
+}
+
+impl Cast {
+    /// Ray from `(x, y)` toward `(dir_x, dir_y)`, which need not be unit
+    /// length.
+    pub fn ray(x: f32, y: f32, dir_x: f32, dir_y: f32) -> Self {
+        let length = Vec2::new(dir_x, dir_y).length();
+        let (dir_x, dir_y) = if length > 0.0 {
+            (dir_x / length, dir_y / length)
+        } else {
+            (0.0, 0.0)
+        };
+        Self {
+            x,
+            y,
+            dir_x,
+            dir_y,
+            radius: 0.0,
+            max_distance: f32::INFINITY,
+            mask: ALL_LAYERS,
+            reflections: 0,
+            ignore: None,
+        }
+    }
+
+    /// Circle of `radius` centered on `(x, y)`, swept toward `(dir_x, dir_y)`.
+    pub fn circle(x: f32, y: f32, dir_x: f32, dir_y: f32, radius: f32) -> Self {
+        Self {
+            radius,
+            ..Self::ray(x, y, dir_x, dir_y)
+        }
+    }
+
+    pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    pub fn with_mask(mut self, mask: u32) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    pub fn with_reflections(mut self, reflections: u32) -> Self {
+        self.reflections = reflections;
+        self
+    }
+
+    pub fn ignoring(mut self, entity: EntityId) -> Self {
+        self.ignore = Some(entity);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CastTarget {
+    Entity(EntityId),
+    Edge(FieldEdge),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CastHit {
+    pub target: CastTarget,
+    /// Path length from the cast's origin, including earlier reflections.
+    pub distance: f32,
+    /// Where the cast touched the surface. A circle's center is then at
+    /// `point + normal * radius`.
+    pub point_x: f32,
+    pub point_y: f32,
+    /// Unit surface normal, pointing back toward the cast.
+    pub normal_x: f32,
+    pub normal_y: f32,
+}
+
+impl World {
+    /// First thing the cast hits, following wall reflections; a reflecting
+    /// edge is only returned once the reflections run out.
+    pub fn cast(&self, cast: &Cast) -> Option<CastHit> {
+        self.cast_path(cast).pop()
+    }
+
+    /// Every hit along the cast: each edge it reflected off, then whatever
+    /// stopped it, if anything did. Colliders are looked up in
+    /// `spatial_hash`, so they're found where `update_spatial_hash` last put
+    /// them.
+    pub fn cast_path(&self, cast: &Cast) -> Vec<CastHit> {
+        let mut hits = Vec::new();
+        if cast.dir_x == 0.0 && cast.dir_y == 0.0 {
+            return hits;
+        }
+        let (mut x, mut y) = (cast.x, cast.y);
+        let (mut dir_x, mut dir_y) = (cast.dir_x, cast.dir_y);
+        let mut travelled = 0.0;
+        let mut reflections = cast.reflections;
+        loop {
+            let remaining = cast.max_distance - travelled;
+            let mut segment = Segment {
+                x,
+                y,
+                dir_x,
+                dir_y,
+                length: remaining,
+            };
+            let wall = self.first_wall_hit(cast.radius, &segment);
+            // Nothing past the wall can be reached on this leg.
+            if let Some(wall) = wall {
+                segment.length = wall.distance;
+            }
+            let collider = self.first_collider_hit(cast, &segment);
+            if let Some(hit) = collider {
+                if wall.is_none_or(|wall| hit.distance <= wall.distance) {
+                    hits.push(CastHit {
+                        distance: travelled + hit.distance,
+                        ..hit
+                    });
+                    return hits;
+                }
+            }
+            let Some(wall) = wall else {
+                return hits;
+            };
+
+            hits.push(CastHit {
+                distance: travelled + wall.distance,
+                ..wall
+            });
+            if reflections == 0 {
+                return hits;
+            }
+            reflections -= 1;
+            travelled += wall.distance;
+            x += dir_x * wall.distance;
+            y += dir_y * wall.distance;
+            if wall.normal_x != 0.0 {
+                dir_x = -dir_x;
+            }
+            if wall.normal_y != 0.0 {
+                dir_y = -dir_y;
+            }
+        }
+    }
+
+    fn first_collider_hit(&self, cast: &Cast, segment: &Segment) -> Option<CastHit> {
+        let mut best: Option<CastHit> = None;
+        let candidates = self
+            .spatial_hash
+            .query_aabb(&segment.bounds(cast.radius), cast.mask);
+        for entity in candidates {
+            let idx = entity.0 as usize;
+            let (Some(Some(collider)), Some(Some(transform))) =
+                (self.colliders.get(idx), self.transforms.get(idx))
+            else {
+                continue;
+            };
+            if collider.layer & cast.mask == 0 || cast.ignore == Some(entity) {
+                continue;
+            }
+            let (cx, cy) = (
+                transform.position.x() + collider.offset_x,
+                transform.position.y() + collider.offset_y,
+            );
+            let hit = match collider.shape {
+                ColliderShape::Circle { radius } => segment.against_circle(cx, cy, radius, cast),
+                ColliderShape::Aabb {
+                    half_width,
+                    half_height,
+                } => segment.against_aabb(Aabb::new(cx, cy, half_width, half_height), cast),
+            };
+            let Some(hit) = hit else {
+                continue;
+            };
+            // Candidates come in id order, so ties keep the lowest id.
+            if best.is_none_or(|best| hit.distance < best.distance) {
+                best = Some(hit.toward(CastTarget::Entity(entity)));
+            }
+        }
+        best
+    }
+
+    /// Nearest reflecting edge the cast is heading for, within the segment.
+    /// Reaching two edges together is a corner hit, with a diagonal normal.
+    fn first_wall_hit(&self, radius: f32, segment: &Segment) -> Option<CastHit> {
+        let field = &self.field;
+        let walls = [
+            (
+                FieldEdge::Left,
+                field.edges.left,
+                -segment.dir_x,
+                segment.x - radius,
+                (1.0, 0.0),
+            ),
+            (
+                FieldEdge::Right,
+                field.edges.right,
+                segment.dir_x,
+                field.width - radius - segment.x,
+                (-1.0, 0.0),
+            ),
+            (
+                FieldEdge::Top,
+                field.edges.top,
+                -segment.dir_y,
+                segment.y - radius,
+                (0.0, 1.0),
+            ),
+            (
+                FieldEdge::Bottom,
+                field.edges.bottom,
+                segment.dir_y,
+                field.height - radius - segment.y,
+                (0.0, -1.0),
+            ),
+        ];
+        let reached: Vec<_> = walls
+            .into_iter()
+            .filter(|(_, behavior, speed, _, _)| *behavior == EdgeBehavior::Reflect && *speed > 0.0)
+            .map(|(edge, _, speed, gap, normal)| (edge, (gap / speed).max(0.0), normal))
+            .filter(|(_, distance, _)| *distance <= segment.length)
+            .collect();
+        let &(edge, distance, (mut normal_x, mut normal_y)) =
+            reached.iter().min_by(|a, b| a.1.total_cmp(&b.1))?;
+        let corner = reached.iter().find(|(other, other_distance, _)| {
+            *other != edge && *other_distance - distance <= CORNER_TOLERANCE
+        });
+        if let Some((_, _, (other_x, other_y))) = corner {
+            normal_x = (normal_x + other_x) * std::f32::consts::FRAC_1_SQRT_2;
+            normal_y = (normal_y + other_y) * std::f32::consts::FRAC_1_SQRT_2;
+        }
+        Some(
+            SurfaceHit {
+                distance,
+                point_x: segment.x + segment.dir_x * distance - normal_x * radius,
+                point_y: segment.y + segment.dir_y * distance - normal_y * radius,
+                normal_x,
+                normal_y,
+            }
+            .toward(CastTarget::Edge(edge)),
+        )
+    }
+}
+
+/// Where a segment meets a surface, before it's known what the surface is.
+#[derive(Clone, Copy)]
+struct SurfaceHit {
+    distance: f32,
+    point_x: f32,
+    point_y: f32,
+    normal_x: f32,
+    normal_y: f32,
+}
+
+impl SurfaceHit {
+    fn toward(self, target: CastTarget) -> CastHit {
+        CastHit {
+            target,
+            distance: self.distance,
+            point_x: self.point_x,
+            point_y: self.point_y,
+            normal_x: self.normal_x,
+            normal_y: self.normal_y,
+        }
+    }
+}
+
+/// One straight leg of a cast.
+struct Segment {
+    x: f32,
+    y: f32,
+    dir_x: f32,
+    dir_y: f32,
+    length: f32,
+}
+
+impl Segment {
+    /// Area the leg sweeps, padded by the cast's radius.
+    fn bounds(&self, radius: f32) -> Aabb {
+        let length = self.length.min(MAX_SWEEP);
+        let (end_x, end_y) = (self.x + self.dir_x * length, self.y + self.dir_y * length);
+        Aabb::from_min_max(
+            self.x.min(end_x) - radius,
+            self.y.min(end_y) - radius,
+            self.x.max(end_x) + radius,
+            self.y.max(end_y) + radius,
+        )
+    }
+
+    /// Casts that start inside a collider pass out of it without a hit.
+    fn against_circle(&self, cx: f32, cy: f32, radius: f32, cast: &Cast) -> Option<SurfaceHit> {
+        let reach = radius + cast.radius;
+        let (ox, oy) = (self.x - cx, self.y - cy);
+        let c = ox * ox + oy * oy - reach * reach;
+        if c < 0.0 {
+            return None;
+        }
+        let b = ox * self.dir_x + oy * self.dir_y;
+        let discriminant = b * b - c;
+        if b > 0.0 || discriminant < 0.0 {
+            return None;
+        }
+        let distance = -b - discriminant.sqrt();
+        if distance > self.length {
+            return None;
+        }
+        let (hx, hy) = (
+            self.x + self.dir_x * distance,
+            self.y + self.dir_y * distance,
+        );
+        let (normal_x, normal_y) = if reach > 0.0 {
+            ((hx - cx) / reach, (hy - cy) / reach)
+        } else {
+            (-self.dir_x, -self.dir_y)
+        };
+        Some(SurfaceHit {
+            distance,
+            point_x: hx - normal_x * cast.radius,
+            point_y: hy - normal_y * cast.radius,
+            normal_x,
+            normal_y,
+        })
+    }
+
+    fn against_aabb(&self, rect: Aabb, cast: &Cast) -> Option<SurfaceHit> {
+        if cast.radius > 0.0 {
+            return self.sweep_circle(rect, cast.radius);
+        }
+        let (enter_x, exit_x) = slab(self.x, self.dir_x, rect.min_x(), rect.max_x())?;
+        let (enter_y, exit_y) = slab(self.y, self.dir_y, rect.min_y(), rect.max_y())?;
+        let enter = enter_x.max(enter_y);
+        let exit = exit_x.min(exit_y);
+        if enter > exit || enter < 0.0 || enter > self.length {
+            return None;
+        }
+        let (normal_x, normal_y) = if enter_x > enter_y {
+            (-self.dir_x.signum(), 0.0)
+        } else {
+            (0.0, -self.dir_y.signum())
+        };
+        Some(SurfaceHit {
+            distance: enter,
+            point_x: self.x + self.dir_x * enter,
+            point_y: self.y + self.dir_y * enter,
+            normal_x,
+            normal_y,
+        })
+    }
+
+    fn sweep_circle(&self, rect: Aabb, radius: f32) -> Option<SurfaceHit> {
+        // Sweep over a finite stretch; beyond the field nothing is hit anyway.
+        let length = self.length.min(MAX_SWEEP);
+        let circle = Circle {
+            x: self.x,
+            y: self.y,
+            radius,
+        };
+        let motion = (self.dir_x * length, self.dir_y * length);
+        let hit = sweep_circle_aabb(circle, motion, rect, (0.0, 0.0))?;
+        // Overlapping at the start counts as being inside.
+        if hit.time == 0.0 {
+            return None;
+        }
+        Some(SurfaceHit {
+            distance: hit.time * length,
+            point_x: hit.point_x,
+            point_y: hit.point_y,
+            normal_x: hit.normal_x,
+            normal_y: hit.normal_y,
+        })
+    }
+}
+
+/// Longest single leg a circle cast sweeps or gathers candidates along.
+const MAX_SWEEP: f32 = 1.0e6;
+
+/// How much later a second edge can be reached and still count as the same
+/// corner hit.
+const CORNER_TOLERANCE: f32 = 1.0e-4;
+
+/// Entry and exit distances of a ray against `[min, max]` on one axis.
+fn slab(origin: f32, dir: f32, min: f32, max: f32) -> Option<(f32, f32)> {
+    if dir == 0.0 {
+        return (origin >= min && origin <= max).then_some((f32::NEG_INFINITY, f32::INFINITY));
+    }
+    let t1 = (min - origin) / dir;
+    let t2 = (max - origin) / dir;
+    Some((t1.min(t2), t1.max(t2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ecs::components::{Collider, Transform};
+    use crate::engine::ecs::resources::FieldEdges;
+    use crate::engine::ecs::spatial_hash::update_spatial_hash;
+
+    const EPS: f32 = 1e-4;
+
+    fn approx(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() <= EPS,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn body(world: &mut World, x: f32, y: f32, collider: Collider) -> EntityId {
+        let entity = world.spawn();
+        world.set_transform(entity, Transform::at(x, y));
+        world.set_collider(entity, collider);
+        update_spatial_hash(world, 0.0);
+        entity
+    }
+
+    #[test]
+    fn ray_reports_the_nearest_collider() {
+        let mut world = World::new(800.0, 600.0);
+        body(&mut world, 300.0, 100.0, Collider::aabb(10.0, 20.0));
+        let near = body(&mut world, 200.0, 100.0, Collider::aabb(10.0, 20.0));
+
+        let hit = world.cast(&Cast::ray(50.0, 105.0, 1.0, 0.0)).unwrap();
+
+        assert_eq!(hit.target, CastTarget::Entity(near));
+        approx(hit.distance, 140.0);
+        assert_eq!((hit.point_x, hit.point_y), (190.0, 105.0));
+        assert_eq!((hit.normal_x, hit.normal_y), (-1.0, 0.0));
+    }
+
+    #[test]
+    fn ray_hits_circles_on_their_surface() {
+        let mut world = World::new(800.0, 600.0);
+        let target = body(&mut world, 100.0, 200.0, Collider::circle(10.0));
+
+        let hit = world.cast(&Cast::ray(100.0, 50.0, 0.0, 3.0)).unwrap();
+
+        assert_eq!(hit.target, CastTarget::Entity(target));
+        approx(hit.distance, 140.0);
+        approx(hit.point_y, 190.0);
+        assert_eq!((hit.normal_x, hit.normal_y), (0.0, -1.0));
+    }
+
+    #[test]
+    fn circle_cast_stops_its_center_a_radius_short() {
+        let mut world = World::new(800.0, 600.0);
+        let wall = body(&mut world, 200.0, 100.0, Collider::aabb(10.0, 50.0));
+        let ball = body(&mut world, 20.0, 100.0, Collider::circle(5.0));
+
+        let cast = Cast::circle(20.0, 100.0, 1.0, 0.0, 5.0).ignoring(ball);
+        let hit = world.cast(&cast).unwrap();
+
+        assert_eq!(hit.target, CastTarget::Entity(wall));
+        approx(hit.distance, 165.0);
+        approx(hit.point_x, 190.0);
+        approx(hit.point_x + hit.normal_x * 5.0, 185.0);
+    }
+
+    #[test]
+    fn masks_skip_colliders_on_other_layers() {
+        let mut world = World::new(800.0, 600.0);
+        world.field.edges = FieldEdges::all(EdgeBehavior::PassThrough);
+        body(
+            &mut world,
+            200.0,
+            100.0,
+            Collider::circle(10.0).on_layer(0b10),
+        );
+        let far = body(
+            &mut world,
+            400.0,
+            100.0,
+            Collider::circle(10.0).on_layer(0b01),
+        );
+
+        let hit = world.cast(&Cast::ray(0.0, 100.0, 1.0, 0.0).with_mask(0b01));
+        assert_eq!(hit.unwrap().target, CastTarget::Entity(far));
+
+        let miss = world.cast(&Cast::ray(0.0, 100.0, 1.0, 0.0).with_mask(0b100));
+        assert_eq!(miss, None);
+    }
+
+    #[test]
+    fn max_distance_limits_the_cast() {
+        let mut world = World::new(800.0, 600.0);
+        body(&mut world, 200.0, 100.0, Collider::circle(10.0));
+
+        let cast = Cast::ray(0.0, 100.0, 1.0, 0.0).with_max_distance(150.0);
+
+        assert_eq!(world.cast(&cast), None);
+    }
+
+    #[test]
+    fn reflections_follow_the_walls_to_the_target() {
+        let mut world = World::new(200.0, 100.0);
+        let paddle = body(&mut world, 190.0, 20.0, Collider::aabb(5.0, 10.0));
+
+        // Down-right at 45 degrees, off the bottom at (100, 100), then up into
+        // the paddle's left face before reaching the top-right corner.
+        let cast = Cast::ray(50.0, 50.0, 1.0, 1.0).with_reflections(3);
+        let path = world.cast_path(&cast);
+
+        assert_eq!(path.len(), 2);
+        assert_eq!(path[0].target, CastTarget::Edge(FieldEdge::Bottom));
+        approx(path[0].point_x, 100.0);
+        approx(path[0].point_y, 100.0);
+        assert_eq!(path[1].target, CastTarget::Entity(paddle));
+        approx(path[1].point_x, 185.0);
+        approx(path[1].point_y, 15.0);
+        approx(path[1].distance, 135.0 * std::f32::consts::SQRT_2);
+    }
+
+    #[test]
+    fn walls_stop_the_cast_once_reflections_run_out() {
+        let mut world = World::new(200.0, 100.0);
+        body(&mut world, 190.0, 50.0, Collider::aabb(5.0, 10.0));
+
+        let hit = world
+            .cast(&Cast::circle(50.0, 50.0, 1.0, 1.0, 4.0))
+            .unwrap();
+
+        assert_eq!(hit.target, CastTarget::Edge(FieldEdge::Bottom));
+        approx(hit.point_y, 100.0);
+        approx(hit.distance, 46.0 * std::f32::consts::SQRT_2);
+    }
+
+    #[test]
+    fn open_edges_let_the_cast_escape() {
+        let mut world = World::new(200.0, 100.0);
+        world.field.edges = FieldEdges::default().with(FieldEdge::Bottom, EdgeBehavior::Exit);
+
+        let path = world.cast_path(&Cast::ray(50.0, 50.0, 0.0, 1.0).with_reflections(2));
+
+        assert!(path.is_empty());
+    }
+
+    #[test]
+    fn corner_hits_reflect_both_axes_for_one_reflection() {
+        let world = World::new(200.0, 100.0);
+
+        // Straight into the bottom-right corner, back out along the
+        // diagonal, and up to the top edge.
+        let cast = Cast::ray(150.0, 50.0, 1.0, 1.0).with_reflections(1);
+        let path = world.cast_path(&cast);
+
+        assert_eq!(path.len(), 2);
+        approx(path[0].point_x, 200.0);
+        approx(path[0].point_y, 100.0);
+        approx(path[0].normal_x, -std::f32::consts::FRAC_1_SQRT_2);
+        approx(path[0].normal_y, -std::f32::consts::FRAC_1_SQRT_2);
+        assert_eq!(path[1].target, CastTarget::Edge(FieldEdge::Top));
+        approx(path[1].point_x, 100.0);
+        approx(path[1].point_y, 0.0);
+        approx(path[1].distance, 150.0 * std::f32::consts::SQRT_2);
+    }
+
+    #[test]
+    fn colliders_are_found_through_the_spatial_hash() {
+        let mut world = World::new(800.0, 600.0);
+        let target = world.spawn();
+        world.set_transform(target, Transform::at(200.0, 100.0));
+        world.set_collider(target, Collider::circle(10.0));
+        let cast = Cast::ray(50.0, 100.0, 1.0, 0.0);
+
+        assert_eq!(
+            world.cast(&cast).unwrap().target,
+            CastTarget::Edge(FieldEdge::Right)
+        );
+
+        update_spatial_hash(&mut world, 0.0);
+        assert_eq!(
+            world.cast(&cast).unwrap().target,
+            CastTarget::Entity(target)
+        );
+    }
+}
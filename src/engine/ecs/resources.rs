@@ -1,3 +1,8 @@
+use super::components::Material;
+use super::entity::EntityId;
+use crate::engine::collision::Aabb;
+use crate::engine::math::Vec2;
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct InputBits {
     pub bits: u32,
@@ -7,4 +12,326 @@ pub struct InputBits {
 pub struct FieldBounds {
     pub width: f32,
     pub height: f32,
+    pub edges: FieldEdges,
+    /// Surface of the reflecting edges.
+    pub wall_material: Material,
+}
+
+/// Most ghost copies a body can need: across a side, across the top or
+/// bottom, and across the corner.
+pub const MAX_GHOSTS: usize = 3;
+/// Floats written by `FieldBounds::write_ghosts`: a count, then x/y pairs.
+pub const GHOST_SNAPSHOT_LEN: usize = 1 + 2 * MAX_GHOSTS;
+
+impl FieldBounds {
+    /// Field with every edge reflecting.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            edges: FieldEdges::default(),
+            wall_material: Material::ELASTIC,
+        }
+    }
+
+    /// True if both side edges wrap, making the field toroidal horizontally.
+    pub fn wraps_x(&self) -> bool {
+        self.edges.left == EdgeBehavior::Wrap && self.edges.right == EdgeBehavior::Wrap
+    }
+
+    /// True if the top and bottom edges both wrap.
+    pub fn wraps_y(&self) -> bool {
+        self.edges.top == EdgeBehavior::Wrap && self.edges.bottom == EdgeBehavior::Wrap
+    }
+
+    /// Shortest displacement from `from` to `to`, going across the seam on
+    /// wrapping axes when that is shorter.
+    pub fn wrapped_delta(&self, from: (f32, f32), to: (f32, f32)) -> (f32, f32) {
+        let shortest = |delta: f32, extent: f32, wraps: bool| {
+            if wraps {
+                delta - extent * (delta / extent).round()
+            } else {
+                delta
+            }
+        };
+        (
+            shortest(to.0 - from.0, self.width, self.wraps_x()),
+            shortest(to.1 - from.1, self.height, self.wraps_y()),
+        )
+    }
+
+    pub fn wrapped_distance(&self, from: (f32, f32), to: (f32, f32)) -> f32 {
+        let (dx, dy) = self.wrapped_delta(from, to);
+        Vec2::new(dx, dy).length()
+    }
+
+    /// Moves a point back inside the field on wrapping axes.
+    pub fn wrap_point(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            if self.wraps_x() {
+                x.rem_euclid(self.width)
+            } else {
+                x
+            },
+            if self.wraps_y() {
+                y.rem_euclid(self.height)
+            } else {
+                y
+            },
+        )
+    }
+
+    /// Translations under which `area` may meet something across a seam:
+    /// always `(0, 0)` first, then one per wrapping edge `area` comes within
+    /// `margin` of, plus the corner when two apply.
+    pub fn seam_offsets(&self, area: &Aabb, margin: f32) -> Vec<(f32, f32)> {
+        let axis = |min: f32, max: f32, extent: f32, wraps: bool| {
+            let mut offsets = vec![0.0];
+            if wraps && min < margin {
+                offsets.push(extent);
+            }
+            if wraps && max > extent - margin {
+                offsets.push(-extent);
+            }
+            offsets
+        };
+        let xs = axis(area.min_x(), area.max_x(), self.width, self.wraps_x());
+        let ys = axis(area.min_y(), area.max_y(), self.height, self.wraps_y());
+        ys.iter()
+            .flat_map(|&oy| xs.iter().map(move |&ox| (ox, oy)))
+            .collect()
+    }
+
+    /// Where copies of a body must be drawn so the part hanging over a
+    /// wrapping edge shows up on the opposite side.
+    pub fn ghosts(&self, x: f32, y: f32, half_width: f32, half_height: f32) -> Vec<(f32, f32)> {
+        let area = Aabb::new(x, y, half_width, half_height);
+        self.seam_offsets(&area, 0.0)
+            .into_iter()
+            .skip(1)
+            .map(|(ox, oy)| (x + ox, y + oy))
+            .collect()
+    }
+
+    /// Writes `ghosts` for a snapshot: the count, then up to `MAX_GHOSTS`
+    /// x/y pairs, zero-filled. `out` must hold `GHOST_SNAPSHOT_LEN` floats.
+    /// A body spanning both seams of an axis has more ghosts than fit; the
+    /// count is capped so readers never walk past the block.
+    pub fn write_ghosts(&self, x: f32, y: f32, half_width: f32, half_height: f32, out: &mut [f32]) {
+        let out = &mut out[..GHOST_SNAPSHOT_LEN];
+        out.fill(0.0);
+        let ghosts = self.ghosts(x, y, half_width, half_height);
+        out[0] = ghosts.len().min(MAX_GHOSTS) as f32;
+        for (slot, (gx, gy)) in out[1..].chunks_exact_mut(2).zip(ghosts) {
+            slot[0] = gx;
+            slot[1] = gy;
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// What `bounce_in_field` does with a body that reaches an edge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeBehavior {
+    /// Clamp inside the field and reverse velocity on that axis.
+    Reflect,
+    /// Leave the body alone.
+    PassThrough,
+    /// Once the center crosses, move it to the opposite edge.
+    Wrap,
+    /// Leave the body alone and report it in `World::field_exits`.
+    Exit,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldEdges {
+    pub left: EdgeBehavior,
+    pub right: EdgeBehavior,
+    pub top: EdgeBehavior,
+    pub bottom: EdgeBehavior,
+}
+
+impl Default for FieldEdges {
+    fn default() -> Self {
+        Self::all(EdgeBehavior::Reflect)
+    }
+}
+
+impl FieldEdges {
+    pub const fn all(behavior: EdgeBehavior) -> Self {
+        Self {
+            left: behavior,
+            right: behavior,
+            top: behavior,
+            bottom: behavior,
+        }
+    }
+
+    pub fn with(mut self, edge: FieldEdge, behavior: EdgeBehavior) -> Self {
+        *self.get_mut(edge) = behavior;
+        self
+    }
+
+    pub fn get(&self, edge: FieldEdge) -> EdgeBehavior {
+        match edge {
+            FieldEdge::Left => self.left,
+            FieldEdge::Right => self.right,
+            FieldEdge::Top => self.top,
+            FieldEdge::Bottom => self.bottom,
+        }
+    }
+
+    pub fn get_mut(&mut self, edge: FieldEdge) -> &mut EdgeBehavior {
+        match edge {
+            FieldEdge::Left => &mut self.left,
+            FieldEdge::Right => &mut self.right,
+            FieldEdge::Top => &mut self.top,
+            FieldEdge::Bottom => &mut self.bottom,
+        }
+    }
+}
+
+/// A body touching or past an `Exit` edge, reported by `bounce_in_field`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldExit {
+    pub entity: EntityId,
+    pub edge: FieldEdge,
+}
+
+pub const DEFAULT_MAX_SUBSTEPS: u32 = 16;
+pub const MAX_SUBSTEPS_LIMIT: u32 = 64;
+
+/// Limits for adaptive physics sub-stepping. Bodies may move at most the
+/// smallest collider size per sub-step, so nothing can skip over a collider.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SubstepConfig {
+    /// Upper bound on sub-steps per frame, `1..=MAX_SUBSTEPS_LIMIT`.
+    pub max_substeps: u32,
+    /// Thinnest collider the engine cannot see (paddles, bricks), in pixels.
+    /// Zero means only `BounceCollider` sizes are considered.
+    pub min_collider_size: f32,
+}
+
+impl Default for SubstepConfig {
+    fn default() -> Self {
+        Self {
+            max_substeps: DEFAULT_MAX_SUBSTEPS,
+            min_collider_size: 0.0,
+        }
+    }
+}
+
+/// Seedable simulation RNG (SplitMix64). Its whole state is one `u64`, so it
+/// can be saved, restored and folded into checksums.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimRng {
+    state: u64,
+}
+
+impl SimRng {
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+}
+
+impl rand::RngCore for SimRng {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn torus() -> FieldBounds {
+        let mut field = FieldBounds::new(100.0, 50.0);
+        field.edges = FieldEdges::all(EdgeBehavior::Wrap);
+        field
+    }
+
+    #[test]
+    fn wrapped_delta_takes_the_short_way_across_the_seam() {
+        let field = torus();
+
+        assert_eq!(field.wrapped_delta((95.0, 25.0), (5.0, 25.0)), (10.0, 0.0));
+        assert_eq!(field.wrapped_delta((10.0, 2.0), (10.0, 48.0)), (0.0, -4.0));
+        assert_eq!(field.wrapped_distance((10.0, 10.0), (40.0, 10.0)), 30.0);
+    }
+
+    #[test]
+    fn only_axes_with_both_edges_wrapping_are_toroidal() {
+        let mut field = torus();
+        field.edges.right = EdgeBehavior::Reflect;
+
+        assert!(!field.wraps_x());
+        assert!(field.wraps_y());
+        assert_eq!(field.wrapped_delta((95.0, 0.0), (5.0, 0.0)).0, -90.0);
+    }
+
+    #[test]
+    fn corner_bodies_get_three_ghosts() {
+        let field = torus();
+
+        let ghosts = field.ghosts(98.0, 1.0, 5.0, 5.0);
+
+        assert_eq!(ghosts, vec![(-2.0, 1.0), (98.0, 51.0), (-2.0, 51.0)]);
+    }
+
+    #[test]
+    fn ghost_snapshot_block_holds_count_and_pairs() {
+        let field = torus();
+        let mut out = [9.0; GHOST_SNAPSHOT_LEN];
+
+        field.write_ghosts(50.0, 48.0, 4.0, 4.0, &mut out);
+
+        assert_eq!(out, [1.0, 50.0, -2.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn ghost_count_is_capped_for_bodies_wider_than_the_field() {
+        let field = torus();
+        let mut out = [9.0; GHOST_SNAPSHOT_LEN + 2];
+
+        field.write_ghosts(50.0, 50.0, 60.0, 60.0, &mut out);
+
+        assert_eq!(field.ghosts(50.0, 50.0, 60.0, 60.0).len(), 8);
+        assert_eq!(out[0], MAX_GHOSTS as f32);
+        assert_eq!(out[GHOST_SNAPSHOT_LEN..], [9.0, 9.0]);
+    }
 }
@@ -1,8 +1,30 @@
+use std::any::Any;
+use std::fmt;
+
+use crate::engine::clock::now_ms;
+
+use super::profiler::Profiler;
+use super::state::{in_state, StateDriver, StateHooks};
 use super::world::World;
 
 pub type SystemFn = fn(&mut World, f32);
+pub type RunCondition = Box<dyn Fn(&World) -> bool>;
+type BoxedSystem = Box<dyn FnMut(&mut World, f32)>;
 
-const PHASE_COUNT: usize = 3;
+/// Upper bound on fixed ticks in one run, so a long frame cannot snowball
+/// into ever more catch-up work. Engine steps are already capped by
+/// `clamp_dt`, which keeps real rates well under this.
+pub const MAX_FIXED_TICKS: u32 = 32;
+
+/// Picks how many sub-steps a frame of `dt` seconds needs.
+pub type SubstepFn = fn(&World, f32) -> u32;
+
+/// A run of consecutive phases repeated several times per step.
+struct SubstepGroup {
+    first: SystemPhase,
+    last: SystemPhase,
+    count: SubstepFn,
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SystemPhase {
@@ -10,29 +32,201 @@ pub enum SystemPhase {
     Control,
     /// Engine-owned integration and other shared physics helpers.
     Physics,
-    /// Post-integration consequences (collisions, scoring, timers).
+    /// Post-integration consequences (collisions, scoring).
     Resolve,
+    /// Game-defined stage, placed with [`Schedule::with_phase_before`] or
+    /// [`Schedule::with_phase_after`].
+    Custom(&'static str),
 }
 
 impl SystemPhase {
-    const ORDERED: [SystemPhase; PHASE_COUNT] = [
+    /// Engine-owned phases every schedule starts with, in run order.
+    pub const BUILT_IN: [SystemPhase; 3] = [
         SystemPhase::Control,
         SystemPhase::Physics,
         SystemPhase::Resolve,
     ];
+}
+
+impl fmt::Display for SystemPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SystemPhase::Control => write!(f, "Control"),
+            SystemPhase::Physics => write!(f, "Physics"),
+            SystemPhase::Resolve => write!(f, "Resolve"),
+            SystemPhase::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Run condition that checks a predicate against resource `T`.
+pub fn resource_is<T: Any>(predicate: impl Fn(&T) -> bool + 'static) -> impl Fn(&World) -> bool {
+    move |world| predicate(world.resource::<T>())
+}
 
-    fn index(self) -> usize {
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScheduleError {
+    UnknownLabel {
+        system: &'static str,
+        label: &'static str,
+        phase: SystemPhase,
+    },
+    AmbiguousLabel {
+        label: &'static str,
+        phase: SystemPhase,
+    },
+    Cycle {
+        phase: SystemPhase,
+        systems: Vec<&'static str>,
+    },
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            SystemPhase::Control => 0,
-            SystemPhase::Physics => 1,
-            SystemPhase::Resolve => 2,
+            ScheduleError::UnknownLabel {
+                system,
+                label,
+                phase,
+            } => write!(
+                f,
+                "system `{}` is ordered against `{}`, but no system with that name is in {}",
+                system, label, phase
+            ),
+            ScheduleError::AmbiguousLabel { label, phase } => write!(
+                f,
+                "ordering label `{}` matches more than one system in {}",
+                label, phase
+            ),
+            ScheduleError::Cycle { phase, systems } => write!(
+                f,
+                "ordering cycle in {} between: {}",
+                phase,
+                systems.join(", ")
+            ),
         }
     }
 }
 
-#[derive(Clone, Default)]
+impl std::error::Error for ScheduleError {}
+
+/// Default system name: the last path segment of the function's type name.
+fn system_name<F>() -> &'static str {
+    let full = std::any::type_name::<F>();
+    full.rsplit("::").next().unwrap_or(full)
+}
+
+/// Fixed timestep for a system or phase running at its own rate. The banked
+/// time lives in `World::fixed_rate_accumulators` at `slot`, so saves and
+/// checksums include it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct FixedRate {
+    period: f32,
+    slot: usize,
+}
+
+impl FixedRate {
+    fn new(hz: f32, slot: usize) -> Self {
+        assert!(
+            hz.is_finite() && hz > 0.0,
+            "tick rate must be a positive number of hertz, got {}",
+            hz
+        );
+        Self {
+            period: 1.0 / hz,
+            slot,
+        }
+    }
+
+    /// Banks `dt` and returns how many fixed ticks are now due.
+    fn advance(&self, world: &mut World, dt: f32) -> u32 {
+        let accumulators = &mut world.fixed_rate_accumulators;
+        if accumulators.len() <= self.slot {
+            accumulators.resize(self.slot + 1, 0.0);
+        }
+        let accumulator = &mut accumulators[self.slot];
+        *accumulator += dt;
+        let mut ticks = 0;
+        while *accumulator >= self.period && ticks < MAX_FIXED_TICKS {
+            *accumulator -= self.period;
+            ticks += 1;
+        }
+        if ticks == MAX_FIXED_TICKS {
+            *accumulator = accumulator.min(self.period);
+        }
+        ticks
+    }
+}
+
+struct ScheduledSystem {
+    name: &'static str,
+    system: BoxedSystem,
+    conditions: Vec<RunCondition>,
+    before: Vec<&'static str>,
+    after: Vec<&'static str>,
+    rate: Option<FixedRate>,
+    enabled: bool,
+}
+
+impl ScheduledSystem {
+    fn should_run(&self, world: &World) -> bool {
+        self.enabled && self.conditions.iter().all(|condition| condition(world))
+    }
+}
+
+/// One scheduled system as reported by [`Schedule::systems`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SystemInfo {
+    pub phase: SystemPhase,
+    pub name: &'static str,
+    pub enabled: bool,
+}
+
+struct NamedSystem {
+    name: &'static str,
+    system: BoxedSystem,
+}
+
+struct PhaseSystems {
+    phase: SystemPhase,
+    systems: Vec<ScheduledSystem>,
+    rate: Option<FixedRate>,
+}
+
 pub struct Schedule {
-    systems: [Vec<SystemFn>; PHASE_COUNT],
+    phases: Vec<PhaseSystems>,
+    last_added: Option<SystemPhase>,
+    states: Vec<Box<dyn StateDriver>>,
+    needs_build: bool,
+    startup: Vec<NamedSystem>,
+    started: bool,
+    one_shots: Vec<NamedSystem>,
+    substeps: Option<SubstepGroup>,
+    /// Accumulator slots handed out so far, in the order rates were added.
+    rate_slots: usize,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            phases: SystemPhase::BUILT_IN
+                .into_iter()
+                .map(|phase| PhaseSystems {
+                    phase,
+                    systems: Vec::new(),
+                    rate: None,
+                })
+                .collect(),
+            last_added: None,
+            states: Vec::new(),
+            needs_build: false,
+            startup: Vec::new(),
+            started: false,
+            one_shots: Vec::new(),
+            substeps: None,
+            rate_slots: 0,
+        }
+    }
 }
 
 impl Schedule {
@@ -40,22 +234,521 @@ impl Schedule {
         Default::default()
     }
 
-    pub fn with_system(self, system: SystemFn) -> Self {
+    fn phase_position(&self, phase: SystemPhase) -> Option<usize> {
+        self.phases.iter().position(|entry| entry.phase == phase)
+    }
+
+    fn phase_systems(&mut self, phase: SystemPhase) -> &mut Vec<ScheduledSystem> {
+        let position = self.phase_position(phase).unwrap_or_else(|| {
+            panic!(
+                "phase {} is not in this schedule; add it with with_phase_before/after",
+                phase
+            )
+        });
+        &mut self.phases[position].systems
+    }
+
+    fn insert_phase(mut self, anchor: SystemPhase, phase: SystemPhase, offset: usize) -> Self {
+        assert!(
+            self.phase_position(phase).is_none(),
+            "phase {} is already in this schedule",
+            phase
+        );
+        let anchor_position = self
+            .phase_position(anchor)
+            .unwrap_or_else(|| panic!("anchor phase {} is not in this schedule", anchor));
+        self.phases.insert(
+            anchor_position + offset,
+            PhaseSystems {
+                phase,
+                systems: Vec::new(),
+                rate: None,
+            },
+        );
+        self
+    }
+
+    /// Adds a game-defined phase that runs immediately before `anchor`.
+    pub fn with_phase_before(self, anchor: SystemPhase, phase: SystemPhase) -> Self {
+        self.insert_phase(anchor, phase, 0)
+    }
+
+    /// Adds a game-defined phase that runs immediately after `anchor`.
+    pub fn with_phase_after(self, anchor: SystemPhase, phase: SystemPhase) -> Self {
+        self.insert_phase(anchor, phase, 1)
+    }
+
+    /// Phases in run order, built-in and game-defined.
+    pub fn phases(&self) -> Vec<SystemPhase> {
+        self.phases.iter().map(|entry| entry.phase).collect()
+    }
+
+    pub fn with_system<F: FnMut(&mut World, f32) + 'static>(self, system: F) -> Self {
         self.with_system_in_phase(SystemPhase::Control, system)
     }
 
-    pub fn with_system_in_phase(mut self, phase: SystemPhase, system: SystemFn) -> Self {
-        self.systems[phase.index()].push(system);
+    /// Adds `system` to `phase`, named after its function unless renamed with
+    /// [`Schedule::named`].
+    pub fn with_system_in_phase<F: FnMut(&mut World, f32) + 'static>(
+        mut self,
+        phase: SystemPhase,
+        system: F,
+    ) -> Self {
+        self.phase_systems(phase).push(ScheduledSystem {
+            name: system_name::<F>(),
+            system: Box::new(system),
+            conditions: Vec::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+            rate: None,
+            enabled: true,
+        });
+        self.last_added = Some(phase);
+        self
+    }
+
+    fn last_system(&mut self, modifier: &str) -> &mut ScheduledSystem {
+        let phase = self
+            .last_added
+            .unwrap_or_else(|| panic!("{} must directly follow a phase system", modifier));
+        self.phase_systems(phase)
+            .last_mut()
+            .expect("last added system missing from its phase")
+    }
+
+    /// Gates the most recently added system. Repeated calls must all pass.
+    pub fn run_if(mut self, condition: impl Fn(&World) -> bool + 'static) -> Self {
+        self.last_system("run_if")
+            .conditions
+            .push(Box::new(condition));
+        self
+    }
+
+    fn next_rate(&mut self, hz: f32) -> FixedRate {
+        let rate = FixedRate::new(hz, self.rate_slots);
+        self.rate_slots += 1;
+        rate
+    }
+
+    /// Runs the most recently added system at a fixed `hz` instead of once
+    /// per step. It keeps its own accumulator and always sees a dt of
+    /// `1 / hz`, running zero or more times per step as time banks up.
+    pub fn at_rate(mut self, hz: f32) -> Self {
+        let rate = self.next_rate(hz);
+        self.last_system("at_rate").rate = Some(rate);
+        self
+    }
+
+    /// Runs every system in `phase` as a group at a fixed `hz`. Rates above
+    /// the frame rate sub-step the phase several times per step.
+    pub fn with_phase_rate(mut self, phase: SystemPhase, hz: f32) -> Self {
+        let position = self
+            .phase_position(phase)
+            .unwrap_or_else(|| panic!("phase {} is not in this schedule", phase));
+        self.phases[position].rate = Some(self.next_rate(hz));
+        self
+    }
+
+    /// Runs phases `first` through `last` (inclusive, in run order) `count`
+    /// times per step, each with an equal share of dt. `count` is asked
+    /// again every step, so sub-stepping can adapt to the world.
+    pub fn with_substeps(
+        mut self,
+        first: SystemPhase,
+        last: SystemPhase,
+        count: SubstepFn,
+    ) -> Self {
+        let missing = |phase| panic!("phase {} is not in this schedule", phase);
+        let start = self.phase_position(first).unwrap_or_else(|| missing(first));
+        let end = self.phase_position(last).unwrap_or_else(|| missing(last));
+        assert!(start <= end, "sub-step phase {} runs after {}", first, last);
+        self.substeps = Some(SubstepGroup { first, last, count });
+        self
+    }
+
+    /// Adds a system that runs once, with a dt of zero, before the first step.
+    pub fn with_startup_system<F: FnMut(&mut World, f32) + 'static>(mut self, system: F) -> Self {
+        self.startup.push(NamedSystem {
+            name: system_name::<F>(),
+            system: Box::new(system),
+        });
+        self.last_added = None;
+        self
+    }
+
+    /// Registers a system that only runs when triggered with
+    /// [`Schedule::run_one_shot`].
+    pub fn with_one_shot<F: FnMut(&mut World, f32) + 'static>(
+        mut self,
+        name: &'static str,
+        system: F,
+    ) -> Self {
+        self.one_shots.push(NamedSystem {
+            name,
+            system: Box::new(system),
+        });
+        self.last_added = None;
+        self
+    }
+
+    /// Renames the most recently added system.
+    pub fn named(mut self, name: &'static str) -> Self {
+        self.last_system("named").name = name;
+        self
+    }
+
+    /// Orders the most recently added system before `label` in the same phase.
+    pub fn before(mut self, label: &'static str) -> Self {
+        self.last_system("before").before.push(label);
+        self.needs_build = true;
+        self
+    }
+
+    /// Orders the most recently added system after `label` in the same phase.
+    pub fn after(mut self, label: &'static str) -> Self {
+        self.last_system("after").after.push(label);
+        self.needs_build = true;
+        self
+    }
+
+    /// Sorts each phase so every `before`/`after` constraint holds. Systems
+    /// without constraints between them keep their insertion order.
+    pub fn build(mut self) -> Result<Self, ScheduleError> {
+        for entry in &mut self.phases {
+            let systems = std::mem::take(&mut entry.systems);
+            entry.systems = sort_phase(entry.phase, systems)?;
+        }
+        self.last_added = None;
+        self.needs_build = false;
+        Ok(self)
+    }
+
+    /// Final execution order, one line per system, grouped by phase.
+    pub fn describe(&self) -> String {
+        let mut out = String::new();
+        if !self.startup.is_empty() {
+            out.push_str("Startup:\n");
+            for (position, startup) in self.startup.iter().enumerate() {
+                out.push_str(&format!("  {}. {}\n", position + 1, startup.name));
+            }
+        }
+        for entry in &self.phases {
+            out.push_str(&format!("{}:{}\n", entry.phase, describe_rate(entry.rate)));
+            for (position, scheduled) in entry.systems.iter().enumerate() {
+                out.push_str(&format!(
+                    "  {}. {}{}{}\n",
+                    position + 1,
+                    scheduled.name,
+                    describe_rate(scheduled.rate),
+                    if scheduled.enabled { "" } else { " (disabled)" }
+                ));
+            }
+        }
+        if let Some(group) = &self.substeps {
+            out.push_str(&format!("Sub-stepped: {}..={}\n", group.first, group.last));
+        }
+        if !self.one_shots.is_empty() {
+            out.push_str("One-shot:\n");
+            for one_shot in &self.one_shots {
+                out.push_str(&format!("  - {}\n", one_shot.name));
+            }
+        }
+        out
+    }
+
+    /// Every phase system in run order with its enabled flag.
+    pub fn systems(&self) -> Vec<SystemInfo> {
+        self.phases
+            .iter()
+            .flat_map(|entry| {
+                entry.systems.iter().map(move |scheduled| SystemInfo {
+                    phase: entry.phase,
+                    name: scheduled.name,
+                    enabled: scheduled.enabled,
+                })
+            })
+            .collect()
+    }
+
+    /// [`Schedule::systems`] as a JSON array of `{phase, name, enabled}` rows.
+    pub fn systems_json(&self) -> String {
+        let rows: Vec<String> = self
+            .systems()
+            .iter()
+            .map(|info| {
+                format!(
+                    "{{\"phase\":\"{}\",\"name\":\"{}\",\"enabled\":{}}}",
+                    info.phase,
+                    info.name.escape_default(),
+                    info.enabled
+                )
+            })
+            .collect();
+        format!("[{}]", rows.join(","))
+    }
+
+    /// Turns every phase system called `name` on or off at runtime. Disabled
+    /// systems are skipped as if their run conditions failed. Returns false if
+    /// no system has that name.
+    pub fn set_system_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        let mut found = false;
+        for scheduled in self
+            .phases
+            .iter_mut()
+            .flat_map(|entry| entry.systems.iter_mut())
+            .filter(|scheduled| scheduled.name == name)
+        {
+            scheduled.enabled = enabled;
+            found = true;
+        }
+        found
+    }
+
+    /// Applies queued `State<S>` transitions each step, even with no hooks.
+    pub fn with_state<S: Copy + PartialEq + 'static>(mut self) -> Self {
+        self.state_hooks::<S>();
+        self
+    }
+
+    pub fn on_enter<S: Copy + PartialEq + 'static>(mut self, state: S, system: SystemFn) -> Self {
+        self.state_hooks::<S>().on_enter.push((state, system));
+        self.last_added = None;
         self
     }
 
-    pub fn run(&self, world: &mut World, dt: f32) {
-        for phase in SystemPhase::ORDERED {
-            for system in &self.systems[phase.index()] {
-                system(world, dt);
+    pub fn on_exit<S: Copy + PartialEq + 'static>(mut self, state: S, system: SystemFn) -> Self {
+        self.state_hooks::<S>().on_exit.push((state, system));
+        self.last_added = None;
+        self
+    }
+
+    /// Adds `system` to `phase`, running only while `State<S>` is in `state`.
+    pub fn on_update<S: Copy + PartialEq + 'static, F: FnMut(&mut World, f32) + 'static>(
+        mut self,
+        phase: SystemPhase,
+        state: S,
+        system: F,
+    ) -> Self {
+        self.state_hooks::<S>();
+        self.with_system_in_phase(phase, system)
+            .run_if(in_state(state))
+    }
+
+    fn state_hooks<S: Copy + PartialEq + 'static>(&mut self) -> &mut StateHooks<S> {
+        let position = self
+            .states
+            .iter_mut()
+            .position(|driver| driver.as_any_mut().is::<StateHooks<S>>());
+        let index = position.unwrap_or_else(|| {
+            self.states.push(Box::new(StateHooks::<S>::default()));
+            self.states.len() - 1
+        });
+        self.states[index]
+            .as_any_mut()
+            .downcast_mut::<StateHooks<S>>()
+            .expect("state driver type mismatch")
+    }
+
+    /// Runs the startup systems unless they already ran. [`Schedule::run`]
+    /// calls this itself; call it directly to start a world without stepping.
+    pub fn run_startup(&mut self, world: &mut World) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        for startup in &mut self.startup {
+            (startup.system)(world, 0.0);
+        }
+    }
+
+    /// Runs the named one-shot system now. Returns false if no system has that name.
+    pub fn run_one_shot(&mut self, name: &str, world: &mut World) -> bool {
+        match self
+            .one_shots
+            .iter_mut()
+            .find(|one_shot| one_shot.name == name)
+        {
+            Some(one_shot) => {
+                (one_shot.system)(world, 0.0);
+                true
             }
+            None => false,
         }
     }
+
+    /// Runs every phase in order, then applies queued state transitions.
+    pub fn run(&mut self, world: &mut World, dt: f32) {
+        self.run_inner(world, dt, None);
+    }
+
+    /// Like [`Schedule::run`], also timing each system that runs and each phase.
+    pub fn run_profiled(&mut self, world: &mut World, dt: f32, profiler: &mut Profiler) {
+        self.run_inner(world, dt, Some(profiler));
+    }
+
+    fn run_inner(&mut self, world: &mut World, dt: f32, mut profiler: Option<&mut Profiler>) {
+        debug_assert!(
+            !self.needs_build,
+            "schedule has ordering constraints; call build() before run()"
+        );
+        self.run_startup(world);
+        let substeps = self.substeps.as_ref().map(|group| {
+            let first = self
+                .phase_position(group.first)
+                .expect("sub-step phase missing");
+            let last = self
+                .phase_position(group.last)
+                .expect("sub-step phase missing");
+            (first, last, group.count)
+        });
+        let mut index = 0;
+        while index < self.phases.len() {
+            match substeps {
+                Some((first, last, count)) if index == first => {
+                    let steps = count(world, dt).max(1);
+                    let sub_dt = dt / steps as f32;
+                    for _ in 0..steps {
+                        for entry in &mut self.phases[first..=last] {
+                            run_phase_at_rate(entry, world, sub_dt, profiler.as_deref_mut());
+                        }
+                    }
+                    index = last + 1;
+                }
+                _ => {
+                    run_phase_at_rate(&mut self.phases[index], world, dt, profiler.as_deref_mut());
+                    index += 1;
+                }
+            }
+        }
+        for driver in &self.states {
+            driver.advance(world, dt);
+        }
+    }
+}
+
+fn run_phase_at_rate(
+    entry: &mut PhaseSystems,
+    world: &mut World,
+    dt: f32,
+    mut profiler: Option<&mut Profiler>,
+) {
+    let (ticks, phase_dt) = match entry.rate {
+        Some(rate) => (rate.advance(world, dt), rate.period),
+        None => (1, dt),
+    };
+    for _ in 0..ticks {
+        run_phase(entry, world, phase_dt, profiler.as_deref_mut());
+    }
+}
+
+fn run_phase(
+    entry: &mut PhaseSystems,
+    world: &mut World,
+    dt: f32,
+    mut profiler: Option<&mut Profiler>,
+) {
+    let phase = entry.phase;
+    let profiling = profiler.is_some();
+    let phase_start = profiling.then(now_ms);
+    for (position, scheduled) in entry.systems.iter_mut().enumerate() {
+        // Gated or disabled systems bank no time, so they do not burst when
+        // they resume.
+        if !scheduled.should_run(world) {
+            continue;
+        }
+        let start = profiling.then(now_ms);
+        match scheduled.rate {
+            Some(rate) => {
+                for tick in 0..rate.advance(world, dt) {
+                    if tick > 0 && !scheduled.should_run(world) {
+                        break;
+                    }
+                    (scheduled.system)(world, rate.period);
+                }
+            }
+            None => (scheduled.system)(world, dt),
+        }
+        if let (Some(profiler), Some(start)) = (profiler.as_deref_mut(), start) {
+            profiler.record_system(phase, position, scheduled.name, now_ms() - start);
+        }
+    }
+    if let (Some(profiler), Some(start)) = (profiler, phase_start) {
+        profiler.record_phase(phase, now_ms() - start);
+    }
+}
+
+fn describe_rate(rate: Option<FixedRate>) -> String {
+    match rate {
+        Some(rate) => format!(" @ {} Hz", (1.0 / rate.period).round()),
+        None => String::new(),
+    }
+}
+
+fn resolve_label(
+    phase: SystemPhase,
+    systems: &[ScheduledSystem],
+    system: &'static str,
+    label: &'static str,
+) -> Result<usize, ScheduleError> {
+    let mut matches = systems
+        .iter()
+        .enumerate()
+        .filter(|(_, candidate)| candidate.name == label);
+    match (matches.next(), matches.next()) {
+        (Some((index, _)), None) => Ok(index),
+        (Some(_), Some(_)) => Err(ScheduleError::AmbiguousLabel { label, phase }),
+        (None, _) => Err(ScheduleError::UnknownLabel {
+            system,
+            label,
+            phase,
+        }),
+    }
+}
+
+fn sort_phase(
+    phase: SystemPhase,
+    systems: Vec<ScheduledSystem>,
+) -> Result<Vec<ScheduledSystem>, ScheduleError> {
+    let count = systems.len();
+    let mut successors = vec![Vec::new(); count];
+    let mut pending_predecessors = vec![0usize; count];
+
+    for (index, scheduled) in systems.iter().enumerate() {
+        for &label in &scheduled.before {
+            let target = resolve_label(phase, &systems, scheduled.name, label)?;
+            successors[index].push(target);
+            pending_predecessors[target] += 1;
+        }
+        for &label in &scheduled.after {
+            let target = resolve_label(phase, &systems, scheduled.name, label)?;
+            successors[target].push(index);
+            pending_predecessors[index] += 1;
+        }
+    }
+
+    // Kahn's algorithm, always taking the earliest-inserted ready system.
+    let mut order = Vec::with_capacity(count);
+    let mut placed = vec![false; count];
+    while order.len() < count {
+        let Some(next) = (0..count).find(|&i| !placed[i] && pending_predecessors[i] == 0) else {
+            let systems = (0..count)
+                .filter(|&i| !placed[i])
+                .map(|i| systems[i].name)
+                .collect();
+            return Err(ScheduleError::Cycle { phase, systems });
+        };
+        placed[next] = true;
+        order.push(next);
+        for &successor in &successors[next] {
+            pending_predecessors[successor] -= 1;
+        }
+    }
+
+    let mut slots: Vec<Option<ScheduledSystem>> = systems.into_iter().map(Some).collect();
+    Ok(order
+        .into_iter()
+        .map(|index| slots[index].take().expect("system placed twice"))
+        .collect())
 }
 
 #[cfg(test)]
@@ -100,7 +793,7 @@ mod tests {
     #[test]
     fn runs_systems_in_order() {
         let mut world = World::new(800.0, 600.0);
-        let schedule = Schedule::new()
+        let mut schedule = Schedule::new()
             .with_system(system_set_flag)
             .with_system(system_require_flag_then_set_next);
 
@@ -112,7 +805,7 @@ mod tests {
     #[test]
     fn passes_dt_through_to_systems() {
         let mut world = World::new(800.0, 600.0);
-        let schedule = Schedule::new().with_system(system_write_dt);
+        let mut schedule = Schedule::new().with_system(system_write_dt);
 
         schedule.run(&mut world, 0.123);
 
@@ -124,7 +817,7 @@ mod tests {
         let mut world = World::new(800.0, 600.0);
         world.insert_resource(Trace::default());
 
-        let schedule = Schedule::new()
+        let mut schedule = Schedule::new()
             .with_system_in_phase(SystemPhase::Control, record_control)
             .with_system_in_phase(SystemPhase::Physics, record_physics)
             .with_system_in_phase(SystemPhase::Resolve, record_resolve);
@@ -150,7 +843,7 @@ mod tests {
         let mut world = World::new(800.0, 600.0);
         world.insert_resource(Trace::default());
 
-        let schedule = Schedule::new()
+        let mut schedule = Schedule::new()
             .with_system_in_phase(SystemPhase::Control, record_first)
             .with_system_in_phase(SystemPhase::Control, record_second);
 
@@ -158,4 +851,503 @@ mod tests {
 
         assert_eq!(world.resource::<Trace>().calls, vec!["first", "second"]);
     }
+
+    fn input_is_set(world: &World) -> bool {
+        world.input.bits != 0
+    }
+
+    #[test]
+    fn run_if_skips_system_when_condition_fails() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+
+        let mut schedule = Schedule::new()
+            .with_system(record_first)
+            .run_if(input_is_set)
+            .with_system(record_second);
+
+        schedule.run(&mut world, 0.0);
+        assert_eq!(world.resource::<Trace>().calls, vec!["second"]);
+
+        world.input.bits = 1;
+        schedule.run(&mut world, 0.0);
+        assert_eq!(
+            world.resource::<Trace>().calls,
+            vec!["second", "first", "second"]
+        );
+    }
+
+    #[test]
+    fn run_if_conditions_combine_with_and() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+        world.input.bits = 1;
+
+        let mut schedule = Schedule::new()
+            .with_system(record_first)
+            .run_if(input_is_set)
+            .run_if(resource_is(|trace: &Trace| trace.calls.is_empty()));
+
+        schedule.run(&mut world, 0.0);
+        schedule.run(&mut world, 0.0);
+
+        assert_eq!(world.resource::<Trace>().calls, vec!["first"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "run_if must directly follow a phase system")]
+    fn run_if_requires_a_system() {
+        let _ = Schedule::new().run_if(input_is_set);
+    }
+
+    #[test]
+    fn systems_default_to_their_function_name() {
+        let schedule = Schedule::new()
+            .with_system(record_first)
+            .with_system_in_phase(SystemPhase::Resolve, record_second)
+            .named("second");
+
+        assert_eq!(
+            schedule.describe(),
+            "Control:\n  1. record_first\nPhysics:\nResolve:\n  1. second\n"
+        );
+    }
+
+    #[test]
+    fn build_honors_after_constraints() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+
+        let mut schedule = Schedule::new()
+            .with_system(record_second)
+            .after("record_first")
+            .with_system(record_first)
+            .build()
+            .unwrap();
+
+        schedule.run(&mut world, 0.0);
+
+        assert_eq!(world.resource::<Trace>().calls, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn build_honors_before_constraints_and_keeps_other_systems_in_place() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+
+        let mut schedule = Schedule::new()
+            .with_system(record_control)
+            .with_system(record_second)
+            .with_system(record_first)
+            .before("record_second")
+            .build()
+            .unwrap();
+
+        schedule.run(&mut world, 0.0);
+
+        assert_eq!(
+            world.resource::<Trace>().calls,
+            vec!["control", "first", "second"]
+        );
+    }
+
+    #[test]
+    fn build_reports_cycles() {
+        let result = Schedule::new()
+            .with_system(record_first)
+            .after("record_second")
+            .with_system(record_second)
+            .after("record_first")
+            .with_system(record_control)
+            .build();
+
+        let err = result.err().expect("cycle should be rejected");
+        assert_eq!(
+            err,
+            ScheduleError::Cycle {
+                phase: SystemPhase::Control,
+                systems: vec!["record_first", "record_second"],
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "ordering cycle in Control between: record_first, record_second"
+        );
+    }
+
+    #[test]
+    fn build_reports_unknown_labels() {
+        let result = Schedule::new()
+            .with_system(record_first)
+            .with_system_in_phase(SystemPhase::Resolve, record_second)
+            .after("record_first")
+            .build();
+
+        assert_eq!(
+            result.err(),
+            Some(ScheduleError::UnknownLabel {
+                system: "record_second",
+                label: "record_first",
+                phase: SystemPhase::Resolve,
+            })
+        );
+    }
+
+    #[test]
+    fn build_reports_ambiguous_labels() {
+        let result = Schedule::new()
+            .with_system(record_first)
+            .with_system(record_first)
+            .with_system(record_second)
+            .after("record_first")
+            .build();
+
+        assert_eq!(
+            result.err(),
+            Some(ScheduleError::AmbiguousLabel {
+                label: "record_first",
+                phase: SystemPhase::Control,
+            })
+        );
+    }
+
+    #[test]
+    fn run_profiled_times_systems_that_ran() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+        let mut profiler = Profiler::new(4);
+
+        let mut schedule = Schedule::new()
+            .with_system(record_first)
+            .with_system(record_second)
+            .run_if(input_is_set)
+            .with_system_in_phase(SystemPhase::Resolve, record_resolve);
+
+        schedule.run_profiled(&mut world, 0.0, &mut profiler);
+        schedule.run_profiled(&mut world, 0.0, &mut profiler);
+
+        let rows: Vec<_> = profiler
+            .report()
+            .iter()
+            .map(|row| (row.phase, row.system, row.samples))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (SystemPhase::Control, None, 2),
+                (SystemPhase::Control, Some("record_first"), 2),
+                (SystemPhase::Physics, None, 2),
+                (SystemPhase::Resolve, None, 2),
+                (SystemPhase::Resolve, Some("record_resolve"), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn closure_systems_keep_local_state_between_runs() {
+        let mut world = World::new(800.0, 600.0);
+        let mut calls = 0;
+        let mut schedule = Schedule::new().with_system(move |world: &mut World, _dt: f32| {
+            calls += 1;
+            world.input.bits = calls;
+        });
+
+        schedule.run(&mut world, 0.0);
+        schedule.run(&mut world, 0.0);
+        schedule.run(&mut world, 0.0);
+
+        assert_eq!(world.input.bits, 3);
+    }
+
+    #[test]
+    fn startup_systems_run_once_before_first_step() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+
+        let mut schedule = Schedule::new()
+            .with_system(record_control)
+            .with_startup_system(record_first);
+
+        schedule.run(&mut world, 0.0);
+        schedule.run(&mut world, 0.0);
+
+        assert_eq!(
+            world.resource::<Trace>().calls,
+            vec!["first", "control", "control"]
+        );
+    }
+
+    #[test]
+    fn run_startup_is_idempotent() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+        let mut schedule = Schedule::new().with_startup_system(record_first);
+
+        schedule.run_startup(&mut world);
+        schedule.run(&mut world, 0.0);
+
+        assert_eq!(world.resource::<Trace>().calls, vec!["first"]);
+    }
+
+    #[test]
+    fn one_shots_only_run_when_triggered() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+        let mut schedule = Schedule::new()
+            .with_system(record_control)
+            .with_one_shot("reset", record_first);
+
+        schedule.run(&mut world, 0.0);
+        assert!(schedule.run_one_shot("reset", &mut world));
+        assert!(!schedule.run_one_shot("missing", &mut world));
+
+        assert_eq!(world.resource::<Trace>().calls, vec!["control", "first"]);
+        assert!(schedule.describe().ends_with("One-shot:\n  - reset\n"));
+    }
+
+    const CLEANUP: SystemPhase = SystemPhase::Custom("Cleanup");
+    const PREP: SystemPhase = SystemPhase::Custom("Prep");
+
+    #[test]
+    fn custom_phases_run_relative_to_built_in_phases() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+
+        let mut schedule = Schedule::new()
+            .with_phase_after(SystemPhase::Resolve, CLEANUP)
+            .with_phase_before(SystemPhase::Physics, PREP)
+            .with_system_in_phase(CLEANUP, record_second)
+            .with_system_in_phase(SystemPhase::Resolve, record_resolve)
+            .with_system_in_phase(PREP, record_first)
+            .with_system_in_phase(SystemPhase::Physics, record_physics)
+            .with_system(record_control);
+
+        schedule.run(&mut world, 0.0);
+
+        assert_eq!(
+            schedule.phases(),
+            vec![
+                SystemPhase::Control,
+                PREP,
+                SystemPhase::Physics,
+                SystemPhase::Resolve,
+                CLEANUP,
+            ]
+        );
+        assert_eq!(
+            world.resource::<Trace>().calls,
+            vec!["control", "first", "physics", "resolve", "second"]
+        );
+    }
+
+    #[test]
+    fn custom_phases_can_anchor_on_each_other() {
+        let schedule = Schedule::new()
+            .with_phase_after(SystemPhase::Resolve, CLEANUP)
+            .with_phase_before(CLEANUP, PREP);
+
+        assert_eq!(
+            schedule.phases(),
+            vec![
+                SystemPhase::Control,
+                SystemPhase::Physics,
+                SystemPhase::Resolve,
+                PREP,
+                CLEANUP,
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "phase Cleanup is not in this schedule")]
+    fn systems_need_a_declared_phase() {
+        let _ = Schedule::new().with_system_in_phase(CLEANUP, record_first);
+    }
+
+    #[test]
+    #[should_panic(expected = "phase Physics is already in this schedule")]
+    fn built_in_phases_cannot_be_moved() {
+        let _ = Schedule::new().with_phase_after(SystemPhase::Resolve, SystemPhase::Physics);
+    }
+
+    #[derive(Default)]
+    struct Ticks {
+        dts: Vec<f32>,
+    }
+
+    fn record_tick(world: &mut World, dt: f32) {
+        world.resource_mut::<Ticks>().dts.push(dt);
+    }
+
+    fn ticks(world: &World) -> Vec<f32> {
+        world.resource::<Ticks>().dts.clone()
+    }
+
+    fn ticking_world() -> World {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Ticks::default());
+        world
+    }
+
+    #[test]
+    fn fixed_rate_systems_bank_time_and_see_their_own_dt() {
+        let mut world = ticking_world();
+        let mut schedule = Schedule::new().with_system(record_tick).at_rate(4.0);
+
+        schedule.run(&mut world, 0.125);
+        assert!(ticks(&world).is_empty());
+
+        schedule.run(&mut world, 0.125);
+        assert_eq!(ticks(&world), vec![0.25]);
+
+        schedule.run(&mut world, 0.5);
+        assert_eq!(ticks(&world), vec![0.25, 0.25, 0.25]);
+    }
+
+    #[test]
+    fn phase_rate_sub_steps_every_system_in_the_phase() {
+        let mut world = ticking_world();
+        let mut schedule = Schedule::new()
+            .with_system_in_phase(SystemPhase::Physics, record_tick)
+            .with_phase_rate(SystemPhase::Physics, 8.0);
+
+        schedule.run(&mut world, 0.5);
+
+        assert_eq!(ticks(&world), vec![0.125; 4]);
+    }
+
+    #[test]
+    fn gated_fixed_rate_systems_do_not_bank_time() {
+        let mut world = ticking_world();
+        let mut schedule = Schedule::new()
+            .with_system(record_tick)
+            .at_rate(4.0)
+            .run_if(|world| world.input.bits != 0);
+
+        schedule.run(&mut world, 1.0);
+        world.input.bits = 1;
+        schedule.run(&mut world, 0.25);
+
+        assert_eq!(ticks(&world), vec![0.25]);
+    }
+
+    #[test]
+    fn fixed_ticks_per_run_are_capped() {
+        let mut world = ticking_world();
+        let mut schedule = Schedule::new().with_system(record_tick).at_rate(100.0);
+
+        schedule.run(&mut world, 10.0);
+        assert_eq!(ticks(&world).len(), MAX_FIXED_TICKS as usize);
+
+        // The cap keeps one period banked, which the next run spends.
+        schedule.run(&mut world, 0.0);
+        assert_eq!(ticks(&world).len(), MAX_FIXED_TICKS as usize + 1);
+    }
+
+    #[test]
+    fn banked_time_lives_in_the_world() {
+        let mut world = ticking_world();
+        let mut schedule = Schedule::new().with_system(record_tick).at_rate(4.0);
+        schedule.run(&mut world, 0.125);
+
+        // A fresh schedule over the same world, as after loading a save.
+        let mut resumed = Schedule::new().with_system(record_tick).at_rate(4.0);
+        resumed.run(&mut world, 0.125);
+
+        assert_eq!(ticks(&world), vec![0.25]);
+    }
+
+    #[test]
+    fn describe_lists_tick_rates() {
+        let schedule = Schedule::new()
+            .with_system(record_tick)
+            .at_rate(12.0)
+            .with_phase_rate(SystemPhase::Physics, 240.0);
+
+        let description = schedule.describe();
+
+        assert!(description.contains("  1. record_tick @ 12 Hz\n"));
+        assert!(description.contains("Physics: @ 240 Hz\n"));
+    }
+
+    #[test]
+    fn disabled_systems_are_skipped_until_re_enabled() {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+        let mut schedule = Schedule::new()
+            .with_system(record_control)
+            .with_system_in_phase(SystemPhase::Resolve, record_resolve);
+
+        assert!(schedule.set_system_enabled("record_control", false));
+        schedule.run(&mut world, 0.1);
+        assert!(schedule.set_system_enabled("record_control", true));
+        schedule.run(&mut world, 0.1);
+
+        assert_eq!(
+            world.resource::<Trace>().calls,
+            vec!["resolve", "control", "resolve"]
+        );
+    }
+
+    #[test]
+    fn set_system_enabled_reports_unknown_names() {
+        let mut schedule = Schedule::new().with_system(record_control);
+
+        assert!(!schedule.set_system_enabled("missing", false));
+    }
+
+    #[test]
+    fn systems_report_phase_name_and_enabled_state() {
+        let mut schedule = Schedule::new()
+            .with_system(record_control)
+            .with_system_in_phase(SystemPhase::Physics, record_physics);
+        schedule.set_system_enabled("record_physics", false);
+
+        assert_eq!(
+            schedule.systems(),
+            vec![
+                SystemInfo {
+                    phase: SystemPhase::Control,
+                    name: "record_control",
+                    enabled: true,
+                },
+                SystemInfo {
+                    phase: SystemPhase::Physics,
+                    name: "record_physics",
+                    enabled: false,
+                },
+            ]
+        );
+        assert_eq!(
+            schedule.systems_json(),
+            "[{\"phase\":\"Control\",\"name\":\"record_control\",\"enabled\":true},\
+{\"phase\":\"Physics\",\"name\":\"record_physics\",\"enabled\":false}]"
+        );
+        assert!(schedule
+            .describe()
+            .contains("  1. record_physics (disabled)\n"));
+    }
+
+    fn three_substeps(_world: &World, _dt: f32) -> u32 {
+        3
+    }
+
+    #[test]
+    fn substep_group_repeats_its_phases_with_a_share_of_dt() {
+        let mut world = ticking_world();
+        world.insert_resource(Trace::default());
+        let mut schedule = Schedule::new()
+            .with_system(record_control)
+            .with_system_in_phase(SystemPhase::Physics, record_tick)
+            .with_system_in_phase(SystemPhase::Resolve, record_resolve)
+            .with_substeps(SystemPhase::Physics, SystemPhase::Resolve, three_substeps);
+
+        schedule.run(&mut world, 0.75);
+
+        assert_eq!(ticks(&world), vec![0.25; 3]);
+        assert_eq!(
+            world.resource::<Trace>().calls,
+            vec!["control", "resolve", "resolve", "resolve"]
+        );
+    }
 }
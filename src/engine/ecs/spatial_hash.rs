@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+
+use super::entity::EntityId;
+use super::resources::FieldBounds;
+use super::world::World;
+use crate::engine::collision::Aabb;
+
+pub const DEFAULT_CELL_SIZE: f32 = 64.0;
+/// Entries covering more cells than this skip the grid and are checked by
+/// every query instead, so one huge collider can't flood the map.
+const MAX_CELLS_PER_ENTRY: i64 = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct CellRange {
+    min_x: i32,
+    min_y: i32,
+    max_x: i32,
+    max_y: i32,
+}
+
+impl CellRange {
+    fn count(&self) -> i64 {
+        (i64::from(self.max_x) - i64::from(self.min_x) + 1)
+            * (i64::from(self.max_y) - i64::from(self.min_y) + 1)
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        (self.min_y..=self.max_y).flat_map(move |y| (self.min_x..=self.max_x).map(move |x| (x, y)))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    bounds: Aabb,
+    layer: u32,
+    range: CellRange,
+}
+
+/// Uniform-grid broadphase over collider bounds. `update_spatial_hash` keeps
+/// it in sync with the colliders each Physics phase, storing each body's
+/// bounds swept over the step along with its collision layer. Queries take a
+/// layer mask and return matching entities in id order.
+#[derive(Debug)]
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<u32>>,
+    oversized: Vec<u32>,
+    entries: Vec<Option<Entry>>,
+    largest_extent: f32,
+}
+
+impl Default for SpatialHash {
+    fn default() -> Self {
+        Self::new(DEFAULT_CELL_SIZE)
+    }
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        assert!(
+            cell_size.is_finite() && cell_size > 0.0,
+            "spatial hash cell size must be positive"
+        );
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            oversized: Vec::new(),
+            entries: Vec::new(),
+            largest_extent: 0.0,
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Changes the cell size, re-bucketing everything already inserted.
+    pub fn set_cell_size(&mut self, cell_size: f32) {
+        let entries = std::mem::take(&mut self.entries);
+        *self = Self::new(cell_size);
+        for (idx, entry) in entries.iter().enumerate() {
+            if let Some(entry) = entry {
+                self.insert(EntityId(idx as u32), entry.bounds, entry.layer);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.oversized.clear();
+        self.entries.clear();
+        self.largest_extent = 0.0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.iter().all(Option::is_none)
+    }
+
+    /// Widest or tallest bounds currently stored. An upper bound on how far
+    /// any entry can reach past its own position.
+    pub fn largest_extent(&self) -> f32 {
+        self.largest_extent
+    }
+
+    pub fn bounds(&self, entity: EntityId) -> Option<Aabb> {
+        self.entry(entity).map(|entry| entry.bounds)
+    }
+
+    pub fn layer(&self, entity: EntityId) -> Option<u32> {
+        self.entry(entity).map(|entry| entry.layer)
+    }
+
+    /// Inserts `entity` or moves it to new bounds and layer. Buckets are only
+    /// touched when the set of covered cells changes.
+    pub fn insert(&mut self, entity: EntityId, bounds: Aabb, layer: u32) {
+        let idx = entity.0 as usize;
+        let range = self.cell_range(&bounds);
+        let extent = extent_of(&bounds);
+        if let Some(entry) = self.entries.get_mut(idx).and_then(Option::as_mut) {
+            if entry.range == range {
+                let was_largest = extent_of(&entry.bounds) >= self.largest_extent;
+                entry.bounds = bounds;
+                entry.layer = layer;
+                if was_largest && extent < self.largest_extent {
+                    self.recompute_largest_extent();
+                } else {
+                    self.largest_extent = self.largest_extent.max(extent);
+                }
+                return;
+            }
+            self.remove(entity);
+        }
+        self.largest_extent = self.largest_extent.max(extent);
+
+        if self.entries.len() <= idx {
+            self.entries.resize_with(idx + 1, || None);
+        }
+        self.entries[idx] = Some(Entry {
+            bounds,
+            layer,
+            range,
+        });
+        if range.count() > MAX_CELLS_PER_ENTRY {
+            self.oversized.push(entity.0);
+        } else {
+            for cell in range.cells() {
+                self.cells.entry(cell).or_default().push(entity.0);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, entity: EntityId) {
+        let Some(entry) = self
+            .entries
+            .get_mut(entity.0 as usize)
+            .and_then(Option::take)
+        else {
+            return;
+        };
+        if extent_of(&entry.bounds) >= self.largest_extent {
+            self.recompute_largest_extent();
+        }
+        if entry.range.count() > MAX_CELLS_PER_ENTRY {
+            self.oversized.retain(|&id| id != entity.0);
+            return;
+        }
+        for cell in entry.range.cells() {
+            if let Some(ids) = self.cells.get_mut(&cell) {
+                ids.retain(|&id| id != entity.0);
+                if ids.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+
+    /// Rescans the entries after the largest one shrank or left, so seam
+    /// queries stop searching a margin nothing needs any more.
+    fn recompute_largest_extent(&mut self) {
+        self.largest_extent = self
+            .entries
+            .iter()
+            .flatten()
+            .map(|entry| extent_of(&entry.bounds))
+            .fold(0.0, f32::max);
+    }
+
+    /// Entities on a layer in `mask` whose bounds overlap `area`.
+    pub fn query_aabb(&self, area: &Aabb, mask: u32) -> Vec<EntityId> {
+        self.query(area, mask, |bounds| bounds.overlaps(area))
+    }
+
+    /// Entities on a layer in `mask` whose bounds come within `radius` of
+    /// `(x, y)`.
+    pub fn query_radius(&self, x: f32, y: f32, radius: f32, mask: u32) -> Vec<EntityId> {
+        let area = Aabb::new(x, y, radius, radius);
+        self.query(&area, mask, |bounds| {
+            let (closest_x, closest_y) = bounds.closest_point(x, y);
+            let (dx, dy) = (x - closest_x, y - closest_y);
+            dx * dx + dy * dy <= radius * radius
+        })
+    }
+
+    /// Like `query_radius`, but measures distance across the seams of a
+    /// wrapping `field`, so entries just over the opposite edge are found.
+    pub fn query_radius_wrapped(
+        &self,
+        field: &FieldBounds,
+        x: f32,
+        y: f32,
+        radius: f32,
+        mask: u32,
+    ) -> Vec<EntityId> {
+        let area = Aabb::new(x, y, radius, radius);
+        let margin = radius + self.largest_extent;
+        let mut found: Vec<EntityId> = field
+            .seam_offsets(&area, margin)
+            .into_iter()
+            .flat_map(|(ox, oy)| self.query_radius(x + ox, y + oy, radius, mask))
+            .collect();
+        found.sort_unstable_by_key(|entity| entity.0);
+        found.dedup();
+        found
+    }
+
+    fn query(&self, area: &Aabb, mask: u32, keep: impl Fn(&Aabb) -> bool) -> Vec<EntityId> {
+        let range = self.cell_range(area);
+        let mut found: Vec<u32> = if range.count() > self.cells.len() as i64 {
+            // Asking for more cells than are occupied: scan the occupied ones.
+            self.cells
+                .iter()
+                .filter(|(cell, _)| {
+                    (range.min_x..=range.max_x).contains(&cell.0)
+                        && (range.min_y..=range.max_y).contains(&cell.1)
+                })
+                .flat_map(|(_, ids)| ids.iter().copied())
+                .collect()
+        } else {
+            range
+                .cells()
+                .filter_map(|cell| self.cells.get(&cell))
+                .flat_map(|ids| ids.iter().copied())
+                .collect()
+        };
+        found.extend_from_slice(&self.oversized);
+        found.sort_unstable();
+        found.dedup();
+        found
+            .into_iter()
+            .map(EntityId)
+            .filter(|&entity| {
+                self.entry(entity)
+                    .is_some_and(|entry| entry.layer & mask != 0 && keep(&entry.bounds))
+            })
+            .collect()
+    }
+
+    fn entry(&self, entity: EntityId) -> Option<&Entry> {
+        self.entries.get(entity.0 as usize).and_then(Option::as_ref)
+    }
+
+    fn cell_range(&self, bounds: &Aabb) -> CellRange {
+        let cell = |value: f32| (value / self.cell_size).floor() as i32;
+        CellRange {
+            min_x: cell(bounds.min_x()),
+            min_y: cell(bounds.min_y()),
+            max_x: cell(bounds.max_x()),
+            max_y: cell(bounds.max_y()),
+        }
+    }
+}
+
+fn extent_of(bounds: &Aabb) -> f32 {
+    (bounds.half_width * 2.0).max(bounds.half_height * 2.0)
+}
+
+/// Bounds of an entity's collider over the step just integrated: from
+/// `transform - velocity * dt` to `transform`.
+pub fn swept_collider_bounds(world: &World, entity: EntityId, dt: f32) -> Option<Aabb> {
+    let idx = entity.0 as usize;
+    let collider = world.colliders.get(idx)?.as_ref()?;
+    let transform = world.transforms.get(idx)?.as_ref()?;
+    let (half_width, half_height) = collider.shape.half_extents();
+    let x = transform.position.x() + collider.offset_x;
+    let y = transform.position.y() + collider.offset_y;
+    let end = Aabb::new(x, y, half_width, half_height);
+    Some(match world.velocities.get(idx).and_then(Option::as_ref) {
+        Some(velocity) => end.union(&Aabb::new(
+            x - velocity.x() * dt,
+            y - velocity.y() * dt,
+            half_width,
+            half_height,
+        )),
+        None => end,
+    })
+}
+
+/// Engine system: brings `world.spatial_hash` up to date with every collider,
+/// dropping entities that no longer have one. Runs after `integrate_velocity`.
+pub fn update_spatial_hash(world: &mut World, dt: f32) {
+    let mut hash = std::mem::take(&mut world.spatial_hash);
+    for idx in 0..world.colliders.len().max(hash.entries.len()) {
+        let entity = EntityId(idx as u32);
+        match swept_collider_bounds(world, entity, dt) {
+            Some(bounds) => hash.insert(entity, bounds, world.collider(entity).layer),
+            None => hash.remove(entity),
+        }
+    }
+    world.spatial_hash = hash;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ecs::components::{Collider, Transform, Velocity};
+    use crate::engine::ecs::layers::{ALL_LAYERS, DEFAULT_LAYER};
+    use crate::engine::ecs::resources::{EdgeBehavior, FieldEdges};
+
+    fn square(x: f32, y: f32, half: f32) -> Aabb {
+        Aabb::new(x, y, half, half)
+    }
+
+    #[test]
+    fn queries_return_overlapping_entries_in_id_order() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(EntityId(3), square(5.0, 5.0, 2.0), DEFAULT_LAYER);
+        hash.insert(EntityId(1), square(12.0, 5.0, 2.0), DEFAULT_LAYER);
+        hash.insert(EntityId(2), square(100.0, 100.0, 2.0), DEFAULT_LAYER);
+
+        let found = hash.query_aabb(&Aabb::from_min_max(0.0, 0.0, 20.0, 10.0), ALL_LAYERS);
+
+        assert_eq!(found, vec![EntityId(1), EntityId(3)]);
+    }
+
+    #[test]
+    fn queries_skip_layers_outside_the_mask() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(EntityId(0), square(5.0, 5.0, 2.0), 0b01);
+        hash.insert(EntityId(1), square(5.0, 5.0, 2.0), 0b10);
+
+        assert_eq!(hash.query_radius(5.0, 5.0, 1.0, 0b10), vec![EntityId(1)]);
+        assert_eq!(hash.query_radius(5.0, 5.0, 1.0, 0b11).len(), 2);
+        assert_eq!(hash.layer(EntityId(1)), Some(0b10));
+    }
+
+    #[test]
+    fn wrapped_radius_query_finds_entries_across_the_seam() {
+        let mut field = FieldBounds::new(100.0, 100.0);
+        field.edges = FieldEdges::all(EdgeBehavior::Wrap);
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(EntityId(0), square(98.0, 50.0, 1.0), DEFAULT_LAYER);
+        hash.insert(EntityId(1), square(50.0, 50.0, 1.0), DEFAULT_LAYER);
+
+        assert!(hash.query_radius(2.0, 50.0, 5.0, ALL_LAYERS).is_empty());
+        assert_eq!(
+            hash.query_radius_wrapped(&field, 2.0, 50.0, 5.0, ALL_LAYERS),
+            vec![EntityId(0)]
+        );
+    }
+
+    #[test]
+    fn radius_query_checks_distance_not_just_cells() {
+        let mut hash = SpatialHash::new(100.0);
+        hash.insert(EntityId(0), square(10.0, 10.0, 1.0), DEFAULT_LAYER);
+        hash.insert(EntityId(1), square(30.0, 30.0, 1.0), DEFAULT_LAYER);
+
+        assert_eq!(
+            hash.query_radius(0.0, 0.0, 15.0, ALL_LAYERS),
+            vec![EntityId(0)]
+        );
+    }
+
+    #[test]
+    fn moving_an_entry_updates_its_cells() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(EntityId(0), square(5.0, 5.0, 1.0), DEFAULT_LAYER);
+        hash.insert(EntityId(0), square(55.0, 5.0, 1.0), DEFAULT_LAYER);
+
+        assert!(hash.query_radius(5.0, 5.0, 3.0, ALL_LAYERS).is_empty());
+        assert_eq!(
+            hash.query_radius(55.0, 5.0, 3.0, ALL_LAYERS),
+            vec![EntityId(0)]
+        );
+        assert_eq!(hash.len(), 1);
+    }
+
+    #[test]
+    fn oversized_entries_are_still_found() {
+        let mut hash = SpatialHash::new(1.0);
+        hash.insert(EntityId(0), square(0.0, 0.0, 1000.0), DEFAULT_LAYER);
+
+        assert_eq!(
+            hash.query_radius(900.0, -900.0, 1.0, ALL_LAYERS),
+            vec![EntityId(0)]
+        );
+
+        hash.remove(EntityId(0));
+        assert!(hash.is_empty());
+        assert!(hash.query_radius(900.0, -900.0, 1.0, ALL_LAYERS).is_empty());
+    }
+
+    #[test]
+    fn largest_extent_shrinks_when_the_largest_entry_goes() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(EntityId(0), square(5.0, 5.0, 2.0), DEFAULT_LAYER);
+        hash.insert(EntityId(1), square(50.0, 50.0, 20.0), DEFAULT_LAYER);
+        assert_eq!(hash.largest_extent(), 40.0);
+
+        hash.remove(EntityId(1));
+        assert_eq!(hash.largest_extent(), 4.0);
+
+        hash.insert(EntityId(0), square(5.0, 5.0, 1.0), DEFAULT_LAYER);
+        assert_eq!(hash.largest_extent(), 2.0);
+    }
+
+    #[test]
+    fn changing_cell_size_keeps_entries() {
+        let mut hash = SpatialHash::new(10.0);
+        hash.insert(EntityId(4), square(25.0, 25.0, 3.0), DEFAULT_LAYER);
+
+        hash.set_cell_size(7.0);
+
+        assert_eq!(
+            hash.query_radius(25.0, 25.0, 1.0, ALL_LAYERS),
+            vec![EntityId(4)]
+        );
+    }
+
+    #[test]
+    fn system_tracks_swept_bounds_and_despawns() {
+        let mut world = World::new(800.0, 600.0);
+        let ball = world.spawn();
+        world.set_transform(ball, Transform::at(100.0, 50.0));
+        world.set_velocity(ball, Velocity::new(200.0, 0.0));
+        world.set_collider(ball, Collider::circle(5.0));
+
+        update_spatial_hash(&mut world, 0.25);
+
+        let bounds = world.spatial_hash.bounds(ball).unwrap();
+        assert_eq!((bounds.min_x(), bounds.max_x()), (45.0, 105.0));
+
+        world.despawn(ball);
+        update_spatial_hash(&mut world, 0.25);
+        assert!(world.spatial_hash.is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+use std::any::Any;
+
+use super::schedule::SystemFn;
+use super::world::World;
+
+/// Finite-state resource. Transitions are queued with [`State::queue`] and
+/// applied by the schedule after the Resolve phase, running the `on_exit`
+/// systems of the old state and then the `on_enter` systems of the new one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct State<S> {
+    current: S,
+    queued: Option<S>,
+    elapsed: f32,
+}
+
+impl<S: Copy + PartialEq> State<S> {
+    pub fn new(initial: S) -> Self {
+        Self::restore(initial, None, 0.0)
+    }
+
+    pub fn restore(current: S, queued: Option<S>, elapsed: f32) -> Self {
+        Self {
+            current,
+            queued,
+            elapsed,
+        }
+    }
+
+    pub fn current(&self) -> S {
+        self.current
+    }
+
+    pub fn queued(&self) -> Option<S> {
+        self.queued
+    }
+
+    /// Seconds spent in the current state.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Requests a transition at the end of this step. The last request wins.
+    pub fn queue(&mut self, next: S) {
+        self.queued = Some(next);
+    }
+}
+
+/// Run condition that passes while `State<S>` is in `state`.
+pub fn in_state<S: Copy + PartialEq + 'static>(state: S) -> impl Fn(&World) -> bool {
+    move |world| world.resource::<State<S>>().current() == state
+}
+
+pub(super) trait StateDriver {
+    fn advance(&self, world: &mut World, dt: f32);
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+pub(super) struct StateHooks<S> {
+    pub on_enter: Vec<(S, SystemFn)>,
+    pub on_exit: Vec<(S, SystemFn)>,
+}
+
+impl<S> Default for StateHooks<S> {
+    fn default() -> Self {
+        Self {
+            on_enter: Vec::new(),
+            on_exit: Vec::new(),
+        }
+    }
+}
+
+impl<S: Copy + PartialEq + 'static> StateDriver for StateHooks<S> {
+    fn advance(&self, world: &mut World, dt: f32) {
+        let (previous, next) = {
+            let state = world.resource_mut::<State<S>>();
+            state.elapsed += dt;
+            match state.queued.take() {
+                Some(next) if next != state.current => (state.current, next),
+                _ => return,
+            }
+        };
+
+        for (_, system) in self.on_exit.iter().filter(|(s, _)| *s == previous) {
+            system(world, dt);
+        }
+
+        {
+            let state = world.resource_mut::<State<S>>();
+            state.current = next;
+            state.elapsed = 0.0;
+        }
+
+        for (_, system) in self.on_enter.iter().filter(|(s, _)| *s == next) {
+            system(world, dt);
+        }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ecs::schedule::{Schedule, SystemPhase};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Mode {
+        Ready,
+        Playing,
+        Paused,
+    }
+
+    #[derive(Default)]
+    struct Trace {
+        calls: Vec<&'static str>,
+    }
+
+    fn record(world: &mut World, event: &'static str) {
+        world.resource_mut::<Trace>().calls.push(event);
+    }
+
+    fn enter_playing(world: &mut World, _dt: f32) {
+        record(world, "enter playing");
+    }
+
+    fn exit_ready(world: &mut World, _dt: f32) {
+        record(world, "exit ready");
+    }
+
+    fn update_playing(world: &mut World, _dt: f32) {
+        record(world, "update playing");
+    }
+
+    fn start_on_input(world: &mut World, _dt: f32) {
+        if world.input.bits != 0 {
+            world.resource_mut::<State<Mode>>().queue(Mode::Playing);
+        }
+    }
+
+    fn new_world() -> World {
+        let mut world = World::new(800.0, 600.0);
+        world.insert_resource(Trace::default());
+        world.insert_resource(State::new(Mode::Ready));
+        world
+    }
+
+    fn schedule() -> Schedule {
+        Schedule::new()
+            .with_system(start_on_input)
+            .on_update(SystemPhase::Resolve, Mode::Playing, update_playing)
+            .on_exit(Mode::Ready, exit_ready)
+            .on_enter(Mode::Playing, enter_playing)
+    }
+
+    #[test]
+    fn queued_transition_applies_after_resolve_with_hooks_in_order() {
+        let mut world = new_world();
+        let mut schedule = schedule();
+
+        world.input.bits = 1;
+        schedule.run(&mut world, 0.1);
+
+        assert_eq!(world.resource::<State<Mode>>().current(), Mode::Playing);
+        assert_eq!(
+            world.resource::<Trace>().calls,
+            vec!["exit ready", "enter playing"]
+        );
+
+        world.input.bits = 0;
+        schedule.run(&mut world, 0.1);
+
+        assert_eq!(
+            world.resource::<Trace>().calls,
+            vec!["exit ready", "enter playing", "update playing"]
+        );
+    }
+
+    #[test]
+    fn tracks_time_in_current_state() {
+        let mut world = new_world();
+        let mut schedule = schedule();
+
+        schedule.run(&mut world, 0.25);
+        schedule.run(&mut world, 0.25);
+        assert_eq!(world.resource::<State<Mode>>().elapsed(), 0.5);
+
+        world.input.bits = 1;
+        schedule.run(&mut world, 0.25);
+        assert_eq!(world.resource::<State<Mode>>().elapsed(), 0.0);
+
+        schedule.run(&mut world, 0.25);
+        assert_eq!(world.resource::<State<Mode>>().elapsed(), 0.25);
+    }
+
+    #[test]
+    fn queueing_current_state_does_not_rerun_hooks() {
+        let mut world = new_world();
+        world.insert_resource(State::new(Mode::Playing));
+        let mut schedule = schedule();
+
+        world.input.bits = 1;
+        schedule.run(&mut world, 0.1);
+
+        assert_eq!(world.resource::<Trace>().calls, vec!["update playing"]);
+        assert_eq!(world.resource::<State<Mode>>().queued(), None);
+    }
+
+    #[test]
+    fn state_without_hooks_still_transitions_when_registered() {
+        let mut world = new_world();
+        let mut schedule = Schedule::new().with_state::<Mode>();
+
+        world.resource_mut::<State<Mode>>().queue(Mode::Paused);
+        schedule.run(&mut world, 0.1);
+
+        assert_eq!(world.resource::<State<Mode>>().current(), Mode::Paused);
+    }
+}
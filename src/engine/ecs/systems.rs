@@ -1,57 +1,359 @@
+use super::components::{ColliderShape, KinematicBody, SimVec2};
+use super::entity::EntityId;
+use super::resources::{EdgeBehavior, FieldEdge, FieldExit};
+use super::schedule::SystemPhase;
 use super::world::World;
+#[cfg(feature = "fixed-point")]
+use crate::engine::fixed::{Fixed, FixedVec2};
+#[cfg(not(feature = "fixed-point"))]
+use crate::engine::math;
+use crate::engine::math::Vec2;
+
+/// Steers velocities before `integrate_velocity`: adds `Acceleration`, then
+/// applies `Drag`, then clamps to `MaxSpeed`. Each part is optional.
+#[cfg(not(feature = "fixed-point"))]
+pub fn apply_acceleration(world: &mut World, dt: f32) {
+    for (idx, velocity) in world.velocities.iter_mut().enumerate() {
+        let Some(velocity) = velocity else {
+            continue;
+        };
+        if let Some(Some(acceleration)) = world.accelerations.get(idx) {
+            *velocity += *acceleration * dt;
+        }
+        if let Some(Some(drag)) = world.drags.get(idx) {
+            *velocity *= math::exp_neg(drag.rate * dt);
+        }
+        if let Some(Some(max_speed)) = world.max_speeds.get(idx) {
+            *velocity = velocity.clamp_length(max_speed.value);
+        }
+    }
+}
+
+#[cfg(feature = "fixed-point")]
+pub fn apply_acceleration(world: &mut World, dt: f32) {
+    let dt = Fixed::from_f32(dt);
+    for (idx, velocity) in world.velocities.iter_mut().enumerate() {
+        let Some(velocity) = velocity else {
+            continue;
+        };
+        if let Some(Some(acceleration)) = world.accelerations.get(idx) {
+            *velocity += FixedVec2::from(*acceleration) * dt;
+        }
+        if let Some(Some(drag)) = world.drags.get(idx) {
+            *velocity = *velocity * (Fixed::from_f32(drag.rate) * dt).exp_neg();
+        }
+        if let Some(Some(max_speed)) = world.max_speeds.get(idx) {
+            *velocity = velocity.clamp_length(Fixed::from_f32(max_speed.value));
+        }
+    }
+}
+
+#[cfg(not(feature = "fixed-point"))]
+pub fn integrate_velocity(world: &mut World, dt: f32) {
+    let (transforms, velocities) = (&mut world.transforms, &world.velocities);
+
+    for (transform, velocity) in transforms.iter_mut().zip(velocities.iter()) {
+        if let (Some(transform), Some(velocity)) = (transform, velocity) {
+            transform.position += *velocity * dt;
+        }
+    }
+}
 
+#[cfg(feature = "fixed-point")]
 pub fn integrate_velocity(world: &mut World, dt: f32) {
+    let dt = Fixed::from_f32(dt);
     let (transforms, velocities) = (&mut world.transforms, &world.velocities);
 
     for (transform, velocity) in transforms.iter_mut().zip(velocities.iter()) {
         if let (Some(transform), Some(velocity)) = (transform, velocity) {
-            transform.x += velocity.x * dt;
-            transform.y += velocity.y * dt;
+            transform.position += *velocity * dt;
         }
     }
 }
 
+/// Engine phase between Control and Physics that runs
+/// `track_kinematic_input` once per frame. Inside the sub-stepped group, a
+/// body moved once per frame in Control would read as moving its whole
+/// distance in the first sub-step and standing still in the rest.
+pub const KINEMATIC_PHASE: SystemPhase = SystemPhase::Custom("Kinematics");
+
+/// Rate at which each `KinematicBody` moved since its last tracked position
+/// over `dt`, taking the short way across wrapping edges, and moves the
+/// tracked position up to the current one. `None` for a body tracked for the
+/// first time or with no elapsed time.
+fn track_displacement(world: &mut World, dt: f32, mut apply: impl FnMut(&mut KinematicBody, Vec2)) {
+    let field = world.field;
+    for (body, transform) in world
+        .kinematic_bodies
+        .iter_mut()
+        .zip(world.transforms.iter())
+    {
+        let (Some(body), Some(transform)) = (body, transform) else {
+            continue;
+        };
+        let position = transform.position.to_vec2();
+        if let Some(last) = body.last_position {
+            if dt > 0.0 {
+                let delta: Vec2 = field.wrapped_delta(last.into(), position.into()).into();
+                apply(body, delta / dt);
+            }
+        }
+        body.last_position = Some(position);
+    }
+}
+
+/// Sets each `KinematicBody`'s `input_velocity` from how far it was moved
+/// since the last physics step, i.e. by Control systems this frame, spread
+/// over the whole frame. Runs once per frame in [`KINEMATIC_PHASE`].
+pub fn track_kinematic_input(world: &mut World, dt: f32) {
+    track_displacement(world, dt, |body, rate| body.input_velocity = rate);
+}
+
+/// Sets each `KinematicBody`'s velocity from its displacement during this
+/// physics step, plus its `input_velocity`. Runs before `detect_contacts`,
+/// after whatever moved or clamped the body this step.
+pub fn track_kinematic_bodies(world: &mut World, dt: f32) {
+    track_displacement(world, dt, |body, rate| {
+        body.velocity = rate + body.input_velocity;
+    });
+}
+
+/// Sub-steps needed so no body moves further than the smallest collider
+/// during one sub-step, capped at `world.substeps.max_substeps`.
+pub fn physics_substeps(world: &World, dt: f32) -> u32 {
+    let max_speed_sq = world
+        .velocities
+        .iter()
+        .zip(world.transforms.iter())
+        .filter_map(|(velocity, transform)| velocity.as_ref().zip(transform.as_ref()))
+        .map(|(velocity, _)| velocity.to_vec2().length_squared())
+        .fold(0.0, f32::max);
+    let smallest = world
+        .wall_bounce_colliders
+        .iter()
+        .flatten()
+        .map(|collider| collider.radius * 2.0)
+        .chain(
+            world
+                .colliders
+                .iter()
+                .flatten()
+                .map(|collider| match collider.shape {
+                    ColliderShape::Circle { radius } => radius * 2.0,
+                    ColliderShape::Aabb {
+                        half_width,
+                        half_height,
+                    } => half_width.min(half_height) * 2.0,
+                }),
+        )
+        .chain(Some(world.substeps.min_collider_size).filter(|size| *size > 0.0))
+        .fold(f32::INFINITY, f32::min);
+    if !smallest.is_finite() || smallest <= 0.0 {
+        return 1;
+    }
+
+    let travel = max_speed_sq.sqrt() * dt;
+    let needed = (travel / smallest).ceil() as u32;
+    needed.clamp(1, world.substeps.max_substeps.max(1))
+}
+
+/// Applies the field's per-edge behavior to bodies with a `BounceCollider`.
+/// Reflecting edges bounce the body using `field.wall_material` and the
+/// body's collider material, if it has one. `Exit` edges report the body in
+/// `world.field_exits` on every run while it touches or is past the edge; the
+/// list is replaced each run, like `world.contacts`, so readers belong later
+/// in the same sub-step.
 pub fn bounce_in_field(world: &mut World, _dt: f32) {
-    let field_width = world.field.width;
-    let field_height = world.field.height;
+    let field = world.field;
+    let mut exits = std::mem::take(&mut world.field_exits);
+    exits.clear();
 
-    for ((collider, transform), velocity) in world
+    for (idx, ((collider, transform), velocity)) in world
         .wall_bounce_colliders
         .iter()
         .zip(world.transforms.iter_mut())
         .zip(world.velocities.iter_mut())
+        .enumerate()
     {
         let (Some(collider), Some(transform), Some(velocity)) = (collider, transform, velocity)
         else {
             continue;
         };
-
+        let entity = EntityId(idx as u32);
+        let material = world
+            .colliders
+            .get(idx)
+            .and_then(|collider| collider.as_ref()?.material)
+            .unwrap_or_default();
+
+        let horizontal = FieldAxis {
+            extent: field.width,
+            low: (FieldEdge::Left, field.edges.left),
+            high: (FieldEdge::Right, field.edges.right),
+        };
+        let vertical = FieldAxis {
+            extent: field.height,
+            low: (FieldEdge::Top, field.edges.top),
+            high: (FieldEdge::Bottom, field.edges.bottom),
+        };
         let radius = collider.radius;
-
-        let mut bounce_x = false;
-        let mut bounce_y = false;
-
-        if transform.x - radius <= 0.0 || transform.x + radius >= field_width {
-            bounce_x = true;
-            transform.x = transform.x.clamp(radius, field_width - radius);
+        let mut position = transform.position.to_vec2();
+        let normal_x = horizontal.apply(entity, radius, &mut position.x, velocity.x(), &mut exits);
+        let normal_y = vertical.apply(entity, radius, &mut position.y, velocity.y(), &mut exits);
+        // Only write back an axis that moved, so a fixed-point position keeps
+        // the bits an `f32` can't hold.
+        if position.x != transform.position.x() {
+            transform.position.set_x(position.x);
+        }
+        if position.y != transform.position.y() {
+            transform.position.set_y(position.y);
         }
 
-        if transform.y - radius <= 0.0 || transform.y + radius >= field_height {
-            bounce_y = true;
-            transform.y = transform.y.clamp(radius, field_height - radius);
+        let mut wall = field.wall_material;
+        for normal in [
+            normal_x.map(|n| Vec2::new(n, 0.0)),
+            normal_y.map(|n| Vec2::new(0.0, n)),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *velocity = SimVec2::from_vec2(material.bounce(&wall, velocity.to_vec2(), normal));
+            // A corner hit still only speeds the body up once.
+            wall.speed_multiplier = 1.0;
         }
+    }
+
+    world.field_exits = exits;
+}
+
+/// One axis of the field: its length and the edges at either end.
+struct FieldAxis {
+    extent: f32,
+    low: (FieldEdge, EdgeBehavior),
+    high: (FieldEdge, EdgeBehavior),
+}
 
-        if bounce_x {
-            velocity.x = -velocity.x;
+impl FieldAxis {
+    /// Handles the edges the body touches on this axis, and returns the
+    /// normal sign of the reflecting edge it hit, if any.
+    fn apply(
+        &self,
+        entity: EntityId,
+        radius: f32,
+        position: &mut f32,
+        velocity: f32,
+        exits: &mut Vec<FieldExit>,
+    ) -> Option<f32> {
+        let mut normal = None;
+        for ((edge, behavior), touching, crossed, wrapped, pushes) in [
+            (
+                self.low,
+                *position - radius <= 0.0,
+                *position < 0.0,
+                *position + self.extent,
+                1.0,
+            ),
+            (
+                self.high,
+                *position + radius >= self.extent,
+                *position > self.extent,
+                *position - self.extent,
+                -1.0,
+            ),
+        ] {
+            if !touching {
+                continue;
+            }
+            match behavior {
+                // Squeezed between both walls, push against the motion.
+                EdgeBehavior::Reflect if normal.is_some() => normal = Some(-velocity.signum()),
+                EdgeBehavior::Reflect => normal = Some(pushes),
+                EdgeBehavior::PassThrough => {}
+                EdgeBehavior::Wrap if crossed => *position = wrapped,
+                EdgeBehavior::Wrap => {}
+                EdgeBehavior::Exit => exits.push(FieldExit { entity, edge }),
+            }
         }
-        if bounce_y {
-            velocity.y = -velocity.y;
+
+        if normal.is_some() {
+            let min = if self.low.1 == EdgeBehavior::Reflect {
+                radius
+            } else {
+                f32::NEG_INFINITY
+            };
+            let max = if self.high.1 == EdgeBehavior::Reflect {
+                self.extent - radius
+            } else {
+                f32::INFINITY
+            };
+            *position = position.max(min).min(max);
         }
+        normal
     }
 }
 
 #[cfg(test)]
 mod tests {
+    mod apply_acceleration {
+        use super::super::apply_acceleration;
+        use crate::engine::ecs::components::{Acceleration, Drag, MaxSpeed, Velocity};
+        use crate::engine::math::Vec2;
+        use crate::engine::World;
+
+        #[test]
+        fn acceleration_adds_to_velocity_over_time() {
+            let mut world = World::new(800.0, 600.0);
+            let entity = world.spawn();
+            world.set_velocity(entity, Velocity::new(10.0, 0.0));
+            world.set_acceleration(entity, Acceleration { x: 100.0, y: -50.0 });
+
+            apply_acceleration(&mut world, 0.1);
+
+            assert_eq!(world.velocity(entity).to_vec2(), Vec2::new(20.0, -5.0));
+        }
+
+        #[test]
+        fn drag_decays_velocity_exponentially() {
+            let mut world = World::new(800.0, 600.0);
+            let entity = world.spawn();
+            world.set_velocity(entity, Velocity::new(100.0, 0.0));
+            world.set_drag(entity, Drag { rate: 2.0 });
+
+            for _ in 0..10 {
+                apply_acceleration(&mut world, 0.05);
+            }
+
+            let expected = 100.0 * (-1.0_f32).exp();
+            assert!((world.velocity(entity).x() - expected).abs() < 1e-3);
+        }
+
+        #[test]
+        fn max_speed_clamps_the_magnitude_and_keeps_direction() {
+            let mut world = World::new(800.0, 600.0);
+            let entity = world.spawn();
+            world.set_velocity(entity, Velocity::new(30.0, 40.0));
+            world.set_acceleration(entity, Acceleration { x: 300.0, y: 400.0 });
+            world.set_max_speed(entity, MaxSpeed { value: 10.0 });
+
+            apply_acceleration(&mut world, 0.1);
+
+            let velocity = world.velocity(entity);
+            assert!((velocity.x() - 6.0).abs() < 1e-4);
+            assert!((velocity.y() - 8.0).abs() < 1e-4);
+        }
+
+        #[test]
+        fn bodies_without_the_components_keep_their_velocity() {
+            let mut world = World::new(800.0, 600.0);
+            let entity = world.spawn();
+            world.set_velocity(entity, Velocity::new(3.0, 4.0));
+
+            apply_acceleration(&mut world, 0.1);
+
+            assert_eq!(world.velocity(entity), &Velocity::new(3.0, 4.0));
+        }
+    }
+
     mod integrate_velocity {
         use super::super::integrate_velocity;
         use crate::engine::ecs::components::{Transform, Velocity};
@@ -61,20 +363,20 @@ mod tests {
         fn moves_entities_with_both_components() {
             let mut world = World::new(800.0, 600.0);
             let entity = world.spawn();
-            world.set_transform(entity, Transform { x: 0.0, y: 0.0 });
-            world.set_velocity(entity, Velocity { x: 100.0, y: 50.0 });
+            world.set_transform(entity, Transform::at(0.0, 0.0));
+            world.set_velocity(entity, Velocity::new(100.0, 50.0));
 
             integrate_velocity(&mut world, 0.1);
 
-            assert_eq!(world.transform(entity).x, 10.0);
-            assert_eq!(world.transform(entity).y, 5.0);
+            assert_eq!(world.transform(entity).position.x(), 10.0);
+            assert_eq!(world.transform(entity).position.y(), 5.0);
         }
 
         #[test]
         fn skips_entities_missing_transform() {
             let mut world = World::new(800.0, 600.0);
             let entity = world.spawn();
-            world.set_velocity(entity, Velocity { x: 100.0, y: 50.0 });
+            world.set_velocity(entity, Velocity::new(100.0, 50.0));
 
             integrate_velocity(&mut world, 0.1);
         }
@@ -83,12 +385,12 @@ mod tests {
         fn skips_entities_missing_velocity() {
             let mut world = World::new(800.0, 600.0);
             let entity = world.spawn();
-            world.set_transform(entity, Transform { x: 10.0, y: 20.0 });
+            world.set_transform(entity, Transform::at(10.0, 20.0));
 
             integrate_velocity(&mut world, 0.1);
 
-            assert_eq!(world.transform(entity).x, 10.0);
-            assert_eq!(world.transform(entity).y, 20.0);
+            assert_eq!(world.transform(entity).position.x(), 10.0);
+            assert_eq!(world.transform(entity).position.y(), 20.0);
         }
 
         #[test]
@@ -96,41 +398,290 @@ mod tests {
             let mut world = World::new(800.0, 600.0);
 
             let entity1 = world.spawn();
-            world.set_transform(entity1, Transform { x: 0.0, y: 0.0 });
-            world.set_velocity(entity1, Velocity { x: 100.0, y: 50.0 });
+            world.set_transform(entity1, Transform::at(0.0, 0.0));
+            world.set_velocity(entity1, Velocity::new(100.0, 50.0));
 
             let entity2 = world.spawn();
-            world.set_transform(entity2, Transform { x: 10.0, y: 20.0 });
-            world.set_velocity(entity2, Velocity { x: 20.0, y: 30.0 });
+            world.set_transform(entity2, Transform::at(10.0, 20.0));
+            world.set_velocity(entity2, Velocity::new(20.0, 30.0));
 
             integrate_velocity(&mut world, 0.1);
 
-            assert_eq!(world.transform(entity1).x, 10.0);
-            assert_eq!(world.transform(entity1).y, 5.0);
-            assert_eq!(world.transform(entity2).x, 12.0);
-            assert_eq!(world.transform(entity2).y, 23.0);
+            assert_eq!(world.transform(entity1).position.x(), 10.0);
+            assert_eq!(world.transform(entity1).position.y(), 5.0);
+            assert_eq!(world.transform(entity2).position.x(), 12.0);
+            assert_eq!(world.transform(entity2).position.y(), 23.0);
+        }
+    }
+
+    mod track_kinematic_bodies {
+        use super::super::{track_kinematic_bodies, track_kinematic_input};
+        use crate::engine::ecs::components::{KinematicBody, SimVec2, Transform};
+        use crate::engine::ecs::entity::EntityId;
+        use crate::engine::ecs::resources::{EdgeBehavior, FieldEdges};
+        use crate::engine::math::Vec2;
+        use crate::engine::World;
+
+        fn tracked_body(world: &mut World, x: f32, y: f32) -> EntityId {
+            let entity = world.spawn();
+            world.set_transform(entity, Transform::at(x, y));
+            world.set_kinematic_body(entity, KinematicBody::default());
+            entity
+        }
+
+        #[test]
+        fn first_step_reports_rest() {
+            let mut world = World::new(800.0, 600.0);
+            let entity = tracked_body(&mut world, 100.0, 100.0);
+
+            track_kinematic_bodies(&mut world, 0.1);
+
+            assert_eq!(world.kinematic_body(entity).velocity, Vec2::ZERO);
+        }
+
+        #[test]
+        fn velocity_follows_the_actual_displacement() {
+            let mut world = World::new(800.0, 600.0);
+            let entity = tracked_body(&mut world, 100.0, 100.0);
+            track_kinematic_bodies(&mut world, 0.1);
+
+            world.transform_mut(entity).position += SimVec2::new(5.0, -2.0);
+            track_kinematic_bodies(&mut world, 0.1);
+            assert_eq!(
+                world.kinematic_body(entity).velocity,
+                Vec2::new(50.0, -20.0)
+            );
+
+            track_kinematic_bodies(&mut world, 0.1);
+            assert_eq!(world.kinematic_body(entity).velocity, Vec2::ZERO);
+        }
+
+        #[test]
+        fn teleport_does_not_read_as_movement() {
+            let mut world = World::new(800.0, 600.0);
+            let entity = tracked_body(&mut world, 100.0, 100.0);
+            track_kinematic_bodies(&mut world, 0.1);
+
+            world.transform_mut(entity).position = SimVec2::new(700.0, 500.0);
+            world.kinematic_body_mut(entity).teleported();
+            track_kinematic_bodies(&mut world, 0.1);
+
+            assert_eq!(world.kinematic_body(entity).velocity, Vec2::ZERO);
+        }
+
+        #[test]
+        fn moves_made_before_physics_count_in_every_sub_step() {
+            let mut world = World::new(800.0, 600.0);
+            let entity = tracked_body(&mut world, 100.0, 100.0);
+            track_kinematic_input(&mut world, 0.1);
+            track_kinematic_bodies(&mut world, 0.05);
+            track_kinematic_bodies(&mut world, 0.05);
+
+            world.transform_mut(entity).position += SimVec2::new(5.0, 0.0);
+            track_kinematic_input(&mut world, 0.1);
+            for _ in 0..2 {
+                track_kinematic_bodies(&mut world, 0.05);
+                assert_eq!(world.kinematic_body(entity).velocity, Vec2::new(50.0, 0.0));
+            }
+        }
+
+        #[test]
+        fn crossing_a_wrapping_edge_takes_the_short_way() {
+            let mut world = World::new(100.0, 100.0);
+            world.field.edges = FieldEdges::all(EdgeBehavior::Wrap);
+            let entity = tracked_body(&mut world, 98.0, 50.0);
+            track_kinematic_bodies(&mut world, 0.5);
+
+            world.transform_mut(entity).position.set_x(1.0);
+            track_kinematic_bodies(&mut world, 0.5);
+
+            assert_eq!(world.kinematic_body(entity).velocity, Vec2::new(6.0, 0.0));
+        }
+    }
+
+    mod physics_substeps {
+        use super::super::physics_substeps;
+        use crate::engine::ecs::components::{BounceCollider, Collider, Transform, Velocity};
+        use crate::engine::World;
+
+        fn world_with_ball(speed: f32, radius: f32) -> World {
+            let mut world = World::new(800.0, 600.0);
+            let ball = world.spawn();
+            world.set_transform(ball, Transform::at(0.0, 0.0));
+            world.set_velocity(ball, Velocity::new(speed, 0.0));
+            world.set_wall_bounce_collider(ball, BounceCollider { radius });
+            world
+        }
+
+        #[test]
+        fn slow_bodies_need_a_single_step() {
+            let world = world_with_ball(100.0, 8.0);
+
+            assert_eq!(physics_substeps(&world, 0.05), 1);
+        }
+
+        #[test]
+        fn limits_travel_to_the_smallest_collider() {
+            let mut world = world_with_ball(900.0, 8.0);
+            assert_eq!(physics_substeps(&world, 0.05), 3);
+
+            world.substeps.min_collider_size = 10.0;
+            assert_eq!(physics_substeps(&world, 0.05), 5);
+        }
+
+        #[test]
+        fn contact_colliders_count_by_their_thinnest_side() {
+            let mut world = world_with_ball(900.0, 8.0);
+            let wall = world.spawn();
+            world.set_transform(wall, Transform::at(400.0, 300.0));
+            world.set_collider(wall, Collider::aabb(5.0, 30.0));
+
+            assert_eq!(physics_substeps(&world, 0.05), 5);
+        }
+
+        #[test]
+        fn respects_the_sub_step_cap() {
+            let mut world = world_with_ball(2000.0, 2.0);
+            world.substeps.max_substeps = 4;
+
+            assert_eq!(physics_substeps(&world, 0.05), 4);
         }
     }
 
     mod bounce_in_field {
         use super::super::bounce_in_field;
-        use crate::engine::ecs::components::{BounceCollider, Transform, Velocity};
+        use crate::engine::ecs::components::{
+            BounceCollider, Collider, Material, Transform, Velocity,
+        };
+        use crate::engine::ecs::entity::EntityId;
+        use crate::engine::ecs::resources::{EdgeBehavior, FieldEdge, FieldEdges, FieldExit};
         use crate::engine::World;
 
+        fn world_with_ball(x: f32, y: f32, vx: f32, vy: f32) -> (World, EntityId) {
+            let mut world = World::new(100.0, 50.0);
+            let ball = world.spawn();
+            world.set_transform(ball, Transform::at(x, y));
+            world.set_velocity(ball, Velocity::new(vx, vy));
+            world.set_wall_bounce_collider(ball, BounceCollider { radius: 2.0 });
+            (world, ball)
+        }
+
+        #[test]
+        fn pass_through_edges_leave_the_body_alone() {
+            let (mut world, ball) = world_with_ball(-5.0, 25.0, -10.0, 0.0);
+            world.field.edges = FieldEdges::all(EdgeBehavior::PassThrough);
+
+            bounce_in_field(&mut world, 0.0);
+
+            assert_eq!(world.transform(ball).position.x(), -5.0);
+            assert_eq!(world.velocity(ball).x(), -10.0);
+            assert!(world.field_exits.is_empty());
+        }
+
+        #[test]
+        fn wrap_edges_move_the_body_once_its_center_crosses() {
+            let (mut world, ball) = world_with_ball(1.0, 25.0, -10.0, 0.0);
+            world.field.edges = FieldEdges::all(EdgeBehavior::Wrap);
+
+            bounce_in_field(&mut world, 0.0);
+            assert_eq!(world.transform(ball).position.x(), 1.0);
+
+            world.transform_mut(ball).position.set_x(-1.0);
+            bounce_in_field(&mut world, 0.0);
+            assert_eq!(world.transform(ball).position.x(), 99.0);
+            assert_eq!(world.velocity(ball).x(), -10.0);
+        }
+
+        #[test]
+        fn exit_edges_report_the_body_every_run_it_is_out() {
+            let (mut world, ball) = world_with_ball(50.0, 49.0, 0.0, 10.0);
+            world.field.edges = FieldEdges::default().with(FieldEdge::Bottom, EdgeBehavior::Exit);
+
+            bounce_in_field(&mut world, 0.0);
+            let exit = FieldExit {
+                entity: ball,
+                edge: FieldEdge::Bottom,
+            };
+            assert_eq!(world.field_exits, vec![exit]);
+            assert_eq!(world.velocity(ball).y(), 10.0);
+
+            world.transform_mut(ball).position.set_y(80.0);
+            bounce_in_field(&mut world, 0.0);
+            assert_eq!(world.field_exits, vec![exit]);
+
+            world.transform_mut(ball).position.set_y(25.0);
+            bounce_in_field(&mut world, 0.0);
+            assert!(world.field_exits.is_empty());
+        }
+
+        #[test]
+        fn open_side_does_not_clamp_a_reflecting_axis() {
+            let (mut world, ball) = world_with_ball(1.0, 25.0, -10.0, 0.0);
+            world.field.edges = FieldEdges::default().with(FieldEdge::Right, EdgeBehavior::Exit);
+
+            bounce_in_field(&mut world, 0.0);
+            assert_eq!(world.transform(ball).position.x(), 2.0);
+            assert_eq!(world.velocity(ball).x(), 10.0);
+
+            world.transform_mut(ball).position.set_x(120.0);
+            bounce_in_field(&mut world, 0.0);
+            assert_eq!(world.transform(ball).position.x(), 120.0);
+            assert_eq!(world.field_exits.len(), 1);
+        }
+
         #[test]
         fn clamps_position_and_reflects_velocity() {
             let mut world = World::new(16.0, 16.0);
             let entity = world.spawn();
-            world.set_transform(entity, Transform { x: 1.0, y: 1.0 });
-            world.set_velocity(entity, Velocity { x: -5.0, y: -7.0 });
+            world.set_transform(entity, Transform::at(1.0, 1.0));
+            world.set_velocity(entity, Velocity::new(-5.0, -7.0));
             world.set_wall_bounce_collider(entity, BounceCollider { radius: 2.0 });
 
             bounce_in_field(&mut world, 0.0);
 
-            assert_eq!(world.transform(entity).x, 2.0);
-            assert_eq!(world.transform(entity).y, 2.0);
-            assert_eq!(world.velocity(entity).x, 5.0);
-            assert_eq!(world.velocity(entity).y, 7.0);
+            assert_eq!(world.transform(entity).position.x(), 2.0);
+            assert_eq!(world.transform(entity).position.y(), 2.0);
+            assert_eq!(world.velocity(entity).x(), 5.0);
+            assert_eq!(world.velocity(entity).y(), 7.0);
+        }
+
+        #[test]
+        fn wall_material_damps_and_slows_the_bounce() {
+            let (mut world, ball) = world_with_ball(1.0, 25.0, -10.0, 4.0);
+            world.field.wall_material = Material::ELASTIC
+                .with_restitution(0.5)
+                .with_friction(0.25)
+                .with_speed_multiplier(2.0);
+
+            bounce_in_field(&mut world, 0.0);
+
+            assert_eq!(world.transform(ball).position.x(), 2.0);
+            assert_eq!(world.velocity(ball).x(), 10.0);
+            assert_eq!(world.velocity(ball).y(), 6.0);
+        }
+
+        #[test]
+        fn body_material_mixes_with_the_wall() {
+            let (mut world, ball) = world_with_ball(1.0, 25.0, -10.0, 0.0);
+            world.field.wall_material = Material::ELASTIC.with_restitution(0.5);
+            world.set_collider(
+                ball,
+                Collider::circle(2.0).with_material(Material::ELASTIC.with_restitution(0.5)),
+            );
+
+            bounce_in_field(&mut world, 0.0);
+
+            assert_eq!(world.velocity(ball).x(), 2.5);
+        }
+
+        #[test]
+        fn bodies_leaving_a_wall_are_not_bounced_back() {
+            let (mut world, ball) = world_with_ball(1.0, 25.0, 10.0, 0.0);
+
+            bounce_in_field(&mut world, 0.0);
+
+            assert_eq!(world.transform(ball).position.x(), 2.0);
+            assert_eq!(world.velocity(ball).x(), 10.0);
         }
 
         #[test]
@@ -138,29 +689,29 @@ mod tests {
             let mut world = World::new(16.0, 16.0);
 
             let missing_transform = world.spawn();
-            world.set_velocity(missing_transform, Velocity { x: -3.0, y: -4.0 });
+            world.set_velocity(missing_transform, Velocity::new(-3.0, -4.0));
             world.set_wall_bounce_collider(missing_transform, BounceCollider { radius: 2.0 });
 
             let missing_velocity = world.spawn();
-            world.set_transform(missing_velocity, Transform { x: 1.0, y: 1.0 });
+            world.set_transform(missing_velocity, Transform::at(1.0, 1.0));
             world.set_wall_bounce_collider(missing_velocity, BounceCollider { radius: 2.0 });
 
             let valid = world.spawn();
-            world.set_transform(valid, Transform { x: 1.0, y: 1.0 });
-            world.set_velocity(valid, Velocity { x: -5.0, y: -7.0 });
+            world.set_transform(valid, Transform::at(1.0, 1.0));
+            world.set_velocity(valid, Velocity::new(-5.0, -7.0));
             world.set_wall_bounce_collider(valid, BounceCollider { radius: 2.0 });
 
             bounce_in_field(&mut world, 0.0);
 
-            assert_eq!(world.transform(valid).x, 2.0);
-            assert_eq!(world.transform(valid).y, 2.0);
-            assert_eq!(world.velocity(valid).x, 5.0);
-            assert_eq!(world.velocity(valid).y, 7.0);
+            assert_eq!(world.transform(valid).position.x(), 2.0);
+            assert_eq!(world.transform(valid).position.y(), 2.0);
+            assert_eq!(world.velocity(valid).x(), 5.0);
+            assert_eq!(world.velocity(valid).y(), 7.0);
 
-            assert_eq!(world.transform(missing_velocity).x, 1.0);
-            assert_eq!(world.transform(missing_velocity).y, 1.0);
-            assert_eq!(world.velocity(missing_transform).x, -3.0);
-            assert_eq!(world.velocity(missing_transform).y, -4.0);
+            assert_eq!(world.transform(missing_velocity).position.x(), 1.0);
+            assert_eq!(world.transform(missing_velocity).position.y(), 1.0);
+            assert_eq!(world.velocity(missing_transform).x(), -3.0);
+            assert_eq!(world.velocity(missing_transform).y(), -4.0);
         }
     }
 }
@@ -0,0 +1,153 @@
+pub const MIN_TIME_SCALE: f32 = 0.1;
+pub const MAX_TIME_SCALE: f32 = 4.0;
+
+/// Simulation clock. `Engine::step` feeds it the clamped frame time; systems
+/// read the scaled and unscaled deltas and the total elapsed time from it.
+/// Pause, time scale and frame stepping are debug controls and are not saved.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Time {
+    delta: f32,
+    unscaled_delta: f32,
+    elapsed: f64,
+    scale: f32,
+    paused: bool,
+    pending_frames: u32,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self {
+            delta: 0.0,
+            unscaled_delta: 0.0,
+            elapsed: 0.0,
+            scale: 1.0,
+            paused: false,
+            pending_frames: 0,
+        }
+    }
+}
+
+impl Time {
+    /// Scaled seconds simulated this frame; zero while paused.
+    pub fn delta(&self) -> f32 {
+        self.delta
+    }
+
+    /// Real (clamped) seconds this frame, ignoring pause and time scale.
+    pub fn unscaled_delta(&self) -> f32 {
+        self.unscaled_delta
+    }
+
+    /// Scaled seconds simulated since the world was created.
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+
+    pub fn set_elapsed(&mut self, elapsed: f64) {
+        self.elapsed = elapsed;
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Sets the time scale, clamped to `MIN_TIME_SCALE..=MAX_TIME_SCALE`.
+    /// Returns the scale actually applied.
+    pub fn set_scale(&mut self, scale: f32) -> f32 {
+        if scale.is_finite() {
+            self.scale = scale.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+        }
+        self.scale
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes and drops any frame steps that were still queued.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.pending_frames = 0;
+    }
+
+    /// While paused, lets exactly one more frame run.
+    pub fn step_frame(&mut self) {
+        if self.paused {
+            self.pending_frames += 1;
+        }
+    }
+
+    /// Starts a frame of `unscaled_dt` seconds. Returns the scaled dt to run
+    /// the schedule with, or `None` if the frame is skipped because of pause.
+    pub fn begin_frame(&mut self, unscaled_dt: f32) -> Option<f32> {
+        self.unscaled_delta = unscaled_dt;
+        if self.paused {
+            if self.pending_frames == 0 {
+                self.delta = 0.0;
+                return None;
+            }
+            self.pending_frames -= 1;
+        }
+        self.delta = unscaled_dt * self.scale;
+        self.elapsed += f64::from(self.delta);
+        Some(self.delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scales_delta_and_accumulates_elapsed() {
+        let mut time = Time::default();
+        time.set_scale(0.5);
+
+        assert_eq!(time.begin_frame(0.25), Some(0.125));
+        assert_eq!(time.begin_frame(0.25), Some(0.125));
+
+        assert_eq!(time.delta(), 0.125);
+        assert_eq!(time.unscaled_delta(), 0.25);
+        assert_eq!(time.elapsed(), 0.25);
+    }
+
+    #[test]
+    fn clamps_time_scale() {
+        let mut time = Time::default();
+
+        assert_eq!(time.set_scale(10.0), MAX_TIME_SCALE);
+        assert_eq!(time.set_scale(0.0), MIN_TIME_SCALE);
+        assert_eq!(time.set_scale(f32::NAN), MIN_TIME_SCALE);
+    }
+
+    #[test]
+    fn paused_frames_are_skipped_unless_stepped() {
+        let mut time = Time::default();
+        time.pause();
+
+        assert_eq!(time.begin_frame(0.25), None);
+        assert_eq!(time.delta(), 0.0);
+        assert_eq!(time.unscaled_delta(), 0.25);
+
+        time.step_frame();
+        assert_eq!(time.begin_frame(0.25), Some(0.25));
+        assert_eq!(time.begin_frame(0.25), None);
+        assert_eq!(time.elapsed(), 0.25);
+    }
+
+    #[test]
+    fn resume_drops_queued_frame_steps() {
+        let mut time = Time::default();
+        time.pause();
+        time.step_frame();
+        time.step_frame();
+        time.resume();
+        time.pause();
+
+        assert_eq!(time.begin_frame(0.25), None);
+    }
+}
@@ -0,0 +1,242 @@
+use std::any::Any;
+
+use super::schedule::SystemPhase;
+use super::world::World;
+
+/// Engine phase that ticks timers once per frame, after the sub-stepped
+/// physics group. Ticking per sub-step would clear `just_finished` before
+/// later phases could see it.
+pub const TIMER_PHASE: SystemPhase = SystemPhase::Custom("Timers");
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimerMode {
+    /// Counts up to its duration once, then stays finished.
+    Once,
+    /// Wraps around every time it reaches its duration.
+    Repeating,
+}
+
+/// Countdown usable as a component (ticked by [`tick_timers`]) or inside a
+/// resource (ticked by [`tick_timer_resource`]). Systems react to
+/// [`Timer::just_finished`], which only holds for the tick that completed it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timer {
+    duration: f32,
+    elapsed: f32,
+    mode: TimerMode,
+    paused: bool,
+    times_finished: u32,
+}
+
+impl Timer {
+    pub fn new(duration: f32, mode: TimerMode) -> Self {
+        Self::restore(duration, 0.0, mode, false, 0)
+    }
+
+    /// One-shot timer. A zero duration starts out already finished.
+    pub fn once(duration: f32) -> Self {
+        Self::new(duration, TimerMode::Once)
+    }
+
+    pub fn repeating(duration: f32) -> Self {
+        Self::new(duration, TimerMode::Repeating)
+    }
+
+    pub fn restore(
+        duration: f32,
+        elapsed: f32,
+        mode: TimerMode,
+        paused: bool,
+        times_finished: u32,
+    ) -> Self {
+        Self {
+            duration,
+            elapsed,
+            mode,
+            paused,
+            times_finished,
+        }
+    }
+
+    /// Advances by `dt` seconds. Returns true if the timer finished this tick.
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.times_finished = 0;
+        if self.paused || self.finished() {
+            return false;
+        }
+        self.elapsed += dt;
+        if self.elapsed < self.duration {
+            return false;
+        }
+        match self.mode {
+            TimerMode::Once => {
+                self.elapsed = self.duration;
+                self.times_finished = 1;
+            }
+            TimerMode::Repeating if self.duration <= 0.0 => {
+                self.elapsed = 0.0;
+                self.times_finished = 1;
+            }
+            TimerMode::Repeating => {
+                let laps = (self.elapsed / self.duration).floor();
+                self.elapsed -= laps * self.duration;
+                self.times_finished = laps as u32;
+            }
+        }
+        true
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn remaining(&self) -> f32 {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    /// Progress through the current run, from 0.0 to 1.0.
+    pub fn fraction(&self) -> f32 {
+        if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).min(1.0)
+        }
+    }
+
+    pub fn mode(&self) -> TimerMode {
+        self.mode
+    }
+
+    /// True once a one-shot timer has run out. Repeating timers never finish.
+    pub fn finished(&self) -> bool {
+        self.mode == TimerMode::Once && self.elapsed >= self.duration
+    }
+
+    /// True only for the tick in which the timer ran out.
+    pub fn just_finished(&self) -> bool {
+        self.times_finished > 0
+    }
+
+    /// How many times the timer ran out during the last tick. Repeating
+    /// timers shorter than a frame can finish more than once.
+    pub fn times_finished(&self) -> u32 {
+        self.times_finished
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Starts the current run over without changing duration or mode.
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.times_finished = 0;
+    }
+}
+
+impl AsMut<Timer> for Timer {
+    fn as_mut(&mut self) -> &mut Timer {
+        self
+    }
+}
+
+/// Engine system: ticks every `Timer` component.
+pub fn tick_timers(world: &mut World, dt: f32) {
+    for timer in world.timers.iter_mut().flatten() {
+        timer.tick(dt);
+    }
+}
+
+/// Ticks the timer held by resource `R`. Schedule one per timer resource.
+pub fn tick_timer_resource<R: AsMut<Timer> + Any>(world: &mut World, dt: f32) {
+    world.resource_mut::<R>().as_mut().tick(dt);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_shot_finishes_once_and_stays_finished() {
+        let mut timer = Timer::once(1.0);
+
+        assert!(!timer.tick(0.75));
+        assert_eq!(timer.fraction(), 0.75);
+        assert!(timer.tick(0.5));
+        assert!(timer.just_finished());
+        assert!(timer.finished());
+        assert_eq!(timer.remaining(), 0.0);
+
+        assert!(!timer.tick(0.5));
+        assert!(!timer.just_finished());
+        assert!(timer.finished());
+    }
+
+    #[test]
+    fn zero_length_one_shot_starts_finished() {
+        let mut timer = Timer::once(0.0);
+
+        assert!(timer.finished());
+        assert!(!timer.tick(0.1));
+    }
+
+    #[test]
+    fn repeating_timer_wraps_and_counts_laps() {
+        let mut timer = Timer::repeating(0.25);
+
+        assert!(timer.tick(0.625));
+        assert_eq!(timer.times_finished(), 2);
+        assert_eq!(timer.elapsed(), 0.125);
+        assert!(!timer.finished());
+    }
+
+    #[test]
+    fn paused_timer_does_not_advance() {
+        let mut timer = Timer::once(1.0);
+        timer.pause();
+
+        assert!(!timer.tick(2.0));
+        assert_eq!(timer.elapsed(), 0.0);
+
+        timer.resume();
+        assert!(timer.tick(2.0));
+    }
+
+    struct Cooldown {
+        timer: Timer,
+    }
+
+    impl AsMut<Timer> for Cooldown {
+        fn as_mut(&mut self) -> &mut Timer {
+            &mut self.timer
+        }
+    }
+
+    #[test]
+    fn engine_systems_tick_components_and_resources() {
+        let mut world = World::new(800.0, 600.0);
+        let entity = world.spawn();
+        world.set_timer(entity, Timer::repeating(0.5));
+        world.insert_resource(Cooldown {
+            timer: Timer::once(0.5),
+        });
+
+        tick_timers(&mut world, 0.5);
+        tick_timer_resource::<Cooldown>(&mut world, 0.5);
+
+        assert!(world.timer(entity).just_finished());
+        assert!(world.resource::<Cooldown>().timer.just_finished());
+    }
+}
@@ -1,6 +1,14 @@
-use super::components::{BounceCollider, Spin, Transform, Velocity};
+use super::components::{
+    Acceleration, BounceCollider, Collider, Drag, KinematicBody, MaxSpeed, Spin, Transform,
+    Velocity,
+};
+use super::contacts::Contact;
 use super::entity::{EntityAllocator, EntityId};
-use super::resources::{FieldBounds, InputBits};
+use super::layers::CollisionLayers;
+use super::resources::{FieldBounds, FieldExit, InputBits, SimRng, SubstepConfig};
+use super::spatial_hash::SpatialHash;
+use super::time::Time;
+use super::timer::Timer;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 
@@ -9,8 +17,27 @@ pub struct World {
     pub velocities: Vec<Option<Velocity>>,
     pub wall_bounce_colliders: Vec<Option<BounceCollider>>,
     pub spins: Vec<Option<Spin>>,
+    pub timers: Vec<Option<Timer>>,
+    pub colliders: Vec<Option<Collider>>,
+    pub accelerations: Vec<Option<Acceleration>>,
+    pub drags: Vec<Option<Drag>>,
+    pub max_speeds: Vec<Option<MaxSpeed>>,
+    pub kinematic_bodies: Vec<Option<KinematicBody>>,
+    /// Contacts found by `detect_contacts` during the latest physics step.
+    pub contacts: Vec<Contact>,
+    /// Broadphase over collider bounds, refreshed by `update_spatial_hash`.
+    pub spatial_hash: SpatialHash,
+    pub collision_layers: CollisionLayers,
+    /// Bodies at an `Exit` edge during the latest `bounce_in_field` run.
+    pub field_exits: Vec<FieldExit>,
     pub input: InputBits,
     pub field: FieldBounds,
+    pub rng: SimRng,
+    pub time: Time,
+    pub substeps: SubstepConfig,
+    /// Time banked by each fixed-rate system or phase, indexed by the slot
+    /// its schedule gave it.
+    pub fixed_rate_accumulators: Vec<f32>,
     resources: HashMap<TypeId, Box<dyn Any>>,
     allocator: EntityAllocator,
 }
@@ -22,8 +49,22 @@ impl World {
             velocities: Vec::new(),
             wall_bounce_colliders: Vec::new(),
             spins: Vec::new(),
+            timers: Vec::new(),
+            colliders: Vec::new(),
+            accelerations: Vec::new(),
+            drags: Vec::new(),
+            max_speeds: Vec::new(),
+            kinematic_bodies: Vec::new(),
+            contacts: Vec::new(),
+            spatial_hash: SpatialHash::default(),
+            collision_layers: CollisionLayers::new(),
+            field_exits: Vec::new(),
             input: InputBits { bits: 0 },
-            field: FieldBounds { width, height },
+            field: FieldBounds::new(width, height),
+            rng: SimRng::from_entropy(),
+            time: Time::default(),
+            substeps: SubstepConfig::default(),
+            fixed_rate_accumulators: Vec::new(),
             resources: HashMap::new(),
             allocator: EntityAllocator::new(),
         }
@@ -50,6 +91,25 @@ impl World {
         if idx < self.spins.len() {
             self.spins[idx] = None;
         }
+        if idx < self.timers.len() {
+            self.timers[idx] = None;
+        }
+        if idx < self.colliders.len() {
+            self.colliders[idx] = None;
+        }
+        if idx < self.accelerations.len() {
+            self.accelerations[idx] = None;
+        }
+        if idx < self.drags.len() {
+            self.drags[idx] = None;
+        }
+        if idx < self.max_speeds.len() {
+            self.max_speeds[idx] = None;
+        }
+        if idx < self.kinematic_bodies.len() {
+            self.kinematic_bodies[idx] = None;
+        }
+        self.spatial_hash.remove(entity);
         self.allocator.free(entity);
     }
 
@@ -73,6 +133,36 @@ impl World {
         self.spins[entity.0 as usize] = Some(value);
     }
 
+    pub fn set_timer(&mut self, entity: EntityId, value: Timer) {
+        self.ensure_capacity(entity.0 as usize);
+        self.timers[entity.0 as usize] = Some(value);
+    }
+
+    pub fn set_collider(&mut self, entity: EntityId, value: Collider) {
+        self.ensure_capacity(entity.0 as usize);
+        self.colliders[entity.0 as usize] = Some(value);
+    }
+
+    pub fn set_acceleration(&mut self, entity: EntityId, value: Acceleration) {
+        self.ensure_capacity(entity.0 as usize);
+        self.accelerations[entity.0 as usize] = Some(value);
+    }
+
+    pub fn set_drag(&mut self, entity: EntityId, value: Drag) {
+        self.ensure_capacity(entity.0 as usize);
+        self.drags[entity.0 as usize] = Some(value);
+    }
+
+    pub fn set_max_speed(&mut self, entity: EntityId, value: MaxSpeed) {
+        self.ensure_capacity(entity.0 as usize);
+        self.max_speeds[entity.0 as usize] = Some(value);
+    }
+
+    pub fn set_kinematic_body(&mut self, entity: EntityId, value: KinematicBody) {
+        self.ensure_capacity(entity.0 as usize);
+        self.kinematic_bodies[entity.0 as usize] = Some(value);
+    }
+
     pub fn transform(&self, entity: EntityId) -> &Transform {
         let idx = entity.0 as usize;
         self.transforms
@@ -105,17 +195,33 @@ impl World {
             .expect("velocity component missing")
     }
 
-    pub fn collider(&self, entity: EntityId) -> &BounceCollider {
+    pub fn wall_bounce_collider(&self, entity: EntityId) -> &BounceCollider {
         let idx = entity.0 as usize;
         self.wall_bounce_colliders
             .get(idx)
             .and_then(|opt| opt.as_ref())
-            .expect("collider component missing")
+            .expect("wall bounce collider component missing")
     }
 
-    pub fn collider_mut(&mut self, entity: EntityId) -> &mut BounceCollider {
+    pub fn wall_bounce_collider_mut(&mut self, entity: EntityId) -> &mut BounceCollider {
         let idx = entity.0 as usize;
         self.wall_bounce_colliders
+            .get_mut(idx)
+            .and_then(|opt| opt.as_mut())
+            .expect("wall bounce collider component missing")
+    }
+
+    pub fn collider(&self, entity: EntityId) -> &Collider {
+        let idx = entity.0 as usize;
+        self.colliders
+            .get(idx)
+            .and_then(|opt| opt.as_ref())
+            .expect("collider component missing")
+    }
+
+    pub fn collider_mut(&mut self, entity: EntityId) -> &mut Collider {
+        let idx = entity.0 as usize;
+        self.colliders
             .get_mut(idx)
             .and_then(|opt| opt.as_mut())
             .expect("collider component missing")
@@ -137,6 +243,86 @@ impl World {
             .expect("spin component missing")
     }
 
+    pub fn timer(&self, entity: EntityId) -> &Timer {
+        let idx = entity.0 as usize;
+        self.timers
+            .get(idx)
+            .and_then(|opt| opt.as_ref())
+            .expect("timer component missing")
+    }
+
+    pub fn timer_mut(&mut self, entity: EntityId) -> &mut Timer {
+        let idx = entity.0 as usize;
+        self.timers
+            .get_mut(idx)
+            .and_then(|opt| opt.as_mut())
+            .expect("timer component missing")
+    }
+
+    pub fn acceleration(&self, entity: EntityId) -> &Acceleration {
+        let idx = entity.0 as usize;
+        self.accelerations
+            .get(idx)
+            .and_then(|opt| opt.as_ref())
+            .expect("acceleration component missing")
+    }
+
+    pub fn acceleration_mut(&mut self, entity: EntityId) -> &mut Acceleration {
+        let idx = entity.0 as usize;
+        self.accelerations
+            .get_mut(idx)
+            .and_then(|opt| opt.as_mut())
+            .expect("acceleration component missing")
+    }
+
+    pub fn drag(&self, entity: EntityId) -> &Drag {
+        let idx = entity.0 as usize;
+        self.drags
+            .get(idx)
+            .and_then(|opt| opt.as_ref())
+            .expect("drag component missing")
+    }
+
+    pub fn drag_mut(&mut self, entity: EntityId) -> &mut Drag {
+        let idx = entity.0 as usize;
+        self.drags
+            .get_mut(idx)
+            .and_then(|opt| opt.as_mut())
+            .expect("drag component missing")
+    }
+
+    pub fn max_speed(&self, entity: EntityId) -> &MaxSpeed {
+        let idx = entity.0 as usize;
+        self.max_speeds
+            .get(idx)
+            .and_then(|opt| opt.as_ref())
+            .expect("max speed component missing")
+    }
+
+    pub fn max_speed_mut(&mut self, entity: EntityId) -> &mut MaxSpeed {
+        let idx = entity.0 as usize;
+        self.max_speeds
+            .get_mut(idx)
+            .and_then(|opt| opt.as_mut())
+            .expect("max speed component missing")
+    }
+
+    pub fn kinematic_body(&self, entity: EntityId) -> &KinematicBody {
+        let idx = entity.0 as usize;
+        self.kinematic_bodies
+            .get(idx)
+            .and_then(|opt| opt.as_ref())
+            .expect("kinematic body component missing")
+    }
+
+    pub fn kinematic_body_mut(&mut self, entity: EntityId) -> &mut KinematicBody {
+        let idx = entity.0 as usize;
+        self.kinematic_bodies
+            .get_mut(idx)
+            .and_then(|opt| opt.as_mut())
+            .expect("kinematic body component missing")
+    }
+
     pub fn insert_resource<T: Any>(&mut self, value: T) {
         self.resources.insert(TypeId::of::<T>(), Box::new(value));
     }
@@ -157,6 +343,14 @@ impl World {
             .unwrap_or_else(|| panic!("resource {} not found", type_name))
     }
 
+    pub fn allocator(&self) -> &EntityAllocator {
+        &self.allocator
+    }
+
+    pub fn set_allocator(&mut self, allocator: EntityAllocator) {
+        self.allocator = allocator;
+    }
+
     fn ensure_capacity(&mut self, idx: usize) {
         let target = idx + 1;
         if self.transforms.len() < target {
@@ -171,6 +365,24 @@ impl World {
         if self.spins.len() < target {
             self.spins.resize_with(target, || None);
         }
+        if self.timers.len() < target {
+            self.timers.resize_with(target, || None);
+        }
+        if self.colliders.len() < target {
+            self.colliders.resize_with(target, || None);
+        }
+        if self.accelerations.len() < target {
+            self.accelerations.resize_with(target, || None);
+        }
+        if self.drags.len() < target {
+            self.drags.resize_with(target, || None);
+        }
+        if self.max_speeds.len() < target {
+            self.max_speeds.resize_with(target, || None);
+        }
+        if self.kinematic_bodies.len() < target {
+            self.kinematic_bodies.resize_with(target, || None);
+        }
     }
 }
 
@@ -189,13 +401,19 @@ mod tests {
         assert!(world.velocities.len() > idx);
         assert!(world.wall_bounce_colliders.len() > idx);
         assert!(world.spins.len() > idx);
+        assert!(world.timers.len() > idx);
+        assert!(world.colliders.len() > idx);
+        assert!(world.accelerations.len() > idx);
+        assert!(world.drags.len() > idx);
+        assert!(world.max_speeds.len() > idx);
+        assert!(world.kinematic_bodies.len() > idx);
     }
 
     #[test]
     fn get_component_returns_what_was_set() {
         let mut world = World::new(800.0, 600.0);
         let entity = world.spawn();
-        let transform = Transform { x: 1.0, y: 2.0 };
+        let transform = Transform::at(1.0, 2.0);
         world.set_transform(entity, transform);
 
         assert_eq!(world.transform(entity), &transform);
@@ -205,11 +423,11 @@ mod tests {
     fn get_component_mut_allows_modification() {
         let mut world = World::new(800.0, 600.0);
         let entity = world.spawn();
-        world.set_velocity(entity, Velocity { x: 1.0, y: 2.0 });
+        world.set_velocity(entity, Velocity::new(1.0, 2.0));
 
-        world.velocity_mut(entity).x = 5.0;
+        world.velocity_mut(entity).set_x(5.0);
 
-        assert_eq!(world.velocity(entity), &Velocity { x: 5.0, y: 2.0 });
+        assert_eq!(world.velocity(entity), &Velocity::new(5.0, 2.0));
     }
 
     #[test]
@@ -217,8 +435,8 @@ mod tests {
     fn despawn_clears_component_slots() {
         let mut world = World::new(800.0, 600.0);
         let entity = world.spawn();
-        world.set_transform(entity, Transform { x: 1.0, y: 2.0 });
-        world.set_velocity(entity, Velocity { x: 3.0, y: 4.0 });
+        world.set_transform(entity, Transform::at(1.0, 2.0));
+        world.set_velocity(entity, Velocity::new(3.0, 4.0));
 
         world.despawn(entity);
 
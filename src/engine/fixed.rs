@@ -0,0 +1,492 @@
+//! Fixed-point numbers for the `fixed-point` feature. Integer arithmetic
+//! gives the same bits on every target, where float functions such as `exp`
+//! may differ between the wasm and native builds. With the feature on,
+//! `Transform::position` and `Velocity` are stored as `FixedVec2` and the
+//! core physics systems do their math in `Fixed`. Game systems get the same
+//! guarantee by calling `math::sin_cos` and `math::exp_neg` instead of the
+//! `f32` methods.
+
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::engine::math::Vec2;
+
+pub const FRACTION_BITS: u32 = 32;
+const ONE_RAW: i64 = 1 << FRACTION_BITS;
+
+/// Signed 32.32 fixed-point number.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(ONE_RAW);
+    pub const PI: Fixed = Fixed(13_493_037_705);
+    pub const FRAC_PI_2: Fixed = Fixed(6_746_518_852);
+    pub const TAU: Fixed = Fixed(26_986_075_409);
+
+    pub const fn from_raw(raw: i64) -> Self {
+        Fixed(raw)
+    }
+
+    pub const fn raw(self) -> i64 {
+        self.0
+    }
+
+    pub const fn from_int(value: i32) -> Self {
+        Fixed((value as i64) << FRACTION_BITS)
+    }
+
+    /// Nearest fixed value. Non-finite input saturates, NaN becomes zero.
+    pub fn from_f32(value: f32) -> Self {
+        Fixed((value as f64 * ONE_RAW as f64).round() as i64)
+    }
+
+    pub fn to_f32(self) -> f32 {
+        (self.0 as f64 / ONE_RAW as f64) as f32
+    }
+
+    pub fn abs(self) -> Fixed {
+        Fixed(self.0.abs())
+    }
+
+    /// Square root, rounded down; negative values give zero.
+    pub fn sqrt(self) -> Fixed {
+        if self.0 <= 0 {
+            return Fixed::ZERO;
+        }
+        Fixed(isqrt((self.0 as u128) << FRACTION_BITS) as i64)
+    }
+
+    /// `e^-x` for `x >= 0`, as used by drag. Halves `x` until it is small,
+    /// takes a short series there, then squares back up.
+    pub fn exp_neg(self) -> Fixed {
+        if self.0 <= 0 {
+            return Fixed::ONE;
+        }
+        let mut halvings = 0;
+        let mut x = self;
+        while x > Fixed::ONE / Fixed::from_int(8) {
+            x = Fixed(x.0 >> 1);
+            halvings += 1;
+            if halvings == 32 {
+                return Fixed::ZERO;
+            }
+        }
+        // Taylor series, summed from the highest term down.
+        let mut result = Fixed::ONE;
+        for n in (1..=6).rev() {
+            result = Fixed::ONE - x * result / Fixed::from_int(n);
+        }
+        for _ in 0..halvings {
+            result = result * result;
+        }
+        result
+    }
+
+    /// `(sin, cos)` of an angle in radians. Folds the angle into
+    /// `[-π/2, π/2]`, where a short series is accurate, then unfolds the cosine's sign.
+    pub fn sin_cos(self) -> (Fixed, Fixed) {
+        let mut x = Fixed(self.0.rem_euclid(Fixed::TAU.0));
+        if x > Fixed::PI {
+            x -= Fixed::TAU;
+        }
+        let folded = x.abs() > Fixed::FRAC_PI_2;
+        if x > Fixed::FRAC_PI_2 {
+            x = Fixed::PI - x;
+        } else if x < -Fixed::FRAC_PI_2 {
+            x = -Fixed::PI - x;
+        }
+        // Taylor series, summed from the highest term down.
+        let x2 = x * x;
+        let (mut sin, mut cos) = (Fixed::ONE, Fixed::ONE);
+        for n in (1..=8).rev() {
+            sin = Fixed::ONE - x2 * sin / Fixed::from_int(2 * n * (2 * n + 1));
+            cos = Fixed::ONE - x2 * cos / Fixed::from_int((2 * n - 1) * 2 * n);
+        }
+        let sin = x * sin;
+        if folded {
+            (sin, -cos)
+        } else {
+            (sin, cos)
+        }
+    }
+}
+
+/// Largest `r` with `r * r <= n`.
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// `value / 2^bits`, rounded to nearest.
+fn round_shift(value: i128, bits: u32) -> i128 {
+    (value + (1 << (bits - 1))) >> bits
+}
+
+/// Narrows a wide intermediate back to `i64`, saturating on overflow.
+fn saturate(value: i128) -> Fixed {
+    Fixed(value.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    fn mul(self, other: Fixed) -> Fixed {
+        saturate(round_shift(self.0 as i128 * other.0 as i128, FRACTION_BITS))
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+
+    /// Division by zero saturates toward the dividend's sign.
+    fn div(self, other: Fixed) -> Fixed {
+        if other.0 == 0 {
+            return saturate(self.0.signum() as i128 * i128::MAX);
+        }
+        saturate(((self.0 as i128) << FRACTION_BITS) / other.0 as i128)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, other: Fixed) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, other: Fixed) {
+        *self = *self - other;
+    }
+}
+
+/// `Vec2` counterpart in fixed point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FixedVec2 {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl FixedVec2 {
+    pub const ZERO: FixedVec2 = FixedVec2 {
+        x: Fixed::ZERO,
+        y: Fixed::ZERO,
+    };
+
+    /// Nearest fixed vector to `(x, y)`.
+    pub fn new(x: f32, y: f32) -> Self {
+        FixedVec2 {
+            x: Fixed::from_f32(x),
+            y: Fixed::from_f32(y),
+        }
+    }
+
+    // The `Vec2` accessors, so code handling a `SimVec2` builds the same
+    // with or without the feature.
+
+    pub fn x(self) -> f32 {
+        self.x.to_f32()
+    }
+
+    pub fn y(self) -> f32 {
+        self.y.to_f32()
+    }
+
+    pub fn set_x(&mut self, x: f32) {
+        self.x = Fixed::from_f32(x);
+    }
+
+    pub fn set_y(&mut self, y: f32) {
+        self.y = Fixed::from_f32(y);
+    }
+
+    pub fn to_vec2(self) -> Vec2 {
+        self.into()
+    }
+
+    pub fn from_vec2(v: Vec2) -> Self {
+        v.into()
+    }
+
+    pub fn length(self) -> Fixed {
+        let (x, y) = (self.x.raw() as i128, self.y.raw() as i128);
+        Fixed(isqrt((x * x + y * y) as u128) as i64)
+    }
+
+    /// Shortened to `max` if it is longer.
+    pub fn clamp_length(self, max: Fixed) -> FixedVec2 {
+        let length = self.length();
+        if length <= max {
+            return self;
+        }
+        // Scaling by `max / length` would round that ratio to 16 bits first.
+        let scale = |value: Fixed| saturate(value.0 as i128 * max.0 as i128 / length.0 as i128);
+        FixedVec2 {
+            x: scale(self.x),
+            y: scale(self.y),
+        }
+    }
+}
+
+impl From<Vec2> for FixedVec2 {
+    fn from(v: Vec2) -> Self {
+        FixedVec2 {
+            x: Fixed::from_f32(v.x),
+            y: Fixed::from_f32(v.y),
+        }
+    }
+}
+
+impl From<FixedVec2> for Vec2 {
+    fn from(v: FixedVec2) -> Self {
+        Vec2::new(v.x.to_f32(), v.y.to_f32())
+    }
+}
+
+impl Add for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn add(self, other: FixedVec2) -> FixedVec2 {
+        FixedVec2 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl Sub for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn sub(self, other: FixedVec2) -> FixedVec2 {
+        FixedVec2 {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl AddAssign for FixedVec2 {
+    fn add_assign(&mut self, other: FixedVec2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for FixedVec2 {
+    fn sub_assign(&mut self, other: FixedVec2) {
+        *self = *self - other;
+    }
+}
+
+/// Scales by the nearest fixed value to `factor`.
+impl Mul<f32> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn mul(self, factor: f32) -> FixedVec2 {
+        self * Fixed::from_f32(factor)
+    }
+}
+
+impl Mul<Fixed> for FixedVec2 {
+    type Output = FixedVec2;
+
+    fn mul(self, factor: Fixed) -> FixedVec2 {
+        FixedVec2 {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn f(value: f32) -> Fixed {
+        Fixed::from_f32(value)
+    }
+
+    #[test]
+    fn converts_to_and_from_f32_on_the_grid() {
+        assert_eq!(f(1.5).raw(), 3 << 31);
+        assert_eq!(f(-2.25).to_f32(), -2.25);
+        assert_eq!(Fixed::from_int(800).to_f32(), 800.0);
+        assert_eq!(f(f32::NAN), Fixed::ZERO);
+    }
+
+    #[test]
+    fn arithmetic_matches_the_real_numbers() {
+        assert_eq!(f(1.5) + f(2.25), f(3.75));
+        assert_eq!(f(1.5) - f(2.25), f(-0.75));
+        assert_eq!(f(1.5) * f(-2.0), f(-3.0));
+        assert_eq!(f(3.0) / f(4.0), f(0.75));
+        assert_eq!(-f(0.5), f(-0.5));
+        assert_eq!(f(6.25).sqrt(), f(2.5));
+    }
+
+    #[test]
+    fn overflow_saturates_instead_of_wrapping() {
+        let huge = Fixed::from_raw(i64::MAX / 2 + 1);
+
+        assert_eq!((huge + huge).raw(), i64::MAX);
+        assert_eq!((huge * f(4.0)).raw(), i64::MAX);
+        assert_eq!((-huge / Fixed::ZERO).raw(), i64::MIN);
+    }
+
+    #[test]
+    fn exp_neg_tracks_the_float_function() {
+        for x in [0.0_f32, 0.01, 0.1, 0.5, 1.0, 2.0, 5.0] {
+            let fixed = f(x).exp_neg().to_f32();
+            assert!((fixed - (-x).exp()).abs() < 1e-6, "e^-{} gave {}", x, fixed);
+        }
+    }
+
+    #[test]
+    fn sin_cos_tracks_the_float_functions() {
+        for x in [
+            -10.0_f64, -3.0, -1.6, -0.5, 0.0, 0.3, 1.0, 1.5, 2.0, 3.1, 4.5, 6.2, 100.0,
+        ] {
+            let (sin, cos) = Fixed::from_raw((x * 4_294_967_296.0) as i64).sin_cos();
+            assert!(
+                (sin.to_f32() as f64 - x.sin()).abs() < 1e-7,
+                "sin {} gave {:?}",
+                x,
+                sin
+            );
+            assert!(
+                (cos.to_f32() as f64 - x.cos()).abs() < 1e-7,
+                "cos {} gave {:?}",
+                x,
+                cos
+            );
+        }
+    }
+
+    #[test]
+    fn vector_length_handles_large_speeds() {
+        let v = FixedVec2::from(Vec2::new(3000.0, 4000.0));
+
+        assert_eq!(v.length(), f(5000.0));
+        assert_eq!(Vec2::from(v.clamp_length(f(50.0))), Vec2::new(30.0, 40.0));
+        assert_eq!(
+            Vec2::from(FixedVec2::from(Vec2::new(1.0e-12, -3.0e-10))),
+            Vec2::new(0.0, -1.0 / 4_294_967_296.0)
+        );
+    }
+}
+
+/// Steps a scripted world through every engine system and compares its
+/// checksum with a constant. Run on native and on wasm, a match on both
+/// proves the two builds step to the same bits. The checksum covers the
+/// save layout, so the constant moves whenever `SAVE_FORMAT_VERSION` does.
+#[cfg(all(test, feature = "fixed-point"))]
+mod determinism {
+    use super::*;
+    use crate::engine::checksum::world_checksum;
+    use crate::engine::ecs::components::{
+        Acceleration, BounceCollider, Collider, Drag, Material, MaxSpeed, Transform, Velocity,
+    };
+    use crate::engine::ecs::resources::{EdgeBehavior, FieldEdge, SimRng};
+    use crate::engine::ecs::schedule::Schedule;
+    use crate::engine::ecs::world::World;
+    use crate::engine::save::SaveApi;
+    use crate::engine::{with_engine_systems, GameId};
+    use rand::Rng;
+
+    const GOLDEN_CHECKSUM: u64 = 0x5689_b58c_dae0_c35b;
+    const STEPS: u32 = 600;
+    const DT: f32 = 1.0 / 60.0;
+
+    fn scripted_world(seed: u64) -> World {
+        let mut world = World::new(320.0, 240.0);
+        world.rng = SimRng::new(seed);
+        world.field.edges = world.field.edges.with(FieldEdge::Left, EdgeBehavior::Wrap);
+        world.field.wall_material = Material::ELASTIC.with_restitution(0.9).with_friction(0.1);
+
+        for _ in 0..8 {
+            let ball = world.spawn();
+            let position = Vec2::new(
+                world.rng.gen_range(20.0..300.0),
+                world.rng.gen_range(20.0..220.0),
+            );
+            let velocity = Velocity::new(
+                world.rng.gen_range(-300.0..300.0),
+                world.rng.gen_range(-300.0..300.0),
+            );
+            world.set_transform(ball, Transform::at(position.x, position.y));
+            world.set_velocity(ball, velocity);
+            world.set_acceleration(ball, Acceleration { x: 0.0, y: 180.0 });
+            world.set_drag(ball, Drag { rate: 0.3 });
+            world.set_max_speed(ball, MaxSpeed { value: 420.0 });
+            world.set_wall_bounce_collider(ball, BounceCollider { radius: 5.0 });
+            world.set_collider(
+                ball,
+                Collider::circle(5.0).with_material(Material::ELASTIC.with_restitution(0.95)),
+            );
+        }
+
+        let bumper = world.spawn();
+        world.set_transform(bumper, Transform::at(160.0, 120.0));
+        world.set_collider(
+            bumper,
+            Collider::aabb(30.0, 8.0).with_material(Material::ELASTIC.with_speed_multiplier(1.05)),
+        );
+        world
+    }
+
+    fn run(seed: u64) -> World {
+        let mut world = scripted_world(seed);
+        let mut schedule = with_engine_systems(Schedule::new());
+        schedule.run_startup(&mut world);
+        for _ in 0..STEPS {
+            schedule.run(&mut world, DT);
+        }
+        world
+    }
+
+    fn checksum(world: &World) -> u64 {
+        world_checksum(GameId(0), world, &SaveApi::unsupported())
+    }
+
+    #[test]
+    fn scripted_run_matches_the_golden_checksum() {
+        assert_eq!(checksum(&run(7)), GOLDEN_CHECKSUM);
+    }
+
+    #[test]
+    fn repeated_runs_are_bit_identical() {
+        assert_eq!(checksum(&run(7)), checksum(&run(7)));
+        assert_ne!(checksum(&run(7)), checksum(&run(8)));
+    }
+}
@@ -0,0 +1,300 @@
+//! Small 2D vector type shared by components and systems.
+
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+#[cfg(feature = "fixed-point")]
+use crate::engine::fixed::Fixed;
+
+/// `(sin, cos)` of `angle` radians. With the `fixed-point` feature this is
+/// worked out in `Fixed`, so wasm and native agree on every bit.
+#[cfg(not(feature = "fixed-point"))]
+pub fn sin_cos(angle: f32) -> (f32, f32) {
+    angle.sin_cos()
+}
+
+#[cfg(feature = "fixed-point")]
+pub fn sin_cos(angle: f32) -> (f32, f32) {
+    let (sin, cos) = Fixed::from_f32(angle).sin_cos();
+    (sin.to_f32(), cos.to_f32())
+}
+
+/// `e^-x` for `x >= 0`, the share left after decaying at rate `x` for one
+/// second. Fixed point under the `fixed-point` feature, like `sin_cos`.
+#[cfg(not(feature = "fixed-point"))]
+pub fn exp_neg(x: f32) -> f32 {
+    (-x).exp()
+}
+
+#[cfg(feature = "fixed-point")]
+pub fn exp_neg(x: f32) -> f32 {
+    Fixed::from_f32(x).exp_neg().to_f32()
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0.0, y: 0.0 };
+    pub const ONE: Vec2 = Vec2 { x: 1.0, y: 1.0 };
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /// Unit vector at `angle` radians, measured from +x toward +y.
+    pub fn from_angle(angle: f32) -> Self {
+        let (sin, cos) = sin_cos(angle);
+        Self::new(cos, sin)
+    }
+
+    pub fn dot(self, other: Vec2) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Z component of the 3D cross product; positive when `other` is
+    /// clockwise from `self` on screen, where y points down.
+    pub fn cross(self, other: Vec2) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// `hypot` comes from the platform's libm, so the `fixed-point` build
+    /// uses `sqrt`, which IEEE 754 requires to be correctly rounded.
+    pub fn length(self) -> f32 {
+        #[cfg(feature = "fixed-point")]
+        return self.length_squared().sqrt();
+        #[cfg(not(feature = "fixed-point"))]
+        self.x.hypot(self.y)
+    }
+
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    pub fn distance(self, other: Vec2) -> f32 {
+        (other - self).length()
+    }
+
+    /// Angle from +x in radians, in `-PI..=PI`.
+    pub fn angle(self) -> f32 {
+        self.y.atan2(self.x)
+    }
+
+    /// Same direction at unit length, or zero for the zero vector.
+    pub fn normalize_or_zero(self) -> Vec2 {
+        let length = self.length();
+        if length > 0.0 {
+            self / length
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    /// Same direction at `length`; the zero vector stays zero.
+    pub fn with_length(self, length: f32) -> Vec2 {
+        self.normalize_or_zero() * length
+    }
+
+    /// Shortened to `max` if it is longer.
+    pub fn clamp_length(self, max: f32) -> Vec2 {
+        let length = self.length();
+        if length > max {
+            self * (max / length)
+        } else {
+            self
+        }
+    }
+
+    /// Mirror image across a surface with unit `normal`.
+    pub fn reflect(self, normal: Vec2) -> Vec2 {
+        self - normal * (2.0 * self.dot(normal))
+    }
+
+    /// Turned by `angle` radians, from +x toward +y.
+    pub fn rotate(self, angle: f32) -> Vec2 {
+        let (sin, cos) = sin_cos(angle);
+        Vec2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// Turned a quarter, from +x toward +y.
+    pub fn perp(self) -> Vec2 {
+        Vec2::new(-self.y, self.x)
+    }
+
+    /// Component-wise product.
+    pub fn scale(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x * other.x, self.y * other.y)
+    }
+
+    // Accessors shared with `FixedVec2`, so code handling a `SimVec2` builds
+    // the same with or without the `fixed-point` feature.
+
+    pub fn x(self) -> f32 {
+        self.x
+    }
+
+    pub fn y(self) -> f32 {
+        self.y
+    }
+
+    pub fn set_x(&mut self, x: f32) {
+        self.x = x;
+    }
+
+    pub fn set_y(&mut self, y: f32) {
+        self.y = y;
+    }
+
+    pub fn to_vec2(self) -> Vec2 {
+        self
+    }
+
+    pub fn from_vec2(v: Vec2) -> Vec2 {
+        v
+    }
+}
+
+impl From<(f32, f32)> for Vec2 {
+    fn from((x, y): (f32, f32)) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl From<Vec2> for (f32, f32) {
+    fn from(v: Vec2) -> Self {
+        (v.x, v.y)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f32> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, factor: f32) -> Vec2 {
+        Vec2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Mul<Vec2> for f32 {
+    type Output = Vec2;
+
+    fn mul(self, v: Vec2) -> Vec2 {
+        v * self
+    }
+}
+
+impl Div<f32> for Vec2 {
+    type Output = Vec2;
+
+    fn div(self, divisor: f32) -> Vec2 {
+        Vec2::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<f32> for Vec2 {
+    fn mul_assign(&mut self, factor: f32) {
+        *self = *self * factor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    const EPS: f32 = 1e-5;
+
+    fn approx(actual: Vec2, expected: Vec2) {
+        assert!(
+            actual.distance(expected) <= EPS,
+            "expected {:?} but got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn operators_work_component_wise() {
+        let a = Vec2::new(1.0, 2.0);
+        let b = Vec2::new(3.0, -4.0);
+
+        assert_eq!(a + b, Vec2::new(4.0, -2.0));
+        assert_eq!(a - b, Vec2::new(-2.0, 6.0));
+        assert_eq!(a * 2.0, Vec2::new(2.0, 4.0));
+        assert_eq!(2.0 * a, a * 2.0);
+        assert_eq!(b / 2.0, Vec2::new(1.5, -2.0));
+        assert_eq!(-a, Vec2::new(-1.0, -2.0));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= 0.5;
+        assert_eq!(c, Vec2::new(1.5, -2.0));
+    }
+
+    #[test]
+    fn length_helpers() {
+        let v = Vec2::new(3.0, 4.0);
+
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.length_squared(), 25.0);
+        approx(v.normalize_or_zero(), Vec2::new(0.6, 0.8));
+        approx(v.with_length(10.0), Vec2::new(6.0, 8.0));
+        approx(v.clamp_length(2.5), Vec2::new(1.5, 2.0));
+        assert_eq!(v.clamp_length(10.0), v);
+        assert_eq!(Vec2::ZERO.normalize_or_zero(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn reflect_mirrors_across_the_normal() {
+        let v = Vec2::new(3.0, -2.0);
+
+        assert_eq!(v.reflect(Vec2::new(0.0, 1.0)), Vec2::new(3.0, 2.0));
+        assert_eq!(v.reflect(Vec2::new(-1.0, 0.0)), Vec2::new(-3.0, -2.0));
+    }
+
+    #[test]
+    fn rotate_turns_toward_positive_y() {
+        approx(Vec2::new(1.0, 0.0).rotate(FRAC_PI_2), Vec2::new(0.0, 1.0));
+        approx(Vec2::from_angle(FRAC_PI_2), Vec2::new(0.0, 1.0));
+        assert_eq!(Vec2::new(1.0, 0.0).perp(), Vec2::new(0.0, 1.0));
+        assert!((Vec2::new(0.0, 2.0).angle() - FRAC_PI_2).abs() <= EPS);
+    }
+}
@@ -1,16 +1,34 @@
 #![allow(dead_code)]
 
+pub mod checksum;
+mod clock;
+pub mod collision;
 pub mod ecs;
+pub mod fixed;
+pub mod math;
+pub mod save;
 
+use crate::engine::ecs::contacts::{detect_contacts, resolve_contacts};
+use crate::engine::ecs::profiler::Profiler;
+use crate::engine::ecs::resources::MAX_SUBSTEPS_LIMIT;
 use crate::engine::ecs::schedule::{Schedule, SystemPhase};
-use crate::engine::ecs::systems::{bounce_in_field, integrate_velocity};
+use crate::engine::ecs::spatial_hash::update_spatial_hash;
+use crate::engine::ecs::systems::{
+    apply_acceleration, bounce_in_field, integrate_velocity, physics_substeps,
+    track_kinematic_bodies, track_kinematic_input, KINEMATIC_PHASE,
+};
+use crate::engine::ecs::time::Time;
+use crate::engine::ecs::timer::{tick_timers, TIMER_PHASE};
 use crate::engine::ecs::world::World;
+use crate::engine::save::{SaveApi, SaveError};
 
 pub const INPUT_UP: u32 = 0b0000_0001;
 pub const INPUT_DOWN: u32 = 0b0000_0010;
 pub const INPUT_LEFT: u32 = 0b0000_0100;
 pub const INPUT_RIGHT: u32 = 0b0000_1000;
 pub const INPUT_ACTION: u32 = 0b0001_0000;
+/// Held for fine control, e.g. Shift to slow a paddle down.
+pub const INPUT_PRECISION: u32 = 0b0010_0000;
 
 pub const MAX_DT: f32 = 0.05;
 
@@ -89,37 +107,155 @@ impl Snapshot {
     }
 }
 
+/// Adds the systems every game runs on: acceleration and integration, the
+/// broadphase, contact detection and material bounces and wall bounces, with
+/// physics and resolution sub-stepped together. Kinematic velocities are
+/// measured every sub-step, with moves made in Control measured once per
+/// frame in [`KINEMATIC_PHASE`], right before Physics. Timers tick once per frame in [`TIMER_PHASE`], right after Resolve, so
+/// `just_finished` survives to every later phase. Game systems can order
+/// themselves against these by name.
+pub fn with_engine_systems(schedule: Schedule) -> Schedule {
+    schedule
+        .with_system_in_phase(SystemPhase::Physics, apply_acceleration)
+        .with_system_in_phase(SystemPhase::Physics, integrate_velocity)
+        .with_system_in_phase(SystemPhase::Physics, update_spatial_hash)
+        .with_system_in_phase(SystemPhase::Physics, track_kinematic_bodies)
+        .with_system_in_phase(SystemPhase::Physics, detect_contacts)
+        .with_system_in_phase(SystemPhase::Physics, resolve_contacts)
+        .with_system_in_phase(SystemPhase::Physics, bounce_in_field)
+        .with_phase_after(SystemPhase::Control, KINEMATIC_PHASE)
+        .with_system_in_phase(KINEMATIC_PHASE, track_kinematic_input)
+        .with_phase_after(SystemPhase::Resolve, TIMER_PHASE)
+        .with_system_in_phase(TIMER_PHASE, tick_timers)
+        .with_substeps(SystemPhase::Physics, SystemPhase::Resolve, physics_substeps)
+}
+
 pub struct Engine {
+    game_id: GameId,
     world: World,
     schedule: Schedule,
     snapshot: Snapshot,
     tuning_api: TuningApi,
+    save_api: SaveApi,
+    profiler: Option<Profiler>,
 }
 
 impl Engine {
     pub fn new(
+        game_id: GameId,
         world: World,
         schedule: Schedule,
         snapshot: Snapshot,
         tuning_api: TuningApi,
+        save_api: SaveApi,
     ) -> Self {
-        let schedule = schedule
-            .with_system_in_phase(SystemPhase::Physics, integrate_velocity)
-            .with_system_in_phase(SystemPhase::Physics, bounce_in_field);
+        let mut schedule = with_engine_systems(schedule)
+            .build()
+            .unwrap_or_else(|err| panic!("invalid schedule: {}", err));
+        let mut world = world;
+        schedule.run_startup(&mut world);
         let mut engine = Self {
+            game_id,
             world,
             schedule,
             snapshot,
             tuning_api,
+            save_api,
+            profiler: None,
         };
         engine.snapshot.update(&engine.world);
         engine
     }
 
+    /// Advances one frame. While paused this does nothing unless a single
+    /// frame was requested with [`Engine::step_frame`].
     pub fn step(&mut self, dt_seconds: f32, input_bits: u32) {
-        let dt = clamp_dt(dt_seconds);
+        let Some(dt) = self.world.time.begin_frame(clamp_dt(dt_seconds)) else {
+            return;
+        };
         self.world.input.bits = input_bits;
-        self.schedule.run(&mut self.world, dt);
+        match self.profiler.as_mut() {
+            Some(profiler) => self.schedule.run_profiled(&mut self.world, dt, profiler),
+            None => self.schedule.run(&mut self.world, dt),
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.world.time.pause();
+    }
+
+    pub fn resume(&mut self) {
+        self.world.time.resume();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.world.time.is_paused()
+    }
+
+    /// While paused, lets the next [`Engine::step`] run one frame.
+    pub fn step_frame(&mut self) {
+        self.world.time.step_frame();
+    }
+
+    /// Sets the time scale, clamped to 0.1×–4×. Returns the applied scale.
+    pub fn set_time_scale(&mut self, scale: f32) -> f32 {
+        self.world.time.set_scale(scale)
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.world.time.scale()
+    }
+
+    /// Caps adaptive physics sub-steps per frame, clamped to
+    /// `1..=MAX_SUBSTEPS_LIMIT`. Returns the cap actually applied.
+    pub fn set_max_substeps(&mut self, max_substeps: u32) -> u32 {
+        let max_substeps = max_substeps.clamp(1, MAX_SUBSTEPS_LIMIT);
+        self.world.substeps.max_substeps = max_substeps;
+        max_substeps
+    }
+
+    pub fn max_substeps(&self) -> u32 {
+        self.world.substeps.max_substeps
+    }
+
+    pub fn time(&self) -> &Time {
+        &self.world.time
+    }
+
+    /// Runs a one-shot system registered by the game. Returns false if unknown.
+    pub fn run_one_shot(&mut self, name: &str) -> bool {
+        self.schedule.run_one_shot(name, &mut self.world)
+    }
+
+    /// Starts or stops recording per-system timings. Stopping discards them.
+    pub fn set_profiling(&mut self, enabled: bool) {
+        self.profiler = enabled.then(Profiler::default);
+    }
+
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_ref()
+    }
+
+    /// Execution order of every scheduled system, for debugging.
+    pub fn describe_schedule(&self) -> String {
+        self.schedule.describe()
+    }
+
+    /// Turns a scheduled system on or off by name. Returns false if no
+    /// system has that name.
+    pub fn set_system_enabled(&mut self, name: &str, enabled: bool) -> bool {
+        self.schedule.set_system_enabled(name, enabled)
+    }
+
+    /// JSON array of `{phase, name, enabled}` rows, one per scheduled system.
+    pub fn systems_json(&self) -> String {
+        self.schedule.systems_json()
+    }
+
+    /// Checksum of the current simulation state. Hashed on request rather
+    /// than every step, since it covers everything a save captures.
+    pub fn checksum(&self) -> u64 {
+        checksum::world_checksum(self.game_id, &self.world, &self.save_api)
     }
 
     pub fn snapshot_ptr(&mut self) -> *const f32 {
@@ -142,4 +278,14 @@ impl Engine {
     pub fn reset_tuning_defaults(&mut self) {
         (self.tuning_api.reset)(&mut self.world);
     }
+
+    pub fn save_state(&self) -> Vec<u8> {
+        save::encode(self.game_id, &self.world, &self.save_api)
+    }
+
+    pub fn load_state(&mut self, bytes: &[u8]) -> Result<(), SaveError> {
+        save::decode(self.game_id, &mut self.world, &self.save_api, bytes)?;
+        self.snapshot.update(&self.world);
+        Ok(())
+    }
 }
@@ -0,0 +1,871 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::engine::ecs::components::{
+    Acceleration, BounceCollider, Collider, ColliderShape, Drag, KinematicBody, Material, MaxSpeed,
+    SimVec2, Spin, Transform,
+};
+use crate::engine::ecs::entity::{EntityAllocator, EntityId};
+use crate::engine::ecs::resources::{
+    EdgeBehavior, FieldBounds, FieldEdges, InputBits, SimRng, SubstepConfig, MAX_SUBSTEPS_LIMIT,
+};
+use crate::engine::ecs::timer::{Timer, TimerMode};
+use crate::engine::ecs::world::World;
+use crate::engine::math::Vec2;
+use crate::engine::GameId;
+
+pub const SAVE_MAGIC: [u8; 4] = *b"GDSV";
+/// Bump whenever the byte layout written by `encode` (or any game section) changes.
+pub const SAVE_FORMAT_VERSION: u16 = 19;
+/// How positions and velocities are stored: `f32` pairs, or raw 32.32 fixed
+/// point under the `fixed-point` feature. The two builds can't read each other's saves.
+#[cfg(not(feature = "fixed-point"))]
+pub const SIM_NUMBER_FORMAT: u8 = 0;
+#[cfg(feature = "fixed-point")]
+pub const SIM_NUMBER_FORMAT: u8 = 1;
+
+pub const SAVE_STATUS_LOADED: u32 = 0;
+pub const SAVE_STATUS_MALFORMED: u32 = 1;
+pub const SAVE_STATUS_VERSION_MISMATCH: u32 = 2;
+pub const SAVE_STATUS_GAME_MISMATCH: u32 = 3;
+/// Returned by the wasm bindings when no game is running to load into.
+pub const SAVE_STATUS_NO_ENGINE: u32 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaveError {
+    BadMagic,
+    UnsupportedVersion(u16),
+    GameMismatch { expected: GameId, found: GameId },
+    Truncated,
+    TrailingBytes,
+    Invalid(&'static str),
+}
+
+impl SaveError {
+    pub fn status(self) -> u32 {
+        match self {
+            SaveError::UnsupportedVersion(_) => SAVE_STATUS_VERSION_MISMATCH,
+            SaveError::GameMismatch { .. } => SAVE_STATUS_GAME_MISMATCH,
+            SaveError::BadMagic
+            | SaveError::Truncated
+            | SaveError::TrailingBytes
+            | SaveError::Invalid(_) => SAVE_STATUS_MALFORMED,
+        }
+    }
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::BadMagic => write!(f, "not a save state"),
+            SaveError::UnsupportedVersion(version) => write!(
+                f,
+                "save layout version {} is not supported (expected {})",
+                version, SAVE_FORMAT_VERSION
+            ),
+            SaveError::GameMismatch { expected, found } => write!(
+                f,
+                "save belongs to game {} but game {} is running",
+                found.0, expected.0
+            ),
+            SaveError::Truncated => write!(f, "save state ended unexpectedly"),
+            SaveError::TrailingBytes => write!(f, "save state has trailing bytes"),
+            SaveError::Invalid(reason) => write!(f, "invalid save state: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Little-endian byte sink used by the engine and by game save hooks.
+#[derive(Default)]
+pub struct SaveWriter {
+    bytes: Vec<u8>,
+}
+
+impl SaveWriter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_f32(&mut self, value: f32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_f64(&mut self, value: f64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    #[cfg(not(feature = "fixed-point"))]
+    pub fn write_sim_vec(&mut self, value: SimVec2) {
+        self.write_f32(value.x);
+        self.write_f32(value.y);
+    }
+
+    #[cfg(feature = "fixed-point")]
+    pub fn write_sim_vec(&mut self, value: SimVec2) {
+        self.write_u64(value.x.raw() as u64);
+        self.write_u64(value.y.raw() as u64);
+    }
+
+    pub fn write_entity(&mut self, entity: EntityId) {
+        self.write_u32(entity.0);
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Cursor over a save buffer. Entity ids are checked against the number of
+/// component slots in the save so game sections cannot reference missing entities.
+pub struct SaveReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    entity_limit: u32,
+}
+
+impl<'a> SaveReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            entity_limit: u32::MAX,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SaveError> {
+        let end = self.pos.checked_add(len).ok_or(SaveError::Truncated)?;
+        let slice = self.bytes.get(self.pos..end).ok_or(SaveError::Truncated)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], SaveError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, SaveError> {
+        Ok(self.take_array::<1>()?[0])
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, SaveError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SaveError::Invalid("bool out of range")),
+        }
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, SaveError> {
+        Ok(u16::from_le_bytes(self.take_array()?))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, SaveError> {
+        Ok(u32::from_le_bytes(self.take_array()?))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, SaveError> {
+        Ok(u64::from_le_bytes(self.take_array()?))
+    }
+
+    pub fn read_f32(&mut self) -> Result<f32, SaveError> {
+        Ok(f32::from_le_bytes(self.take_array()?))
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, SaveError> {
+        Ok(f64::from_le_bytes(self.take_array()?))
+    }
+
+    #[cfg(not(feature = "fixed-point"))]
+    pub fn read_sim_vec(&mut self) -> Result<SimVec2, SaveError> {
+        Ok(Vec2::new(self.read_f32()?, self.read_f32()?))
+    }
+
+    #[cfg(feature = "fixed-point")]
+    pub fn read_sim_vec(&mut self) -> Result<SimVec2, SaveError> {
+        use crate::engine::fixed::{Fixed, FixedVec2};
+        let x = Fixed::from_raw(self.read_u64()? as i64);
+        let y = Fixed::from_raw(self.read_u64()? as i64);
+        Ok(FixedVec2 { x, y })
+    }
+
+    pub fn read_entity(&mut self) -> Result<EntityId, SaveError> {
+        let id = self.read_u32()?;
+        if id >= self.entity_limit {
+            return Err(SaveError::Invalid("entity id out of range"));
+        }
+        Ok(EntityId(id))
+    }
+
+    /// Fails unless every byte has been consumed. Game hooks call this before
+    /// touching the world so a bad save leaves it untouched.
+    pub fn finish(&self) -> Result<(), SaveError> {
+        if self.remaining() == 0 {
+            Ok(())
+        } else {
+            Err(SaveError::TrailingBytes)
+        }
+    }
+}
+
+type SaveWriteFn = fn(&World, &mut SaveWriter);
+type SaveReadFn = fn(&mut World, &mut SaveReader) -> Result<(), SaveError>;
+
+/// Game hooks for the resources the engine cannot see into.
+///
+/// `read` must decode and validate its whole section before mutating the world.
+pub struct SaveApi {
+    write: SaveWriteFn,
+    read: SaveReadFn,
+}
+
+impl SaveApi {
+    pub fn unsupported() -> Self {
+        fn write_nothing(_world: &World, _writer: &mut SaveWriter) {}
+
+        fn read_nothing(_world: &mut World, reader: &mut SaveReader) -> Result<(), SaveError> {
+            reader.finish()
+        }
+
+        Self {
+            write: write_nothing,
+            read: read_nothing,
+        }
+    }
+
+    pub fn new(write: SaveWriteFn, read: SaveReadFn) -> Self {
+        Self { write, read }
+    }
+}
+
+fn write_slots<T>(
+    writer: &mut SaveWriter,
+    slots: &[Option<T>],
+    write: impl Fn(&mut SaveWriter, &T),
+) {
+    for slot in slots {
+        match slot {
+            Some(value) => {
+                writer.write_bool(true);
+                write(writer, value);
+            }
+            None => writer.write_bool(false),
+        }
+    }
+}
+
+fn read_slots<T>(
+    reader: &mut SaveReader,
+    count: usize,
+    read: impl Fn(&mut SaveReader) -> Result<T, SaveError>,
+) -> Result<Vec<Option<T>>, SaveError> {
+    let mut slots = Vec::with_capacity(count);
+    for _ in 0..count {
+        let slot = if reader.read_bool()? {
+            Some(read(reader)?)
+        } else {
+            None
+        };
+        slots.push(slot);
+    }
+    Ok(slots)
+}
+
+fn write_edge_behavior(writer: &mut SaveWriter, behavior: EdgeBehavior) {
+    writer.write_u8(match behavior {
+        EdgeBehavior::Reflect => 0,
+        EdgeBehavior::PassThrough => 1,
+        EdgeBehavior::Wrap => 2,
+        EdgeBehavior::Exit => 3,
+    });
+}
+
+fn read_edge_behavior(reader: &mut SaveReader) -> Result<EdgeBehavior, SaveError> {
+    match reader.read_u8()? {
+        0 => Ok(EdgeBehavior::Reflect),
+        1 => Ok(EdgeBehavior::PassThrough),
+        2 => Ok(EdgeBehavior::Wrap),
+        3 => Ok(EdgeBehavior::Exit),
+        _ => Err(SaveError::Invalid("unknown edge behavior")),
+    }
+}
+
+fn write_material(writer: &mut SaveWriter, material: &Material) {
+    writer.write_f32(material.restitution);
+    writer.write_f32(material.friction);
+    writer.write_f32(material.speed_multiplier);
+}
+
+fn read_material(reader: &mut SaveReader) -> Result<Material, SaveError> {
+    Ok(Material {
+        restitution: reader.read_f32()?,
+        friction: reader.read_f32()?,
+        speed_multiplier: reader.read_f32()?,
+    })
+}
+
+pub fn encode(game_id: GameId, world: &World, api: &SaveApi) -> Vec<u8> {
+    let mut writer = SaveWriter::new();
+    writer.write_bytes(&SAVE_MAGIC);
+    writer.write_u16(SAVE_FORMAT_VERSION);
+    writer.write_u32(game_id.0);
+    writer.write_u8(SIM_NUMBER_FORMAT);
+
+    writer.write_f32(world.field.width);
+    writer.write_f32(world.field.height);
+    let edges = world.field.edges;
+    for behavior in [edges.left, edges.right, edges.top, edges.bottom] {
+        write_edge_behavior(&mut writer, behavior);
+    }
+    write_material(&mut writer, &world.field.wall_material);
+    writer.write_u32(world.input.bits);
+    writer.write_u64(world.rng.state());
+    writer.write_f64(world.time.elapsed());
+    writer.write_u32(world.substeps.max_substeps);
+    writer.write_f32(world.substeps.min_collider_size);
+    writer.write_u32(world.fixed_rate_accumulators.len() as u32);
+    for &accumulator in &world.fixed_rate_accumulators {
+        writer.write_f32(accumulator);
+    }
+
+    let allocator = world.allocator();
+    writer.write_u32(allocator.next_id());
+    writer.write_u32(allocator.free_ids().len() as u32);
+    for &id in allocator.free_ids() {
+        writer.write_u32(id);
+    }
+
+    writer.write_u32(world.transforms.len() as u32);
+    write_slots(&mut writer, &world.transforms, |w, t| {
+        w.write_sim_vec(t.position);
+        w.write_f32(t.rotation);
+        w.write_f32(t.scale.x);
+        w.write_f32(t.scale.y);
+    });
+    write_slots(&mut writer, &world.velocities, |w, v| {
+        w.write_sim_vec(*v);
+    });
+    write_slots(&mut writer, &world.wall_bounce_colliders, |w, c| {
+        w.write_f32(c.radius);
+    });
+    write_slots(&mut writer, &world.spins, |w, s| {
+        w.write_f32(s.value);
+    });
+    write_slots(&mut writer, &world.timers, |w, t| {
+        w.write_f32(t.duration());
+        w.write_f32(t.elapsed());
+        w.write_u8(match t.mode() {
+            TimerMode::Once => 0,
+            TimerMode::Repeating => 1,
+        });
+        w.write_bool(t.is_paused());
+        w.write_u32(t.times_finished());
+    });
+    write_slots(&mut writer, &world.colliders, |w, c| {
+        match c.shape {
+            ColliderShape::Circle { radius } => {
+                w.write_u8(0);
+                w.write_f32(radius);
+            }
+            ColliderShape::Aabb {
+                half_width,
+                half_height,
+            } => {
+                w.write_u8(1);
+                w.write_f32(half_width);
+                w.write_f32(half_height);
+            }
+        }
+        w.write_f32(c.offset_x);
+        w.write_f32(c.offset_y);
+        w.write_u32(c.layer);
+        w.write_u32(c.mask);
+        w.write_bool(c.material.is_some());
+        if let Some(material) = &c.material {
+            write_material(w, material);
+        }
+    });
+    write_slots(&mut writer, &world.accelerations, |w, a| {
+        w.write_f32(a.x);
+        w.write_f32(a.y);
+    });
+    write_slots(&mut writer, &world.drags, |w, d| w.write_f32(d.rate));
+    write_slots(&mut writer, &world.max_speeds, |w, m| w.write_f32(m.value));
+    write_slots(&mut writer, &world.kinematic_bodies, |w, k| {
+        w.write_f32(k.velocity.x);
+        w.write_f32(k.velocity.y);
+        w.write_f32(k.input_velocity.x);
+        w.write_f32(k.input_velocity.y);
+        w.write_bool(k.last_position.is_some());
+        if let Some(last) = k.last_position {
+            w.write_f32(last.x);
+            w.write_f32(last.y);
+        }
+    });
+
+    let mut game = SaveWriter::new();
+    (api.write)(world, &mut game);
+    let game = game.into_bytes();
+    writer.write_u32(game.len() as u32);
+    writer.write_bytes(&game);
+
+    writer.into_bytes()
+}
+
+/// Restores `world` from `bytes`. On error the world is left unchanged.
+pub fn decode(
+    game_id: GameId,
+    world: &mut World,
+    api: &SaveApi,
+    bytes: &[u8],
+) -> Result<(), SaveError> {
+    let mut reader = SaveReader::new(bytes);
+    if reader.take_array::<4>().map_err(|_| SaveError::BadMagic)? != SAVE_MAGIC {
+        return Err(SaveError::BadMagic);
+    }
+    let version = reader.read_u16()?;
+    if version != SAVE_FORMAT_VERSION {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+    let found = GameId(reader.read_u32()?);
+    if found != game_id {
+        return Err(SaveError::GameMismatch {
+            expected: game_id,
+            found,
+        });
+    }
+    if reader.read_u8()? != SIM_NUMBER_FORMAT {
+        return Err(SaveError::Invalid(
+            "save was written by a build with another number format",
+        ));
+    }
+
+    let width = reader.read_f32()?;
+    let height = reader.read_f32()?;
+    let field = FieldBounds {
+        width,
+        height,
+        edges: FieldEdges {
+            left: read_edge_behavior(&mut reader)?,
+            right: read_edge_behavior(&mut reader)?,
+            top: read_edge_behavior(&mut reader)?,
+            bottom: read_edge_behavior(&mut reader)?,
+        },
+        wall_material: read_material(&mut reader)?,
+    };
+    let input = InputBits {
+        bits: reader.read_u32()?,
+    };
+    let rng = SimRng::new(reader.read_u64()?);
+    let elapsed = reader.read_f64()?;
+    let substeps = SubstepConfig {
+        max_substeps: reader.read_u32()?,
+        min_collider_size: reader.read_f32()?,
+    };
+    if !(1..=MAX_SUBSTEPS_LIMIT).contains(&substeps.max_substeps) {
+        return Err(SaveError::Invalid("sub-step limit out of range"));
+    }
+    let accumulator_count = reader.read_u32()? as usize;
+    if accumulator_count > reader.remaining() / 4 {
+        return Err(SaveError::Truncated);
+    }
+    let fixed_rate_accumulators = (0..accumulator_count)
+        .map(|_| reader.read_f32())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let next_id = reader.read_u32()?;
+    let free_count = reader.read_u32()? as usize;
+    if free_count > reader.remaining() / 4 {
+        return Err(SaveError::Truncated);
+    }
+    let mut free_ids = Vec::with_capacity(free_count);
+    let mut seen = HashSet::with_capacity(free_count);
+    for _ in 0..free_count {
+        let id = reader.read_u32()?;
+        if id >= next_id {
+            return Err(SaveError::Invalid("free entity id was never allocated"));
+        }
+        // A repeated id would be handed out to two live entities.
+        if !seen.insert(id) {
+            return Err(SaveError::Invalid("duplicate free entity id"));
+        }
+        free_ids.push(id);
+    }
+
+    let slot_count = reader.read_u32()?;
+    if slot_count < next_id {
+        return Err(SaveError::Invalid("fewer component slots than entities"));
+    }
+    // Every slot needs at least one presence byte per storage.
+    if slot_count as usize > reader.remaining() / 4 {
+        return Err(SaveError::Truncated);
+    }
+    let count = slot_count as usize;
+    let transforms = read_slots(&mut reader, count, |r| {
+        Ok(Transform {
+            position: r.read_sim_vec()?,
+            rotation: r.read_f32()?,
+            scale: Vec2::new(r.read_f32()?, r.read_f32()?),
+        })
+    })?;
+    let velocities = read_slots(&mut reader, count, |r| r.read_sim_vec())?;
+    let wall_bounce_colliders = read_slots(&mut reader, count, |r| {
+        Ok(BounceCollider {
+            radius: r.read_f32()?,
+        })
+    })?;
+    let spins = read_slots(&mut reader, count, |r| {
+        Ok(Spin {
+            value: r.read_f32()?,
+        })
+    })?;
+    let timers = read_slots(&mut reader, count, |r| {
+        let duration = r.read_f32()?;
+        let elapsed = r.read_f32()?;
+        let mode = match r.read_u8()? {
+            0 => TimerMode::Once,
+            1 => TimerMode::Repeating,
+            _ => return Err(SaveError::Invalid("unknown timer mode")),
+        };
+        Ok(Timer::restore(
+            duration,
+            elapsed,
+            mode,
+            r.read_bool()?,
+            r.read_u32()?,
+        ))
+    })?;
+    let colliders = read_slots(&mut reader, count, |r| {
+        let shape = match r.read_u8()? {
+            0 => ColliderShape::Circle {
+                radius: r.read_f32()?,
+            },
+            1 => ColliderShape::Aabb {
+                half_width: r.read_f32()?,
+                half_height: r.read_f32()?,
+            },
+            _ => return Err(SaveError::Invalid("unknown collider shape")),
+        };
+        Ok(Collider {
+            shape,
+            offset_x: r.read_f32()?,
+            offset_y: r.read_f32()?,
+            layer: r.read_u32()?,
+            mask: r.read_u32()?,
+            material: if r.read_bool()? {
+                Some(read_material(r)?)
+            } else {
+                None
+            },
+        })
+    })?;
+    let accelerations = read_slots(&mut reader, count, |r| {
+        Ok(Acceleration {
+            x: r.read_f32()?,
+            y: r.read_f32()?,
+        })
+    })?;
+    let drags = read_slots(&mut reader, count, |r| {
+        Ok(Drag {
+            rate: r.read_f32()?,
+        })
+    })?;
+    let max_speeds = read_slots(&mut reader, count, |r| {
+        Ok(MaxSpeed {
+            value: r.read_f32()?,
+        })
+    })?;
+
+    let kinematic_bodies = read_slots(&mut reader, count, |r| {
+        let velocity = Vec2::new(r.read_f32()?, r.read_f32()?);
+        let input_velocity = Vec2::new(r.read_f32()?, r.read_f32()?);
+        let last_position = if r.read_bool()? {
+            Some(Vec2::new(r.read_f32()?, r.read_f32()?))
+        } else {
+            None
+        };
+        Ok(KinematicBody {
+            velocity,
+            input_velocity,
+            last_position,
+        })
+    })?;
+
+    let game_len = reader.read_u32()? as usize;
+    let mut game = SaveReader::new(reader.take(game_len)?);
+    game.entity_limit = slot_count;
+    reader.finish()?;
+
+    (api.read)(world, &mut game)?;
+
+    world.field = field;
+    world.input = input;
+    world.rng = rng;
+    world.time.set_elapsed(elapsed);
+    world.substeps = substeps;
+    world.fixed_rate_accumulators = fixed_rate_accumulators;
+    world.transforms = transforms;
+    world.velocities = velocities;
+    world.wall_bounce_colliders = wall_bounce_colliders;
+    world.spins = spins;
+    world.timers = timers;
+    world.colliders = colliders;
+    world.accelerations = accelerations;
+    world.drags = drags;
+    world.max_speeds = max_speeds;
+    world.kinematic_bodies = kinematic_bodies;
+    world.contacts.clear();
+    world.spatial_hash.clear();
+    world.field_exits.clear();
+    world.set_allocator(EntityAllocator::from_parts(next_id, free_ids));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::ecs::components::Velocity;
+    use crate::engine::ecs::resources::FieldEdge;
+
+    const GAME: GameId = GameId(7);
+
+    #[derive(Debug, PartialEq)]
+    struct Counter {
+        value: u32,
+        target: EntityId,
+    }
+
+    fn write_counter(world: &World, writer: &mut SaveWriter) {
+        let counter = world.resource::<Counter>();
+        writer.write_u32(counter.value);
+        writer.write_entity(counter.target);
+    }
+
+    fn read_counter(world: &mut World, reader: &mut SaveReader) -> Result<(), SaveError> {
+        let value = reader.read_u32()?;
+        let target = reader.read_entity()?;
+        reader.finish()?;
+        *world.resource_mut::<Counter>() = Counter { value, target };
+        Ok(())
+    }
+
+    fn counter_api() -> SaveApi {
+        SaveApi::new(write_counter, read_counter)
+    }
+
+    fn sample_world() -> World {
+        let mut world = World::new(320.0, 240.0);
+        let first = world.spawn();
+        let second = world.spawn();
+        let third = world.spawn();
+        world.set_transform(
+            first,
+            Transform::at(1.5, -2.0)
+                .with_rotation(0.75)
+                .with_scale(Vec2::new(2.0, 0.5)),
+        );
+        world.set_velocity(first, Velocity::new(30.0, 40.0));
+        world.set_wall_bounce_collider(first, BounceCollider { radius: 4.0 });
+        world.set_spin(first, Spin { value: 0.25 });
+        world.set_timer(
+            third,
+            Timer::restore(2.0, 0.5, TimerMode::Repeating, true, 1),
+        );
+        world.set_transform(third, Transform::at(9.0, 8.0));
+        world.set_collider(first, Collider::circle(4.0));
+        world.set_collider(
+            third,
+            Collider::aabb(5.0, 30.0)
+                .with_offset(0.0, -2.0)
+                .on_layer(0b100)
+                .with_mask(0b011)
+                .with_material(Material::ELASTIC.with_friction(0.5)),
+        );
+        world.set_acceleration(first, Acceleration { x: 0.0, y: -120.0 });
+        world.set_drag(first, Drag { rate: 6.0 });
+        world.set_max_speed(first, MaxSpeed { value: 300.0 });
+        world.set_kinematic_body(
+            first,
+            KinematicBody {
+                velocity: Vec2::new(-12.0, 3.5),
+                input_velocity: Vec2::new(-6.0, 0.0),
+                last_position: Some(Vec2::new(1.0, -2.5)),
+            },
+        );
+        world.set_kinematic_body(third, KinematicBody::default());
+        world.despawn(second);
+        world.input.bits = 0b101;
+        world.field.edges = FieldEdges::default()
+            .with(FieldEdge::Bottom, EdgeBehavior::Exit)
+            .with(FieldEdge::Left, EdgeBehavior::Wrap);
+        world.field.wall_material = Material::ELASTIC.with_restitution(0.75);
+        world.fixed_rate_accumulators = vec![0.03, 0.0];
+        world.insert_resource(Counter {
+            value: 42,
+            target: third,
+        });
+        world
+    }
+
+    #[test]
+    fn round_trips_components_resources_and_allocator() {
+        let source = sample_world();
+        let bytes = encode(GAME, &source, &counter_api());
+
+        let mut restored = World::new(1.0, 1.0);
+        restored.insert_resource(Counter {
+            value: 0,
+            target: EntityId(0),
+        });
+        decode(GAME, &mut restored, &counter_api(), &bytes).unwrap();
+
+        assert_eq!(restored.field, source.field);
+        assert_eq!(restored.input, source.input);
+        assert_eq!(restored.rng, source.rng);
+        assert_eq!(restored.time.elapsed(), source.time.elapsed());
+        assert_eq!(restored.transforms, source.transforms);
+        assert_eq!(restored.velocities, source.velocities);
+        assert_eq!(restored.wall_bounce_colliders, source.wall_bounce_colliders);
+        assert_eq!(restored.spins, source.spins);
+        assert_eq!(restored.timers, source.timers);
+        assert_eq!(restored.colliders, source.colliders);
+        assert_eq!(restored.accelerations, source.accelerations);
+        assert_eq!(restored.drags, source.drags);
+        assert_eq!(restored.max_speeds, source.max_speeds);
+        assert_eq!(restored.kinematic_bodies, source.kinematic_bodies);
+        assert_eq!(
+            restored.fixed_rate_accumulators,
+            source.fixed_rate_accumulators
+        );
+        assert_eq!(
+            restored.resource::<Counter>(),
+            &Counter {
+                value: 42,
+                target: EntityId(2)
+            }
+        );
+        assert_eq!(restored.spawn(), EntityId(1), "freed id should be reused");
+        assert_eq!(restored.spawn(), EntityId(3));
+    }
+
+    #[test]
+    fn rejects_other_layout_versions_without_touching_world() {
+        let mut bytes = encode(GAME, &sample_world(), &counter_api());
+        bytes[4..6].copy_from_slice(&(SAVE_FORMAT_VERSION + 1).to_le_bytes());
+
+        let mut world = World::new(1.0, 1.0);
+        world.insert_resource(Counter {
+            value: 0,
+            target: EntityId(0),
+        });
+        let result = decode(GAME, &mut world, &counter_api(), &bytes);
+
+        assert_eq!(
+            result,
+            Err(SaveError::UnsupportedVersion(SAVE_FORMAT_VERSION + 1))
+        );
+        assert_eq!(world.field.width, 1.0);
+        assert!(world.transforms.is_empty());
+        assert_eq!(world.resource::<Counter>().value, 0);
+    }
+
+    #[test]
+    fn rejects_saves_from_another_game() {
+        let bytes = encode(GAME, &sample_world(), &counter_api());
+        let mut world = sample_world();
+
+        let result = decode(GameId(8), &mut world, &counter_api(), &bytes);
+
+        assert_eq!(
+            result,
+            Err(SaveError::GameMismatch {
+                expected: GameId(8),
+                found: GAME
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_saves_from_a_build_with_another_number_format() {
+        let mut bytes = encode(GAME, &sample_world(), &counter_api());
+        bytes[10] ^= 1;
+        let mut world = sample_world();
+
+        assert!(matches!(
+            decode(GAME, &mut world, &counter_api(), &bytes),
+            Err(SaveError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_and_garbage_input() {
+        let bytes = encode(GAME, &sample_world(), &counter_api());
+        let mut world = sample_world();
+
+        for len in [0, 3, 10, bytes.len() - 1] {
+            assert!(
+                decode(GAME, &mut world, &counter_api(), &bytes[..len]).is_err(),
+                "prefix of {} bytes should not load",
+                len
+            );
+        }
+        assert_eq!(
+            decode(GAME, &mut world, &counter_api(), b"nope, not a save"),
+            Err(SaveError::BadMagic)
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_free_entity_ids() {
+        let mut world = sample_world();
+        world.set_allocator(EntityAllocator::from_parts(3, vec![1, 1]));
+        let bytes = encode(GAME, &world, &counter_api());
+
+        let result = decode(GAME, &mut sample_world(), &counter_api(), &bytes);
+
+        assert_eq!(result, Err(SaveError::Invalid("duplicate free entity id")));
+    }
+
+    #[test]
+    fn game_section_cannot_reference_missing_entities() {
+        let mut world = sample_world();
+        world.resource_mut::<Counter>().target = EntityId(99);
+        let bytes = encode(GAME, &world, &counter_api());
+
+        let result = decode(GAME, &mut sample_world(), &counter_api(), &bytes);
+
+        assert_eq!(result, Err(SaveError::Invalid("entity id out of range")));
+    }
+}
@@ -1,24 +1,45 @@
-use crate::engine::ecs::components::{BounceCollider, Transform, Velocity};
+use crate::engine::ecs::components::{
+    Acceleration, BounceCollider, Collider, ColliderShape, Drag, KinematicBody, MaxSpeed, SimVec2,
+    Transform, Velocity,
+};
 use crate::engine::ecs::entity::EntityId;
+use crate::engine::ecs::layers::CollisionLayers;
+use crate::engine::ecs::resources::{EdgeBehavior, FieldEdge, FieldEdges};
 use crate::engine::ecs::schedule::{Schedule, SystemPhase};
 use crate::engine::ecs::world::World;
+use crate::engine::math::{self, Vec2};
+use crate::engine::save::{SaveApi, SaveError, SaveReader, SaveWriter};
 use crate::engine::{
-    Snapshot, TuningApi, INPUT_LEFT, INPUT_RIGHT, TUNING_STATUS_APPLIED, TUNING_STATUS_REJECTED,
-    TUNING_STATUS_UNKNOWN_PARAM,
+    Snapshot, TuningApi, INPUT_LEFT, INPUT_PRECISION, INPUT_RIGHT, TUNING_STATUS_APPLIED,
+    TUNING_STATUS_REJECTED, TUNING_STATUS_UNKNOWN_PARAM,
 };
 
 const PADDLE_WIDTH: f32 = 100.0;
 const PADDLE_HEIGHT: f32 = 16.0;
 const PADDLE_SPEED: f32 = 400.0;
+const PADDLE_ACCELERATION: f32 = 8000.0;
+const PADDLE_DRAG: f32 = 10.0;
+/// Share of the acceleration kept while the precision key is held.
+const PADDLE_PRECISION_SCALE: f32 = 0.35;
+/// Share of the paddle's tracked velocity added to the ball on a top hit.
+/// Off by default, leaving the bounce to the zone angles alone.
+const PADDLE_LAUNCH_BIAS: f32 = 0.0;
 const BALL_RADIUS: f32 = 6.0;
 const BALL_SPEED: f32 = 420.0;
 const BOUNCE_ZONE_ANGLES: [f32; 8] = [35.0, 45.0, 66.0, 66.0, 66.0, 66.0, 45.0, 35.0];
+const STARTING_LIVES: u32 = 3;
 
 const MIN_PADDLE_WIDTH: f32 = 20.0;
 const MIN_PADDLE_HEIGHT: f32 = 6.0;
 const MAX_PADDLE_HEIGHT: f32 = 80.0;
 const MIN_PADDLE_SPEED: f32 = 50.0;
 const MAX_PADDLE_SPEED: f32 = 1200.0;
+const MIN_PADDLE_ACCELERATION: f32 = 500.0;
+const MAX_PADDLE_ACCELERATION: f32 = 20000.0;
+const MIN_PADDLE_DRAG: f32 = 0.0;
+const MAX_PADDLE_DRAG: f32 = 40.0;
+const MIN_PADDLE_LAUNCH_BIAS: f32 = 0.0;
+const MAX_PADDLE_LAUNCH_BIAS: f32 = 1.0;
 const MIN_BALL_RADIUS: f32 = 2.0;
 const MAX_BALL_RADIUS: f32 = 30.0;
 const MIN_BALL_SPEED: f32 = 50.0;
@@ -45,6 +66,9 @@ pub enum ArkanoidTuningParam {
     BounceZone5Angle = 10,
     BounceZone6Angle = 11,
     BounceZone7Angle = 12,
+    PaddleAcceleration = 13,
+    PaddleDrag = 14,
+    PaddleLaunchBias = 15,
 }
 
 impl TryFrom<u32> for ArkanoidTuningParam {
@@ -65,6 +89,9 @@ impl TryFrom<u32> for ArkanoidTuningParam {
             10 => Ok(Self::BounceZone5Angle),
             11 => Ok(Self::BounceZone6Angle),
             12 => Ok(Self::BounceZone7Angle),
+            13 => Ok(Self::PaddleAcceleration),
+            14 => Ok(Self::PaddleDrag),
+            15 => Ok(Self::PaddleLaunchBias),
             _ => Err(()),
         }
     }
@@ -74,7 +101,11 @@ impl TryFrom<u32> for ArkanoidTuningParam {
 struct ArkanoidTuning {
     paddle_width: f32,
     paddle_height: f32,
+    /// Top speed of the paddle.
     paddle_speed: f32,
+    paddle_acceleration: f32,
+    paddle_drag: f32,
+    paddle_launch_bias: f32,
     ball_radius: f32,
     ball_speed: f32,
     bounce_zone_angles: [f32; 8],
@@ -86,6 +117,9 @@ impl Default for ArkanoidTuning {
             paddle_width: PADDLE_WIDTH,
             paddle_height: PADDLE_HEIGHT,
             paddle_speed: PADDLE_SPEED,
+            paddle_acceleration: PADDLE_ACCELERATION,
+            paddle_drag: PADDLE_DRAG,
+            paddle_launch_bias: PADDLE_LAUNCH_BIAS,
             ball_radius: BALL_RADIUS,
             ball_speed: BALL_SPEED,
             bounce_zone_angles: BOUNCE_ZONE_ANGLES,
@@ -96,6 +130,7 @@ impl Default for ArkanoidTuning {
 struct ArkanoidState {
     paddle: EntityId,
     ball: EntityId,
+    lives: u32,
 }
 
 fn zone_angle_index(param: ArkanoidTuningParam) -> Option<usize> {
@@ -114,10 +149,8 @@ fn zone_angle_index(param: ArkanoidTuningParam) -> Option<usize> {
 
 fn launch_velocity(speed: f32) -> Velocity {
     let angle = std::f32::consts::PI / 4.0;
-    Velocity {
-        x: angle.cos() * speed,
-        y: angle.sin() * speed,
-    }
+    let (sin, cos) = math::sin_cos(angle);
+    Velocity::new(cos * speed, sin * speed)
 }
 
 fn apply_tuning(world: &mut World) {
@@ -126,23 +159,29 @@ fn apply_tuning(world: &mut World) {
     let tuning = world.resource::<ArkanoidTuning>();
     let ball_radius = tuning.ball_radius;
     let ball_speed = tuning.ball_speed;
+    let paddle_speed = tuning.paddle_speed;
+    let paddle_drag = tuning.paddle_drag;
+    let paddle_shape = ColliderShape::Aabb {
+        half_width: tuning.paddle_width / 2.0,
+        half_height: tuning.paddle_height / 2.0,
+    };
     let paddle_y = world.field.height - tuning.paddle_height * 1.5;
 
-    world.collider_mut(ball).radius = ball_radius;
-    world.transform_mut(paddle).y = paddle_y;
+    world.wall_bounce_collider_mut(ball).radius = ball_radius;
+    world.collider_mut(ball).shape = ColliderShape::Circle {
+        radius: ball_radius,
+    };
+    world.collider_mut(paddle).shape = paddle_shape;
+    world.transform_mut(paddle).position.set_y(paddle_y);
+    world.max_speed_mut(paddle).value = paddle_speed;
+    world.drag_mut(paddle).rate = paddle_drag;
     apply_ball_speed(world, ball, ball_speed);
 }
 
+/// A parked ball (out of lives) has no direction to keep and stays put.
 fn apply_ball_speed(world: &mut World, ball: EntityId, target_speed: f32) {
-    let current_velocity = world.velocity_mut(ball);
-    let current_speed = current_velocity.x.hypot(current_velocity.y);
-    let (dir_x, dir_y) = (
-        current_velocity.x / current_speed,
-        current_velocity.y / current_speed,
-    );
-
-    current_velocity.x = dir_x * target_speed;
-    current_velocity.y = dir_y * target_speed;
+    let velocity = world.velocity_mut(ball);
+    *velocity = Velocity::from_vec2(velocity.to_vec2().with_length(target_speed));
 }
 
 fn set_tuning_param(world: &mut World, param_id: u32, value: f32) -> u32 {
@@ -167,6 +206,16 @@ fn set_tuning_param(world: &mut World, param_id: u32, value: f32) -> u32 {
         ArkanoidTuningParam::PaddleSpeed => {
             tuning.paddle_speed = value.clamp(MIN_PADDLE_SPEED, MAX_PADDLE_SPEED);
         }
+        ArkanoidTuningParam::PaddleAcceleration => {
+            tuning.paddle_acceleration =
+                value.clamp(MIN_PADDLE_ACCELERATION, MAX_PADDLE_ACCELERATION);
+        }
+        ArkanoidTuningParam::PaddleDrag => {
+            tuning.paddle_drag = value.clamp(MIN_PADDLE_DRAG, MAX_PADDLE_DRAG);
+        }
+        ArkanoidTuningParam::PaddleLaunchBias => {
+            tuning.paddle_launch_bias = value.clamp(MIN_PADDLE_LAUNCH_BIAS, MAX_PADDLE_LAUNCH_BIAS);
+        }
         ArkanoidTuningParam::BallRadius => {
             tuning.ball_radius = value.clamp(MIN_BALL_RADIUS, MAX_BALL_RADIUS);
         }
@@ -192,6 +241,9 @@ fn get_tuning_param(world: &World, param_id: u32) -> Option<f32> {
         ArkanoidTuningParam::PaddleWidth => tuning.paddle_width,
         ArkanoidTuningParam::PaddleHeight => tuning.paddle_height,
         ArkanoidTuningParam::PaddleSpeed => tuning.paddle_speed,
+        ArkanoidTuningParam::PaddleAcceleration => tuning.paddle_acceleration,
+        ArkanoidTuningParam::PaddleDrag => tuning.paddle_drag,
+        ArkanoidTuningParam::PaddleLaunchBias => tuning.paddle_launch_bias,
         ArkanoidTuningParam::BallRadius => tuning.ball_radius,
         ArkanoidTuningParam::BallSpeed => tuning.ball_speed,
         _ => {
@@ -208,9 +260,12 @@ fn reset_tuning_defaults(world: &mut World) {
     apply_tuning(world);
 }
 
+/// Keys steer the paddle through its acceleration; the mouse moves it
+/// directly and leaves its `Velocity` at rest. A mouse move is clamped to the
+/// field right away, before the paddle's `KinematicBody` measures it.
 fn apply_input(world: &mut World, dt: f32) {
     let paddle = world.resource::<ArkanoidState>().paddle;
-    let paddle_speed = world.resource::<ArkanoidTuning>().paddle_speed;
+    let paddle_acceleration = world.resource::<ArkanoidTuning>().paddle_acceleration;
 
     let input_bits = world.input.bits;
     let mouse_delta_raw = (input_bits >> 16) as u16 as i16;
@@ -218,12 +273,21 @@ fn apply_input(world: &mut World, dt: f32) {
 
     if mouse_delta.abs() > 0.1 {
         const MOUSE_SENSITIVITY: f32 = 1.5;
-        world.transform_mut(paddle).x += mouse_delta * MOUSE_SENSITIVITY;
+        let position = &mut world.transform_mut(paddle).position;
+        position.set_x(position.x() + mouse_delta * MOUSE_SENSITIVITY);
+        world.velocity_mut(paddle).set_x(0.0);
+        world.acceleration_mut(paddle).x = 0.0;
+        clamp_paddle_to_field(world, dt);
     } else {
         let left = (input_bits & INPUT_LEFT) != 0;
         let right = (input_bits & INPUT_RIGHT) != 0;
         let dir = (right as i32) - (left as i32);
-        world.transform_mut(paddle).x += dir as f32 * paddle_speed * dt;
+        let scale = if (input_bits & INPUT_PRECISION) != 0 {
+            PADDLE_PRECISION_SCALE
+        } else {
+            1.0
+        };
+        world.acceleration_mut(paddle).x = dir as f32 * paddle_acceleration * scale;
     }
 }
 
@@ -236,88 +300,178 @@ fn clamp_paddle_to_field(world: &mut World, _dt: f32) {
     let min_x = paddle_half_width;
     let max_x = field_width - paddle_half_width;
     let paddle_transform = world.transform_mut(paddle);
-    paddle_transform.x = paddle_transform.x.clamp(min_x, max_x);
+    let x = paddle_transform.position.x();
+    if !(min_x..=max_x).contains(&x) {
+        paddle_transform.position.set_x(x.clamp(min_x, max_x));
+        world.velocity_mut(paddle).set_x(0.0);
+    }
 }
 
-fn ball_paddle_collision(world: &mut World, _dt: f32) {
+/// Bounces the ball off the paddle using the contact from `detect_contacts`.
+/// Top hits bounce by zone angle, nudged along the paddle's tracked motion
+/// by the launch bias; side hits reflect. The ball then travels
+/// the rest of the step from the contact point.
+fn ball_paddle_collision(world: &mut World, dt: f32) {
     let ball = world.resource::<ArkanoidState>().ball;
     let paddle = world.resource::<ArkanoidState>().paddle;
+    let Some(contact) = world
+        .contacts
+        .iter()
+        .find(|contact| contact.involves(ball) && contact.involves(paddle))
+        .copied()
+    else {
+        return;
+    };
     let tuning = world.resource::<ArkanoidTuning>();
-    let paddle_transform = world.transform(paddle);
-    let ball_transform = world.transform(ball);
-    let ball_velocity = world.velocity(ball);
+    let ball_velocity = world.velocity(ball).to_vec2();
+    let normal = contact.normal_toward(ball);
+    let hit = contact.point() + normal * tuning.ball_radius;
 
     // Already moving away from paddle
-    if ball_velocity.y <= 0.0 {
+    if ball_velocity.dot(normal) >= 0.0 {
         return;
     }
 
-    let paddle_half_width = tuning.paddle_width / 2.0;
-    let paddle_half_height = tuning.paddle_height / 2.0;
-    let paddle_left = paddle_transform.x - paddle_half_width;
-    let paddle_right = paddle_transform.x + paddle_half_width;
-    let paddle_top = paddle_transform.y - paddle_half_height;
-    let paddle_bottom = paddle_transform.y + paddle_half_height;
-
-    let ball_bottom = ball_transform.y + tuning.ball_radius;
-    let ball_top = ball_transform.y - tuning.ball_radius;
-    let ball_right = ball_transform.x + tuning.ball_radius;
-    let ball_left = ball_transform.x - tuning.ball_radius;
-
-    // Ball completely outside paddle
-    if ball_bottom < paddle_top
-        || ball_top > paddle_bottom
-        || ball_right < paddle_left
-        || ball_left > paddle_right
-    {
+    let speed = ball_velocity.length();
+    let bounced = if normal.y < 0.0 {
+        let paddle_left = world.transform(paddle).position.x() - tuning.paddle_width / 2.0;
+        let relative_hit = (hit.x - paddle_left) / tuning.paddle_width;
+        let zone_count = tuning.bounce_zone_angles.len();
+        let zone_index = (relative_hit * zone_count as f32).floor() as usize;
+        let zone_index = zone_index.clamp(0, zone_count - 1);
+
+        let angle_from_horizontal = tuning.bounce_zone_angles[zone_index];
+        let angle_rad = (90.0 - angle_from_horizontal).to_radians();
+        let direction = if zone_index < zone_count / 2 {
+            -1.0
+        } else {
+            1.0
+        };
+        let (sin, cos) = math::sin_cos(angle_rad);
+        let zone_bounce = Vec2::new(direction * sin, -cos) * speed;
+        let english = world.kinematic_body(paddle).velocity.x * tuning.paddle_launch_bias;
+        (zone_bounce + Vec2::new(english, 0.0)).with_length(speed)
+    } else {
+        ball_velocity.reflect(normal)
+    };
+
+    *world.velocity_mut(ball) = Velocity::from_vec2(bounced);
+    let remaining = (1.0 - contact.time) * dt;
+    world.transform_mut(ball).position = SimVec2::from_vec2(hit + bounced * remaining);
+}
+
+/// The bottom edge is open: `bounce_in_field` reports the ball leaving, which
+/// costs a life. The ball is served again from the middle while lives remain,
+/// and parked there once they run out.
+fn lose_life(world: &mut World, _dt: f32) {
+    let ball = world.resource::<ArkanoidState>().ball;
+    let lost = world
+        .field_exits
+        .iter()
+        .any(|exit| exit.entity == ball && exit.edge == FieldEdge::Bottom);
+    if !lost {
         return;
     }
 
-    let relative_hit = (ball_transform.x - paddle_left) / tuning.paddle_width;
-    let zone_index = (relative_hit * tuning.bounce_zone_angles.len() as f32).floor() as usize;
-    let zone_index = zone_index.clamp(0, tuning.bounce_zone_angles.len() - 1);
-
-    let angle_from_horizontal = tuning.bounce_zone_angles[zone_index];
-    let angle_rad = (90.0 - angle_from_horizontal).to_radians();
-    let speed = ball_velocity.x.hypot(ball_velocity.y);
-    let direction = if zone_index < tuning.bounce_zone_angles.len() / 2 {
-        -1.0
+    let state = world.resource_mut::<ArkanoidState>();
+    state.lives = state.lives.saturating_sub(1);
+    let lives = state.lives;
+    let speed = world.resource::<ArkanoidTuning>().ball_speed;
+    let (x, y) = (world.field.width / 2.0, world.field.height / 2.0);
+    *world.transform_mut(ball) = Transform::at(x, y);
+    *world.velocity_mut(ball) = if lives > 0 {
+        launch_velocity(speed)
     } else {
-        1.0
+        Velocity::ZERO
     };
+}
 
-    let new_vx = direction * angle_rad.sin() * speed;
-    let new_vy = -angle_rad.cos() * speed;
-    let ball_radius = tuning.ball_radius;
+fn write_save(world: &World, writer: &mut SaveWriter) {
+    let state = world.resource::<ArkanoidState>();
+    writer.write_entity(state.paddle);
+    writer.write_entity(state.ball);
+    writer.write_u32(state.lives);
 
-    let ball_velocity_mut = world.velocity_mut(ball);
-    ball_velocity_mut.x = new_vx;
-    ball_velocity_mut.y = new_vy;
+    let tuning = world.resource::<ArkanoidTuning>();
+    writer.write_f32(tuning.paddle_width);
+    writer.write_f32(tuning.paddle_height);
+    writer.write_f32(tuning.paddle_speed);
+    writer.write_f32(tuning.paddle_acceleration);
+    writer.write_f32(tuning.paddle_drag);
+    writer.write_f32(tuning.paddle_launch_bias);
+    writer.write_f32(tuning.ball_radius);
+    writer.write_f32(tuning.ball_speed);
+    for angle in tuning.bounce_zone_angles {
+        writer.write_f32(angle);
+    }
+}
 
-    let ball_transform_mut = world.transform_mut(ball);
-    ball_transform_mut.y = paddle_top - ball_radius;
+fn read_save(world: &mut World, reader: &mut SaveReader) -> Result<(), SaveError> {
+    let state = ArkanoidState {
+        paddle: reader.read_entity()?,
+        ball: reader.read_entity()?,
+        lives: reader.read_u32()?,
+    };
+    let mut tuning = ArkanoidTuning {
+        paddle_width: reader.read_f32()?,
+        paddle_height: reader.read_f32()?,
+        paddle_speed: reader.read_f32()?,
+        paddle_acceleration: reader.read_f32()?,
+        paddle_drag: reader.read_f32()?,
+        paddle_launch_bias: reader.read_f32()?,
+        ball_radius: reader.read_f32()?,
+        ball_speed: reader.read_f32()?,
+        bounce_zone_angles: [0.0; 8],
+    };
+    for angle in &mut tuning.bounce_zone_angles {
+        *angle = reader.read_f32()?;
+    }
+    reader.finish()?;
+
+    *world.resource_mut::<ArkanoidState>() = state;
+    *world.resource_mut::<ArkanoidTuning>() = tuning;
+    Ok(())
 }
 
-pub fn build_world(width: f32, height: f32) -> (World, Schedule, Snapshot, TuningApi) {
+pub fn build_world(width: f32, height: f32) -> (World, Schedule, Snapshot, TuningApi, SaveApi) {
     let mut world = World::new(width, height);
     let tuning = ArkanoidTuning::default();
+    world.field.edges = FieldEdges::default().with(FieldEdge::Bottom, EdgeBehavior::Exit);
+    world.collision_layers = CollisionLayers::new()
+        .with_layer("ball")
+        .with_layer("paddle");
+    let ball_layer = world.collision_layers.layer("ball");
+    let paddle_layer = world.collision_layers.layer("paddle");
+
     let paddle = world.spawn();
     world.set_transform(
         paddle,
-        Transform {
-            x: width / 2.0,
-            y: height - tuning.paddle_height * 1.5,
+        Transform::at(width / 2.0, height - tuning.paddle_height * 1.5),
+    );
+    world.set_collider(
+        paddle,
+        Collider::aabb(tuning.paddle_width / 2.0, tuning.paddle_height / 2.0)
+            .on_layer(paddle_layer)
+            .with_mask(ball_layer),
+    );
+    world.set_velocity(paddle, Velocity::ZERO);
+    world.set_acceleration(paddle, Acceleration { x: 0.0, y: 0.0 });
+    world.set_drag(
+        paddle,
+        Drag {
+            rate: tuning.paddle_drag,
         },
     );
-
-    let ball = world.spawn();
-    world.set_transform(
-        ball,
-        Transform {
-            x: width / 2.0,
-            y: height / 2.0,
+    world.set_max_speed(
+        paddle,
+        MaxSpeed {
+            value: tuning.paddle_speed,
         },
     );
+    world.set_kinematic_body(paddle, KinematicBody::default());
+
+    let ball = world.spawn();
+    world.set_transform(ball, Transform::at(width / 2.0, height / 2.0));
     world.set_velocity(ball, launch_velocity(tuning.ball_speed));
     world.set_wall_bounce_collider(
         ball,
@@ -325,20 +479,36 @@ pub fn build_world(width: f32, height: f32) -> (World, Schedule, Snapshot, Tunin
             radius: tuning.ball_radius,
         },
     );
+    world.set_collider(
+        ball,
+        Collider::circle(tuning.ball_radius)
+            .on_layer(ball_layer)
+            .with_mask(paddle_layer),
+    );
 
-    world.insert_resource(ArkanoidState { paddle, ball });
+    world.insert_resource(ArkanoidState {
+        paddle,
+        ball,
+        lives: STARTING_LIVES,
+    });
     world.insert_resource(tuning);
 
     let schedule = Schedule::new()
         .with_system_in_phase(SystemPhase::Control, apply_input)
-        .with_system_in_phase(SystemPhase::Resolve, clamp_paddle_to_field)
-        .with_system_in_phase(SystemPhase::Resolve, ball_paddle_collision);
+        .with_system_in_phase(SystemPhase::Physics, clamp_paddle_to_field)
+        .after("integrate_velocity")
+        .before("update_spatial_hash")
+        .with_system_in_phase(SystemPhase::Physics, ball_paddle_collision)
+        .after("detect_contacts")
+        .before("bounce_in_field")
+        .with_system_in_phase(SystemPhase::Resolve, lose_life);
 
     (
         world,
         schedule,
         Snapshot::new(write_snapshot, vec![0.0; SNAPSHOT_LEN]),
         TuningApi::new(set_tuning_param, get_tuning_param, reset_tuning_defaults),
+        SaveApi::new(write_save, read_save),
     )
 }
 
@@ -351,15 +521,16 @@ fn write_snapshot(world: &World, snapshot: &mut [f32]) {
     let tuning = world.resource::<ArkanoidTuning>();
 
     let paddle_transform = world.transform(paddle);
-    snapshot[PaddleX.idx()] = paddle_transform.x;
-    snapshot[PaddleY.idx()] = paddle_transform.y;
+    snapshot[PaddleX.idx()] = paddle_transform.position.x();
+    snapshot[PaddleY.idx()] = paddle_transform.position.y();
     snapshot[PaddleWidth.idx()] = tuning.paddle_width;
     snapshot[PaddleHeight.idx()] = tuning.paddle_height;
 
     let ball_transform = world.transform(ball);
-    snapshot[BallX.idx()] = ball_transform.x;
-    snapshot[BallY.idx()] = ball_transform.y;
+    snapshot[BallX.idx()] = ball_transform.position.x();
+    snapshot[BallY.idx()] = ball_transform.position.y();
     snapshot[BallSize.idx()] = tuning.ball_radius * 2.0;
+    snapshot[Lives.idx()] = state.lives as f32;
 }
 
 #[repr(usize)]
@@ -371,7 +542,8 @@ enum SnapshotField {
     BallX = 4,
     BallY = 5,
     BallSize = 6,
-    Count = 7,
+    Lives = 7,
+    Count = 8,
 }
 
 impl SnapshotField {
@@ -383,7 +555,8 @@ impl SnapshotField {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::engine::TUNING_STATUS_UNKNOWN_PARAM;
+    use crate::engine::ecs::systems::physics_substeps;
+    use crate::engine::{with_engine_systems, MAX_DT, TUNING_STATUS_UNKNOWN_PARAM};
 
     const EPS: f32 = 0.001;
 
@@ -397,9 +570,239 @@ mod tests {
         );
     }
 
+    fn new_game() -> (World, Schedule) {
+        let (world, schedule, _snapshot, _tuning_api, _save_api) = build_world(800.0, 600.0);
+        let schedule = with_engine_systems(schedule).build().unwrap();
+        (world, schedule)
+    }
+
     fn new_world() -> World {
-        let (world, _schedule, _snapshot, _tuning_api) = build_world(800.0, 600.0);
+        let (world, _schedule, _snapshot, _tuning_api, _save_api) = build_world(800.0, 600.0);
+        world
+    }
+
+    #[test]
+    fn fast_ball_hits_paddle_instead_of_skipping_past_it() {
+        let (mut world, mut schedule) = new_game();
+        set_tuning_param(
+            &mut world,
+            ArkanoidTuningParam::BallSpeed as u32,
+            MAX_BALL_SPEED,
+        );
+        let ball = world.resource::<ArkanoidState>().ball;
+        let paddle = world.resource::<ArkanoidState>().paddle;
+        let paddle_x = world.transform(paddle).position.x();
+        *world.transform_mut(ball) = Transform::at(paddle_x, 500.0);
+        *world.velocity_mut(ball) = Velocity::new(0.0, MAX_BALL_SPEED);
+
+        schedule.run(&mut world, MAX_DT);
+
+        assert!(world.velocity(ball).y() < 0.0);
+        assert!(
+            world.velocity(ball).x() != 0.0,
+            "ball bounced off the bottom wall, not the paddle"
+        );
+    }
+
+    #[test]
+    fn side_hit_reflects_horizontally() {
+        let (mut world, mut schedule) = new_game();
+        world.substeps.max_substeps = 1;
+        let ball = world.resource::<ArkanoidState>().ball;
+        let paddle = *world.transform(world.resource::<ArkanoidState>().paddle);
+        let left_face = paddle.position.x() - PADDLE_WIDTH / 2.0;
+        *world.transform_mut(ball) =
+            Transform::at(left_face - BALL_RADIUS - 10.0, paddle.position.y());
+        *world.velocity_mut(ball) = Velocity::new(400.0, 0.0);
+
+        schedule.run(&mut world, MAX_DT);
+
+        assert_eq!(world.velocity(ball).x(), -400.0);
+        // 10 px to the face, then the other 10 px of the step back out.
+        approx_eq(
+            world.transform(ball).position.x(),
+            left_face - BALL_RADIUS - 10.0,
+        );
+    }
+
+    #[test]
+    fn losing_the_ball_off_the_bottom_costs_a_life_and_reserves() {
+        let (mut world, mut schedule) = new_game();
+        let ball = world.resource::<ArkanoidState>().ball;
+        let paddle = world.resource::<ArkanoidState>().paddle;
+        // Well clear of the paddle, heading out of the field.
+        world.transform_mut(paddle).position.set_x(100.0);
+        world.transform_mut(ball).position.set_x(700.0);
+        world.transform_mut(ball).position.set_y(595.0);
+        *world.velocity_mut(ball) = Velocity::new(0.0, 300.0);
+
+        schedule.run(&mut world, 1.0 / 60.0);
+
+        assert_eq!(world.resource::<ArkanoidState>().lives, STARTING_LIVES - 1);
+        let ball_transform = world.transform(ball);
+        approx_eq(ball_transform.position.x(), 400.0);
+        approx_eq(ball_transform.position.y(), 300.0);
+        assert_eq!(world.velocity(ball), &launch_velocity(BALL_SPEED));
+    }
+
+    #[test]
+    fn ball_is_parked_after_the_last_life() {
+        let (mut world, mut schedule) = new_game();
+        let ball = world.resource::<ArkanoidState>().ball;
+        world.resource_mut::<ArkanoidState>().lives = 1;
+        world.transform_mut(ball).position.set_x(50.0);
+        world.transform_mut(ball).position.set_y(599.0);
+        *world.velocity_mut(ball) = Velocity::new(0.0, 300.0);
+
+        schedule.run(&mut world, 1.0 / 60.0);
+
+        assert_eq!(world.resource::<ArkanoidState>().lives, 0);
+        assert_eq!(world.velocity(ball), &Velocity::new(0.0, 0.0));
+
+        set_tuning_param(&mut world, ArkanoidTuningParam::BallSpeed as u32, 600.0);
+        assert_eq!(world.velocity(ball), &Velocity::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn held_keys_accelerate_the_paddle_up_to_its_speed() {
+        let (mut world, mut schedule) = new_game();
+        let paddle = world.resource::<ArkanoidState>().paddle;
+
+        world.input.bits = INPUT_RIGHT;
+        schedule.run(&mut world, 1.0 / 60.0);
+        let first = world.velocity(paddle).x();
+        assert!(first > 0.0 && first < PADDLE_SPEED);
+
+        for _ in 0..20 {
+            schedule.run(&mut world, 1.0 / 60.0);
+        }
+        approx_eq(world.velocity(paddle).x(), PADDLE_SPEED);
+
+        world.input.bits = INPUT_RIGHT | INPUT_PRECISION;
+        for _ in 0..60 {
+            schedule.run(&mut world, 1.0 / 60.0);
+        }
+        let precise = PADDLE_ACCELERATION * PADDLE_PRECISION_SCALE / PADDLE_DRAG;
+        assert!(world.velocity(paddle).x() <= precise + EPS);
+    }
+
+    #[test]
+    fn paddle_stops_at_the_field_edge() {
+        let (mut world, mut schedule) = new_game();
+        let paddle = world.resource::<ArkanoidState>().paddle;
+
+        world.input.bits = INPUT_LEFT;
+        for _ in 0..120 {
+            schedule.run(&mut world, 1.0 / 60.0);
+        }
+
+        approx_eq(world.transform(paddle).position.x(), PADDLE_WIDTH / 2.0);
+        assert_eq!(world.velocity(paddle).x(), 0.0);
+    }
+
+    fn mouse_bits(delta: i16) -> u32 {
+        (delta as u16 as u32) << 16
+    }
+
+    #[test]
+    fn mouse_movement_is_tracked_as_paddle_velocity() {
+        let (mut world, mut schedule) = new_game();
+        world.substeps.max_substeps = 1;
+        let paddle = world.resource::<ArkanoidState>().paddle;
+        schedule.run(&mut world, 0.05);
+
+        world.input.bits = mouse_bits(10);
+        schedule.run(&mut world, 0.05);
+
+        assert_eq!(world.velocity(paddle).x(), 0.0);
+        approx_eq(world.kinematic_body(paddle).velocity.x, 15.0 / 0.05);
+    }
+
+    #[test]
+    fn paddle_held_at_the_edge_tracks_no_velocity() {
+        let (mut world, mut schedule) = new_game();
+        world.substeps.max_substeps = 1;
+        let paddle = world.resource::<ArkanoidState>().paddle;
         world
+            .transform_mut(paddle)
+            .position
+            .set_x(PADDLE_WIDTH / 2.0);
+        schedule.run(&mut world, 0.05);
+
+        world.input.bits = mouse_bits(-20);
+        schedule.run(&mut world, 0.05);
+
+        approx_eq(world.transform(paddle).position.x(), PADDLE_WIDTH / 2.0);
+        assert_eq!(world.kinematic_body(paddle).velocity.x, 0.0);
+    }
+
+    #[test]
+    fn mouse_movement_counts_over_the_whole_frame_when_sub_stepping() {
+        let (mut world, mut schedule) = new_game();
+        let ball = world.resource::<ArkanoidState>().ball;
+        let paddle = world.resource::<ArkanoidState>().paddle;
+        schedule.run(&mut world, MAX_DT);
+
+        *world.velocity_mut(ball) = Velocity::new(0.0, -MAX_BALL_SPEED);
+        assert!(physics_substeps(&world, MAX_DT) > 1);
+        world.input.bits = mouse_bits(10);
+        schedule.run(&mut world, MAX_DT);
+
+        approx_eq(world.kinematic_body(paddle).velocity.x, 15.0 / MAX_DT);
+    }
+
+    /// Drops the ball onto the paddle in the frame the mouse moves it, and
+    /// returns the ball's velocity after the bounce.
+    fn bounce_off_a_moving_paddle(bias: f32, ball_speed: f32, max_substeps: u32) -> Vec2 {
+        let (mut world, mut schedule) = new_game();
+        world.substeps.max_substeps = max_substeps;
+        set_tuning_param(
+            &mut world,
+            ArkanoidTuningParam::PaddleLaunchBias as u32,
+            bias,
+        );
+        let ball = world.resource::<ArkanoidState>().ball;
+        let paddle = *world.transform(world.resource::<ArkanoidState>().paddle);
+        schedule.run(&mut world, 1.0 / 60.0);
+
+        let top = paddle.position.y() - PADDLE_HEIGHT / 2.0;
+        *world.transform_mut(ball) = Transform::at(paddle.position.x(), top - BALL_RADIUS - 5.0);
+        *world.velocity_mut(ball) = Velocity::new(0.0, ball_speed);
+        assert_eq!(physics_substeps(&world, 1.0 / 60.0), max_substeps);
+        world.input.bits = mouse_bits(10);
+        schedule.run(&mut world, 1.0 / 60.0);
+        world.velocity(ball).to_vec2()
+    }
+
+    #[test]
+    fn launch_bias_carries_paddle_motion_into_the_ball() {
+        let plain = bounce_off_a_moving_paddle(0.0, 600.0, 1);
+        let biased = bounce_off_a_moving_paddle(1.0, 600.0, 1);
+
+        assert!(plain.y < 0.0 && biased.y < 0.0);
+        assert!(biased.x > plain.x, "{biased:?} vs {plain:?}");
+        approx_eq(biased.length(), plain.length());
+    }
+
+    #[test]
+    fn launch_bias_is_the_same_when_sub_stepping() {
+        let single = bounce_off_a_moving_paddle(1.0, MAX_BALL_SPEED, 1);
+        let sub_stepped = bounce_off_a_moving_paddle(1.0, MAX_BALL_SPEED, 2);
+
+        approx_eq(sub_stepped.x, single.x);
+        approx_eq(sub_stepped.y, single.y);
+    }
+
+    #[test]
+    fn paddle_tuning_updates_its_movement_components() {
+        let mut world = new_world();
+        let paddle = world.resource::<ArkanoidState>().paddle;
+
+        set_tuning_param(&mut world, ArkanoidTuningParam::PaddleSpeed as u32, 600.0);
+        set_tuning_param(&mut world, ArkanoidTuningParam::PaddleDrag as u32, 100.0);
+
+        assert_eq!(world.max_speed(paddle).value, 600.0);
+        assert_eq!(world.drag(paddle).rate, MAX_PADDLE_DRAG);
     }
 
     #[test]
@@ -409,7 +812,24 @@ mod tests {
 
         set_tuning_param(&mut world, ArkanoidTuningParam::BallRadius as u32, 12.0);
 
-        approx_eq(world.collider(ball).radius, 12.0);
+        approx_eq(world.wall_bounce_collider(ball).radius, 12.0);
+        assert_eq!(
+            world.collider(ball).shape,
+            ColliderShape::Circle { radius: 12.0 }
+        );
+    }
+
+    #[test]
+    fn paddle_size_update_resizes_contact_collider() {
+        let mut world = new_world();
+        let paddle = world.resource::<ArkanoidState>().paddle;
+
+        set_tuning_param(&mut world, ArkanoidTuningParam::PaddleWidth as u32, 150.0);
+
+        let half_height = world.resource::<ArkanoidTuning>().paddle_height / 2.0;
+        let collider = world.collider(paddle);
+        assert_eq!(collider.shape, Collider::aabb(75.0, half_height).shape);
+        assert_eq!(collider.layer, world.collision_layers.layer("paddle"));
     }
 
     #[test]
@@ -417,16 +837,16 @@ mod tests {
         let mut world = new_world();
         let ball = world.resource::<ArkanoidState>().ball;
         let velocity_before = *world.velocity(ball);
-        let before_speed = velocity_before.x.hypot(velocity_before.y);
-        let dir_x = velocity_before.x / before_speed;
-        let dir_y = velocity_before.y / before_speed;
+        let before_speed = velocity_before.x().hypot(velocity_before.y());
+        let dir_x = velocity_before.x() / before_speed;
+        let dir_y = velocity_before.y() / before_speed;
 
         set_tuning_param(&mut world, ArkanoidTuningParam::BallSpeed as u32, 900.0);
 
         let velocity_after = *world.velocity(ball);
-        approx_eq(velocity_after.x.hypot(velocity_after.y), 900.0);
-        approx_eq(velocity_after.x / 900.0, dir_x);
-        approx_eq(velocity_after.y / 900.0, dir_y);
+        approx_eq(velocity_after.x().hypot(velocity_after.y()), 900.0);
+        approx_eq(velocity_after.x() / 900.0, dir_x);
+        approx_eq(velocity_after.y() / 900.0, dir_y);
     }
 
     #[test]
@@ -505,4 +925,35 @@ mod tests {
             PADDLE_WIDTH,
         );
     }
+
+    #[test]
+    fn save_state_restores_tuning() {
+        use crate::engine::save::{decode, encode};
+        use crate::engine::GameId;
+
+        let (mut world, _schedule, _snapshot, _tuning_api, save_api) = build_world(800.0, 600.0);
+        set_tuning_param(&mut world, ArkanoidTuningParam::PaddleWidth as u32, 150.0);
+        set_tuning_param(
+            &mut world,
+            ArkanoidTuningParam::BounceZone3Angle as u32,
+            50.0,
+        );
+        world.resource_mut::<ArkanoidState>().lives = 1;
+        let bytes = encode(GameId(1), &world, &save_api);
+
+        reset_tuning_defaults(&mut world);
+        world.resource_mut::<ArkanoidState>().lives = STARTING_LIVES;
+        decode(GameId(1), &mut world, &save_api, &bytes).unwrap();
+
+        assert_eq!(world.resource::<ArkanoidState>().lives, 1);
+
+        approx_eq(
+            get_tuning_param(&world, ArkanoidTuningParam::PaddleWidth as u32).unwrap(),
+            150.0,
+        );
+        approx_eq(
+            get_tuning_param(&world, ArkanoidTuningParam::BounceZone3Angle as u32).unwrap(),
+            50.0,
+        );
+    }
 }
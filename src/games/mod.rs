@@ -1,18 +1,23 @@
+use crate::engine::ecs::resources::SimRng;
 use crate::engine::ecs::schedule::Schedule;
 use crate::engine::ecs::world::World;
-use crate::engine::{Snapshot, TuningApi};
+use crate::engine::save::SaveApi;
+use crate::engine::{GameId, Snapshot, TuningApi};
 
 mod arkanoid;
 mod pong;
 mod registry;
 
-type BuildFn = fn(f32, f32) -> (World, Schedule, Snapshot, TuningApi);
+type BuildFn = fn(f32, f32) -> (World, Schedule, Snapshot, TuningApi, SaveApi);
 
+/// Builds the named game. With a `seed` the world's RNG starts from it
+/// instead of entropy, so startup systems and every later draw repeat.
 pub fn build_game(
     game_name: &str,
     width: f32,
     height: f32,
-) -> (World, Schedule, Snapshot, TuningApi) {
+    seed: Option<u64>,
+) -> (GameId, World, Schedule, Snapshot, TuningApi, SaveApi) {
     let entry = registry::GAMES
         .iter()
         .find(|entry| entry.def.name == game_name)
@@ -20,5 +25,66 @@ pub fn build_game(
             panic!("game definition for \"{}\" not found", game_name);
         });
 
-    (entry.build)(width, height)
+    let (mut world, schedule, snapshot, tuning_api, save_api) = (entry.build)(width, height);
+    if let Some(seed) = seed {
+        world.rng = SimRng::new(seed);
+    }
+    (
+        entry.def.id,
+        world,
+        schedule,
+        snapshot,
+        tuning_api,
+        save_api,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Engine, INPUT_DOWN, INPUT_UP};
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn seeded_engine(game_name: &str, seed: u64) -> Engine {
+        let (game_id, world, schedule, snapshot, tuning_api, save_api) =
+            build_game(game_name, 800.0, 600.0, Some(seed));
+        Engine::new(game_id, world, schedule, snapshot, tuning_api, save_api)
+    }
+
+    fn input_for_frame(frame: u32) -> u32 {
+        match frame % 40 {
+            0..=14 => INPUT_UP,
+            20..=34 => INPUT_DOWN,
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn engines_with_the_same_seed_produce_the_same_checksums() {
+        for game in registry::GAMES {
+            let mut first = seeded_engine(game.def.name, 7);
+            let mut second = seeded_engine(game.def.name, 7);
+            assert_eq!(first.checksum(), second.checksum(), "{}", game.def.name);
+            for frame in 0..600 {
+                first.step(DT, input_for_frame(frame));
+                second.step(DT, input_for_frame(frame));
+                assert_eq!(
+                    first.checksum(),
+                    second.checksum(),
+                    "{} diverged at frame {frame}",
+                    game.def.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn different_seeds_serve_differently() {
+        let mut first = seeded_engine("Pong", 7);
+        let mut second = seeded_engine("Pong", 8);
+        first.step(DT, 0);
+        second.step(DT, 0);
+        assert_ne!(first.checksum(), second.checksum());
+    }
 }
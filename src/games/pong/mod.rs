@@ -1,12 +1,23 @@
 use rand::Rng;
 
-use crate::engine::ecs::components::{BounceCollider, Spin, Transform, Velocity};
-use crate::engine::{INPUT_ACTION, INPUT_DOWN, INPUT_UP};
+use crate::engine::ecs::components::{
+    Acceleration, BounceCollider, Collider, Drag, KinematicBody, MaxSpeed, SimVec2, Spin,
+    Transform, Velocity,
+};
+use crate::engine::ecs::layers::CollisionLayers;
+use crate::engine::ecs::resources::{EdgeBehavior, FieldEdge, FieldEdges, GHOST_SNAPSHOT_LEN};
+use crate::engine::{INPUT_ACTION, INPUT_DOWN, INPUT_PRECISION, INPUT_UP};
 pub mod resources;
 use crate::engine::ecs::schedule::{Schedule, SystemPhase};
+use crate::engine::ecs::state::State;
+use crate::engine::ecs::timer::Timer;
 use crate::engine::ecs::world::World;
-use crate::engine::{Snapshot, TuningApi};
-pub use resources::{PongPhase, PongPlayer, PongState};
+use crate::engine::math::{self, Vec2};
+use crate::engine::save::{SaveApi, SaveError, SaveReader, SaveWriter};
+use crate::engine::{
+    Snapshot, TuningApi, TUNING_STATUS_APPLIED, TUNING_STATUS_REJECTED, TUNING_STATUS_UNKNOWN_PARAM,
+};
+pub use resources::{PongPhase, PongPlayer, PongState, PongTuning};
 
 #[repr(usize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -28,7 +39,11 @@ enum SnapshotField {
     BallVisible = 14,
     PaddleWidth = 15,
     PaddleHeight = 16,
-    Count = 17,
+    PhaseElapsed = 17,
+    /// Where to draw the ball again when it hangs over a wrapping edge, in
+    /// the `FieldBounds::write_ghosts` layout.
+    BallGhosts = 18,
+    Count = 18 + GHOST_SNAPSHOT_LEN,
 }
 
 impl SnapshotField {
@@ -44,6 +59,10 @@ enum PongTuningParam {
     BallY = 1,
     BallVx = 2,
     BallVy = 3,
+    /// 1.0 when the top and bottom walls wrap instead of reflecting.
+    WrapWalls = 4,
+    PaddleAcceleration = 5,
+    PaddleDrag = 6,
 }
 
 impl TryFrom<u32> for PongTuningParam {
@@ -55,13 +74,31 @@ impl TryFrom<u32> for PongTuningParam {
             1 => Ok(Self::BallY),
             2 => Ok(Self::BallVx),
             3 => Ok(Self::BallVy),
+            4 => Ok(Self::WrapWalls),
+            5 => Ok(Self::PaddleAcceleration),
+            6 => Ok(Self::PaddleDrag),
             _ => Err(()),
         }
     }
 }
 
+/// Bookkeeping that must see every collision from this step.
+const SCORING_PHASE: SystemPhase = SystemPhase::Custom("Scoring");
+
 const PADDLE_SPEED: f32 = 300.0;
+/// Paddles speed up at this rate while a direction is held; `PADDLE_DRAG`
+/// bleeds speed off again, so a tap nudges the paddle a few pixels.
+const PADDLE_ACCELERATION: f32 = 6000.0;
+const PADDLE_DRAG: f32 = 10.0;
+const MIN_PADDLE_ACCELERATION: f32 = 500.0;
+const MAX_PADDLE_ACCELERATION: f32 = 20000.0;
+const MIN_PADDLE_DRAG: f32 = 0.0;
+const MAX_PADDLE_DRAG: f32 = 40.0;
+/// Share of the acceleration kept while the precision key is held.
+const PADDLE_PRECISION_SCALE: f32 = 0.35;
 const AI_DEAD_ZONE: f32 = 10.0;
+/// How often the AI re-reads the ball, so it reacts with a human-like lag.
+const AI_DECISION_HZ: f32 = 12.0;
 const WINNING_SCORE: u32 = 11;
 const SERVE_DELAY_MIN: f32 = 1.0;
 const SERVE_DELAY_MAX: f32 = 3.0;
@@ -75,10 +112,12 @@ const MAX_BOUNCE_ANGLE: f32 = std::f32::consts::FRAC_PI_3;
 const BALL_SPEED_ACCEL_FACTOR: f32 = 1.08;
 const BALL_MAX_SPEED: f32 = 900.0;
 const SPIN_TRANSFER_RATE: f32 = 0.1;
-const SPIN_DECAY_RATE: f32 = 0.90;
+/// Exponential decay rate of the ball's spin, per second: `-ln(0.9)`, so
+/// spin keeps 90% of itself each second.
+const SPIN_DECAY_RATE: f32 = 0.105_360_5;
 const SPIN_MAX: f32 = 400.0;
 
-fn reset_game(world: &mut World) {
+fn reset_game(world: &mut World, dt: f32) {
     let width = world.field.width;
     let height = world.field.height;
 
@@ -86,28 +125,28 @@ fn reset_game(world: &mut World) {
         let pong = world.resource_mut::<PongState>();
         pong.player_one_score = 0;
         pong.player_two_score = 0;
-        pong.phase = PongPhase::Playing;
         pong.winner = None;
         pong.conceded_by = None;
         pong.winning_score = WINNING_SCORE;
-        pong.serve_delay_remaining = 0.0;
+        pong.ai_target_y = height / 2.0;
     }
 
     let paddle_mid = height / 2.0;
     let paddles = world.resource::<PongState>().paddles;
     for &paddle in &paddles {
-        world.transform_mut(paddle).y = paddle_mid;
-        world.velocity_mut(paddle).y = 0.0;
+        world.transform_mut(paddle).position.set_y(paddle_mid);
+        world.velocity_mut(paddle).set_y(0.0);
+        world.acceleration_mut(paddle).y = 0.0;
+        world.kinematic_body_mut(paddle).teleported();
     }
 
     let ball = world.resource::<PongState>().ball;
-    world.transform_mut(ball).x = width / 2.0;
-    world.transform_mut(ball).y = height / 2.0;
-    world.velocity_mut(ball).x = 0.0;
-    world.velocity_mut(ball).y = 0.0;
+    world.transform_mut(ball).position = SimVec2::new(width / 2.0, height / 2.0);
+    *world.velocity_mut(ball) = Velocity::ZERO;
     world.spin_mut(ball).value = 0.0;
+    *world.timer_mut(ball) = Timer::once(0.0);
 
-    launch_ball(world);
+    launch_ball(world, dt);
 }
 
 fn center_ball(world: &mut World, side: f32) {
@@ -116,28 +155,23 @@ fn center_ball(world: &mut World, side: f32) {
     let pong = world.resource_mut::<PongState>();
     let ball = pong.ball;
 
-    world.transform_mut(ball).x = width / 2.0 + width * 0.1 * side;
-    world.transform_mut(ball).y = height / 2.0;
-    world.velocity_mut(ball).x = 0.0;
-    world.velocity_mut(ball).y = 0.0;
+    world.transform_mut(ball).position =
+        SimVec2::new(width / 2.0 + width * 0.1 * side, height / 2.0);
+    *world.velocity_mut(ball) = Velocity::ZERO;
     world.spin_mut(ball).value = 0.0;
 }
 
-fn launch_ball(world: &mut World) {
-    let mut rng = rand::thread_rng();
+fn launch_ball(world: &mut World, _dt: f32) {
     let half_cone = std::f32::consts::FRAC_PI_4;
-    let spread = rng.gen_range(-half_cone..half_cone);
-    let launch_side = {
-        let pong = world.resource_mut::<PongState>();
-        match pong.conceded_by.take() {
-            Some(PongPlayer::One) => 1.0,
-            Some(PongPlayer::Two) => -1.0,
-            None => {
-                if rng.gen_bool(0.5) {
-                    1.0
-                } else {
-                    -1.0
-                }
+    let spread = world.rng.gen_range(-half_cone..half_cone);
+    let launch_side = match world.resource_mut::<PongState>().conceded_by.take() {
+        Some(PongPlayer::One) => 1.0,
+        Some(PongPlayer::Two) => -1.0,
+        None => {
+            if world.rng.gen_bool(0.5) {
+                1.0
+            } else {
+                -1.0
             }
         }
     };
@@ -147,40 +181,35 @@ fn launch_ball(world: &mut World) {
         0.0
     };
     let angle = base_angle + spread;
-    let speed = rng.gen_range(350.0..500.0);
+    let speed = world.rng.gen_range(350.0..500.0);
 
     center_ball(world, launch_side);
 
     let ball = world.resource::<PongState>().ball;
-    let velocity = world.velocity_mut(ball);
-    velocity.x = angle.cos() * speed;
-    velocity.y = angle.sin() * speed;
-    world.resource_mut::<PongState>().serve_delay_remaining = 0.0;
+    let (sin, cos) = math::sin_cos(angle);
+    *world.velocity_mut(ball) = Velocity::new(cos * speed, sin * speed);
 }
 
-fn tick_serve_delay(world: &mut World, dt: f32) {
-    let should_launch = {
-        let pong = world.resource_mut::<PongState>();
-        if pong.serve_delay_remaining <= 0.0 {
-            return;
-        }
-        pong.serve_delay_remaining = (pong.serve_delay_remaining - dt).max(0.0);
-        pong.serve_delay_remaining <= 0.0
-    };
-    if should_launch {
-        launch_ball(world);
-    }
+fn serve_timer(world: &World) -> &Timer {
+    world.timer(world.resource::<PongState>().ball)
+}
+
+fn phase(world: &World) -> PongPhase {
+    world.resource::<State<PongPhase>>().current()
 }
 
 fn ball_visible(world: &World) -> bool {
-    world.resource::<PongState>().ball_visible()
+    phase(world) == PongPhase::Playing && serve_timer(world).finished()
+}
+
+fn serve_due(world: &World) -> bool {
+    phase(world) == PongPhase::Playing && serve_timer(world).just_finished()
 }
 
 fn compute_ai_input(world: &World) -> u32 {
     let pong = world.resource::<PongState>();
-    let ball_y = world.transform(pong.ball).y;
-    let paddle_y = world.transform(pong.paddles[1]).y;
-    let diff = ball_y - paddle_y;
+    let paddle_y = world.transform(pong.paddles[1]).position.y();
+    let diff = pong.ai_target_y - paddle_y;
 
     if diff.abs() < AI_DEAD_ZONE {
         0
@@ -191,136 +220,130 @@ fn compute_ai_input(world: &World) -> u32 {
     }
 }
 
-fn apply_input(world: &mut World, _dt: f32) {
-    let phase = world.resource::<PongState>().phase;
-    if phase == PongPhase::GameOver {
-        return;
-    }
+fn decide_ai(world: &mut World, _dt: f32) {
+    let ball_y = world
+        .transform(world.resource::<PongState>().ball)
+        .position
+        .y();
+    world.resource_mut::<PongState>().ai_target_y = ball_y;
+}
 
-    let p1_input = world.input.bits;
-    let p2_input = compute_ai_input(world);
-    let inputs = [p1_input, p2_input];
+fn paddle_acceleration_for(world: &World, input_bits: u32) -> f32 {
+    let up = (input_bits & INPUT_UP) != 0;
+    let down = (input_bits & INPUT_DOWN) != 0;
+    let dir = (down as i32) - (up as i32);
+    let scale = if (input_bits & INPUT_PRECISION) != 0 {
+        PADDLE_PRECISION_SCALE
+    } else {
+        1.0
+    };
+    dir as f32 * world.resource::<PongTuning>().paddle_acceleration * scale
+}
+
+/// Steers the player's paddle and lets the AI paddle coast, so the AI only
+/// moves while `steer_ai` runs after this.
+fn apply_input(world: &mut World, _dt: f32) {
+    let acceleration = paddle_acceleration_for(world, world.input.bits);
     let paddles = world.resource::<PongState>().paddles;
+    world.acceleration_mut(paddles[0]).y = acceleration;
+    world.acceleration_mut(paddles[1]).y = 0.0;
+}
 
-    for (idx, &input_bits) in inputs.iter().enumerate() {
-        let up = (input_bits & INPUT_UP) != 0;
-        let down = (input_bits & INPUT_DOWN) != 0;
-        let dir = (down as i32) - (up as i32);
-        let paddle = paddles[idx];
-        world.velocity_mut(paddle).y = dir as f32 * PADDLE_SPEED;
-    }
+fn steer_ai(world: &mut World, _dt: f32) {
+    let acceleration = paddle_acceleration_for(world, compute_ai_input(world));
+    let paddle = world.resource::<PongState>().paddles[1];
+    world.acceleration_mut(paddle).y = acceleration;
 }
 
 fn resolve_post_integration(world: &mut World, dt: f32) {
-    let phase = world.resource::<PongState>().phase;
-    if phase == PongPhase::GameOver {
-        return;
-    }
-
     if ball_visible(world) {
         let ball = world.resource::<PongState>().ball;
         let sv = world.spin(ball).value;
-        world.velocity_mut(ball).y += sv * dt;
-        world.spin_mut(ball).value *= SPIN_DECAY_RATE.powf(dt);
+        *world.velocity_mut(ball) += Velocity::new(0.0, sv * dt);
+        world.spin_mut(ball).value *= math::exp_neg(SPIN_DECAY_RATE * dt);
     }
+}
 
+/// Runs right after `integrate_velocity`, so the paddles' kinematic
+/// velocities, and the bounces that read them, see the clamped motion.
+fn clamp_paddles_to_field(world: &mut World, _dt: f32) {
     let paddle_half_height = PADDLE_HEIGHT / 2.0;
     let max_paddle_y = world.field.height - paddle_half_height;
     let paddles = world.resource::<PongState>().paddles;
     for &paddle in &paddles {
         let paddle_transform = world.transform_mut(paddle);
-        paddle_transform.y = paddle_transform.y.clamp(paddle_half_height, max_paddle_y);
+        let y = paddle_transform.position.y();
+        // Stop at the edge instead of pressing into it at full speed.
+        if !(paddle_half_height..=max_paddle_y).contains(&y) {
+            paddle_transform
+                .position
+                .set_y(y.clamp(paddle_half_height, max_paddle_y));
+            world.velocity_mut(paddle).set_y(0.0);
+        }
     }
 }
 
-fn check_paddle_collision(ball_x: f32, ball_y: f32, paddle_x: f32, paddle_y: f32) -> bool {
-    let paddle_half_h = PADDLE_HEIGHT / 2.0;
-    let paddle_half_w = PADDLE_WIDTH / 2.0;
-
-    let closest_x = ball_x.clamp(paddle_x - paddle_half_w, paddle_x + paddle_half_w);
-    let closest_y = ball_y.clamp(paddle_y - paddle_half_h, paddle_y + paddle_half_h);
-
-    let dist_x = ball_x - closest_x;
-    let dist_y = ball_y - closest_y;
-    let dist_squared = dist_x * dist_x + dist_y * dist_y;
-
-    dist_squared < (BALL_RADIUS * BALL_RADIUS)
-}
-
-fn collide_paddles(world: &mut World, _dt: f32) {
-    let phase = world.resource::<PongState>().phase;
-    if phase == PongPhase::GameOver || !ball_visible(world) {
+/// Bounces the ball off the first paddle it touched this step, from the exact
+/// contact point, and lets it travel the rest of the step after it. Reads the
+/// contacts found by `detect_contacts`.
+fn collide_paddles(world: &mut World, dt: f32) {
+    let pong = world.resource::<PongState>();
+    let (ball, paddles) = (pong.ball, pong.paddles);
+    let first_hit = world
+        .contacts
+        .iter()
+        .filter_map(|contact| {
+            let other = contact.other(ball)?;
+            let side = paddles.iter().position(|&paddle| paddle == other)?;
+            Some((*contact, side))
+        })
+        .min_by(|(a, _), (b, _)| a.time.total_cmp(&b.time));
+    let Some((contact, side)) = first_hit else {
         return;
-    }
+    };
 
-    let paddles = world.resource::<PongState>().paddles;
-    let ball = world.resource::<PongState>().ball;
-    let paddle_positions = [PADDLE1_X, PADDLE2_X];
+    // How the paddle actually moved, clamping included.
+    let paddle_y_vel = world.kinematic_body(paddles[side]).velocity.y;
+    let paddle_y_at_hit =
+        world.transform(paddles[side]).position.y() - paddle_y_vel * dt * (1.0 - contact.time);
+    let hit = contact.point() + contact.normal_toward(ball) * BALL_RADIUS;
 
-    for (i, &paddle_x) in paddle_positions.iter().enumerate() {
-        let paddle_entity = paddles[i];
-        let paddle_y = world.transform(paddle_entity).y;
+    let speed =
+        (world.velocity(ball).to_vec2().length() * BALL_SPEED_ACCEL_FACTOR).min(BALL_MAX_SPEED);
+    let offset = ((hit.y - paddle_y_at_hit) / (PADDLE_HEIGHT / 2.0)).clamp(-1.0, 1.0);
+    let angle = offset * MAX_BOUNCE_ANGLE;
+    let direction = if side == 0 { 1.0_f32 } else { -1.0 };
 
-        let ball_transform = world.transform(ball);
-        let ball_x = ball_transform.x;
-        let ball_y = ball_transform.y;
+    let (sin, cos) = math::sin_cos(angle);
+    let bounced = Vec2::new(direction * cos, sin) * speed;
+    *world.velocity_mut(ball) = Velocity::from_vec2(bounced);
 
-        if !check_paddle_collision(ball_x, ball_y, paddle_x, paddle_y) {
-            continue;
-        }
+    let spin_transfer = -paddle_y_vel * SPIN_TRANSFER_RATE;
+    let ball_spin = world.spin_mut(ball);
+    ball_spin.value = (ball_spin.value + spin_transfer).clamp(-SPIN_MAX, SPIN_MAX);
 
-        let ball_velocity = world.velocity(ball);
-        let speed =
-            (ball_velocity.x.hypot(ball_velocity.y) * BALL_SPEED_ACCEL_FACTOR).min(BALL_MAX_SPEED);
-        let paddle_half_h = PADDLE_HEIGHT / 2.0;
-        let offset = ((ball_y - paddle_y) / paddle_half_h).clamp(-1.0, 1.0);
-        let angle = offset * MAX_BOUNCE_ANGLE;
-        let direction = if i == 0 { 1.0_f32 } else { -1.0 };
-
-        let ball_vel_mut = world.velocity_mut(ball);
-        ball_vel_mut.x = direction * speed * angle.cos();
-        ball_vel_mut.y = speed * angle.sin();
-
-        let paddle_y_vel = world.velocity(paddle_entity).y;
-        let spin_transfer = -paddle_y_vel * SPIN_TRANSFER_RATE;
-        let ball_spin = world.spin_mut(ball);
-        ball_spin.value = (ball_spin.value + spin_transfer).clamp(-SPIN_MAX, SPIN_MAX);
-
-        let paddle_top = paddle_y - paddle_half_h;
-        let paddle_bottom = paddle_y + paddle_half_h;
-        let is_face_hit = ball_y >= paddle_top && ball_y <= paddle_bottom;
-
-        let ball_transform = world.transform_mut(ball);
-        if is_face_hit {
-            ball_transform.x = paddle_x + direction * (PADDLE_WIDTH / 2.0 + BALL_RADIUS);
-        } else if ball_y < paddle_top {
-            ball_transform.y = paddle_top - BALL_RADIUS;
-        } else {
-            ball_transform.y = paddle_bottom + BALL_RADIUS;
-        }
-    }
+    let remaining = (1.0 - contact.time) * dt;
+    world.transform_mut(ball).position = SimVec2::from_vec2(hit + bounced * remaining);
 }
 
+/// The side walls are goals: `bounce_in_field` reports the ball leaving.
 fn collide_walls(world: &mut World, _dt: f32) {
-    if world.resource::<PongState>().phase == PongPhase::GameOver || !ball_visible(world) {
-        return;
-    }
-
     let ball = world.resource::<PongState>().ball;
-    let ball_transform = world.transform(ball);
-    let ball_x = ball_transform.x;
-
-    if ball_x <= BALL_RADIUS {
-        world.resource_mut::<PongState>().conceded_by = Some(PongPlayer::One);
-    } else if ball_x >= world.field.width - BALL_RADIUS {
-        world.resource_mut::<PongState>().conceded_by = Some(PongPlayer::Two);
+    let conceded_by = world
+        .field_exits
+        .iter()
+        .filter(|exit| exit.entity == ball)
+        .find_map(|exit| match exit.edge {
+            FieldEdge::Left => Some(PongPlayer::One),
+            FieldEdge::Right => Some(PongPlayer::Two),
+            FieldEdge::Top | FieldEdge::Bottom => None,
+        });
+    if conceded_by.is_some() {
+        world.resource_mut::<PongState>().conceded_by = conceded_by;
     }
 }
 
 fn resolve_scoring(world: &mut World, _dt: f32) {
-    if !ball_visible(world) {
-        return;
-    }
     let conceder = match world.resource::<PongState>().conceded_by {
         Some(player) => player,
         None => return,
@@ -331,6 +354,7 @@ fn resolve_scoring(world: &mut World, _dt: f32) {
         PongPlayer::Two => PongPlayer::One,
     };
 
+    let serve_delay = world.rng.gen_range(SERVE_DELAY_MIN..SERVE_DELAY_MAX);
     let pong = world.resource_mut::<PongState>();
     match scorer {
         PongPlayer::One => pong.player_one_score += 1,
@@ -342,126 +366,338 @@ fn resolve_scoring(world: &mut World, _dt: f32) {
         PongPlayer::Two => pong.player_two_score,
     };
 
+    let ball = pong.ball;
     if score >= pong.winning_score {
-        pong.phase = PongPhase::GameOver;
         pong.winner = Some(scorer);
-        pong.serve_delay_remaining = 0.0;
+        world
+            .resource_mut::<State<PongPhase>>()
+            .queue(PongPhase::GameOver);
     } else {
-        let mut rng = rand::thread_rng();
-        pong.serve_delay_remaining = rng.gen_range(SERVE_DELAY_MIN..SERVE_DELAY_MAX);
+        *world.timer_mut(ball) = Timer::once(serve_delay);
     }
 
     center_ball(world, 0.0);
 }
 
-fn handle_restart(world: &mut World, _dt: f32) {
-    if world.resource::<PongState>().phase != PongPhase::GameOver {
-        return;
+/// One-shot: restart the match from the tuning panel or tests.
+fn restart(world: &mut World, dt: f32) {
+    if phase(world) == PongPhase::Playing {
+        reset_game(world, dt);
+    } else {
+        world
+            .resource_mut::<State<PongPhase>>()
+            .queue(PongPhase::Playing);
     }
+}
+
+fn handle_restart(world: &mut World, _dt: f32) {
     if (world.input.bits & INPUT_ACTION) != 0 {
-        reset_game(world);
+        world
+            .resource_mut::<State<PongPhase>>()
+            .queue(PongPhase::Playing);
     }
 }
 
-fn tick_serve(world: &mut World, dt: f32) {
-    let phase = world.resource::<PongState>().phase;
-    if phase != PongPhase::Playing {
-        return;
+/// Switches the top and bottom walls between reflecting and wrapping.
+fn set_wrap_walls(world: &mut World, wrap: bool) {
+    let behavior = if wrap {
+        EdgeBehavior::Wrap
+    } else {
+        EdgeBehavior::Reflect
+    };
+    world.field.edges = world
+        .field
+        .edges
+        .with(FieldEdge::Top, behavior)
+        .with(FieldEdge::Bottom, behavior);
+}
+
+impl Default for PongTuning {
+    fn default() -> Self {
+        Self {
+            paddle_acceleration: PADDLE_ACCELERATION,
+            paddle_drag: PADDLE_DRAG,
+        }
     }
-    if !ball_visible(world) {
-        tick_serve_delay(world, dt);
+}
+
+fn apply_tuning(world: &mut World) {
+    let paddle_drag = world.resource::<PongTuning>().paddle_drag;
+    let paddles = world.resource::<PongState>().paddles;
+    for paddle in paddles {
+        world.drag_mut(paddle).rate = paddle_drag;
     }
 }
 
+fn set_tuning_param(world: &mut World, param_id: u32, value: f32) -> u32 {
+    if !value.is_finite() {
+        return TUNING_STATUS_REJECTED;
+    }
+
+    let Ok(param) = PongTuningParam::try_from(param_id) else {
+        return TUNING_STATUS_UNKNOWN_PARAM;
+    };
+
+    let tuning = world.resource_mut::<PongTuning>();
+    match param {
+        PongTuningParam::WrapWalls => set_wrap_walls(world, value >= 0.5),
+        PongTuningParam::PaddleAcceleration => {
+            tuning.paddle_acceleration =
+                value.clamp(MIN_PADDLE_ACCELERATION, MAX_PADDLE_ACCELERATION);
+        }
+        PongTuningParam::PaddleDrag => {
+            tuning.paddle_drag = value.clamp(MIN_PADDLE_DRAG, MAX_PADDLE_DRAG);
+        }
+        PongTuningParam::BallX
+        | PongTuningParam::BallY
+        | PongTuningParam::BallVx
+        | PongTuningParam::BallVy => return TUNING_STATUS_REJECTED,
+    }
+
+    apply_tuning(world);
+
+    TUNING_STATUS_APPLIED
+}
+
+fn reset_tuning_defaults(world: &mut World) {
+    *world.resource_mut::<PongTuning>() = PongTuning::default();
+    apply_tuning(world);
+    set_wrap_walls(world, false);
+}
+
 fn get_tuning_param(world: &World, param_id: u32) -> Option<f32> {
     let param = PongTuningParam::try_from(param_id).ok()?;
     let pong = world.resource::<PongState>();
     let ball = pong.ball;
     let ball_transform = world.transform(ball);
     let ball_velocity = world.velocity(ball);
+    let tuning = world.resource::<PongTuning>();
     let value = match param {
-        PongTuningParam::BallX => ball_transform.x,
-        PongTuningParam::BallY => ball_transform.y,
-        PongTuningParam::BallVx => ball_velocity.x,
-        PongTuningParam::BallVy => ball_velocity.y,
+        PongTuningParam::BallX => ball_transform.position.x(),
+        PongTuningParam::BallY => ball_transform.position.y(),
+        PongTuningParam::BallVx => ball_velocity.x(),
+        PongTuningParam::BallVy => ball_velocity.y(),
+        PongTuningParam::WrapWalls => {
+            if world.field.wraps_y() {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        PongTuningParam::PaddleAcceleration => tuning.paddle_acceleration,
+        PongTuningParam::PaddleDrag => tuning.paddle_drag,
     };
     Some(value)
 }
 
-pub fn build_world(width: f32, height: f32) -> (World, Schedule, Snapshot, TuningApi) {
+fn write_player(writer: &mut SaveWriter, player: Option<PongPlayer>) {
+    writer.write_u8(match player {
+        None => 0,
+        Some(PongPlayer::One) => 1,
+        Some(PongPlayer::Two) => 2,
+    });
+}
+
+fn read_player(reader: &mut SaveReader) -> Result<Option<PongPlayer>, SaveError> {
+    match reader.read_u8()? {
+        0 => Ok(None),
+        1 => Ok(Some(PongPlayer::One)),
+        2 => Ok(Some(PongPlayer::Two)),
+        _ => Err(SaveError::Invalid("unknown pong player")),
+    }
+}
+
+fn write_phase(writer: &mut SaveWriter, phase: Option<PongPhase>) {
+    writer.write_u8(match phase {
+        None => 0,
+        Some(PongPhase::Playing) => 1,
+        Some(PongPhase::GameOver) => 2,
+    });
+}
+
+fn read_phase(reader: &mut SaveReader) -> Result<Option<PongPhase>, SaveError> {
+    match reader.read_u8()? {
+        0 => Ok(None),
+        1 => Ok(Some(PongPhase::Playing)),
+        2 => Ok(Some(PongPhase::GameOver)),
+        _ => Err(SaveError::Invalid("unknown pong phase")),
+    }
+}
+
+fn write_save(world: &World, writer: &mut SaveWriter) {
+    let pong = world.resource::<PongState>();
+    writer.write_entity(pong.ball);
+    writer.write_entity(pong.paddles[0]);
+    writer.write_entity(pong.paddles[1]);
+    writer.write_u32(pong.player_one_score);
+    writer.write_u32(pong.player_two_score);
+    write_player(writer, pong.winner);
+    write_player(writer, pong.conceded_by);
+    writer.write_u32(pong.winning_score);
+    writer.write_f32(pong.ai_target_y);
+
+    let phase = world.resource::<State<PongPhase>>();
+    write_phase(writer, Some(phase.current()));
+    write_phase(writer, phase.queued());
+    writer.write_f32(phase.elapsed());
+
+    let tuning = world.resource::<PongTuning>();
+    writer.write_f32(tuning.paddle_acceleration);
+    writer.write_f32(tuning.paddle_drag);
+}
+
+fn read_save(world: &mut World, reader: &mut SaveReader) -> Result<(), SaveError> {
+    let state = PongState {
+        ball: reader.read_entity()?,
+        paddles: [reader.read_entity()?, reader.read_entity()?],
+        player_one_score: reader.read_u32()?,
+        player_two_score: reader.read_u32()?,
+        winner: read_player(reader)?,
+        conceded_by: read_player(reader)?,
+        winning_score: reader.read_u32()?,
+        ai_target_y: reader.read_f32()?,
+    };
+    let current = read_phase(reader)?.ok_or(SaveError::Invalid("missing pong phase"))?;
+    let phase = State::restore(current, read_phase(reader)?, reader.read_f32()?);
+    let tuning = PongTuning {
+        paddle_acceleration: reader.read_f32()?,
+        paddle_drag: reader.read_f32()?,
+    };
+    reader.finish()?;
+
+    *world.resource_mut::<PongState>() = state;
+    *world.resource_mut::<State<PongPhase>>() = phase;
+    *world.resource_mut::<PongTuning>() = tuning;
+    Ok(())
+}
+
+pub fn build_world(width: f32, height: f32) -> (World, Schedule, Snapshot, TuningApi, SaveApi) {
     let mut world = World::new(width, height);
+    let tuning = PongTuning::default();
+    world.field.edges = FieldEdges::default()
+        .with(FieldEdge::Left, EdgeBehavior::Exit)
+        .with(FieldEdge::Right, EdgeBehavior::Exit);
+    world.collision_layers = CollisionLayers::new()
+        .with_layer("ball")
+        .with_layer("paddle");
+    let ball_layer = world.collision_layers.layer("ball");
+    let paddle_layer = world.collision_layers.layer("paddle");
+    let paddle_collider = Collider::aabb(PADDLE_WIDTH / 2.0, PADDLE_HEIGHT / 2.0)
+        .on_layer(paddle_layer)
+        .with_mask(ball_layer);
 
     let ball = world.spawn();
-    world.set_transform(
-        ball,
-        Transform {
-            x: width / 2.0,
-            y: height / 2.0,
-        },
-    );
-    world.set_velocity(ball, Velocity { x: 0.0, y: 0.0 });
+    world.set_transform(ball, Transform::at(width / 2.0, height / 2.0));
+    world.set_velocity(ball, Velocity::ZERO);
     world.set_spin(ball, Spin { value: 0.0 });
+    // Serve countdown; starts finished so the opening serve is immediate.
+    world.set_timer(ball, Timer::once(0.0));
     world.set_wall_bounce_collider(
         ball,
         BounceCollider {
             radius: BALL_RADIUS,
         },
     );
+    world.set_collider(
+        ball,
+        Collider::circle(BALL_RADIUS)
+            .on_layer(ball_layer)
+            .with_mask(paddle_layer),
+    );
 
     let paddle1 = world.spawn();
-    world.set_transform(
+    world.set_transform(paddle1, Transform::at(PADDLE1_X, height / 2.0));
+    world.set_velocity(paddle1, Velocity::ZERO);
+    world.set_acceleration(paddle1, Acceleration { x: 0.0, y: 0.0 });
+    world.set_drag(
         paddle1,
-        Transform {
-            x: PADDLE1_X,
-            y: height / 2.0,
+        Drag {
+            rate: tuning.paddle_drag,
         },
     );
-    world.set_velocity(paddle1, Velocity { x: 0.0, y: 0.0 });
+    world.set_max_speed(
+        paddle1,
+        MaxSpeed {
+            value: PADDLE_SPEED,
+        },
+    );
+    world.set_kinematic_body(paddle1, KinematicBody::default());
+    world.set_collider(paddle1, paddle_collider);
 
     let paddle2 = world.spawn();
-    world.set_transform(
+    world.set_transform(paddle2, Transform::at(PADDLE2_X, height / 2.0));
+    world.set_velocity(paddle2, Velocity::ZERO);
+    world.set_acceleration(paddle2, Acceleration { x: 0.0, y: 0.0 });
+    world.set_drag(
+        paddle2,
+        Drag {
+            rate: tuning.paddle_drag,
+        },
+    );
+    world.set_max_speed(
         paddle2,
-        Transform {
-            x: PADDLE2_X,
-            y: height / 2.0,
+        MaxSpeed {
+            value: PADDLE_SPEED,
         },
     );
-    world.set_velocity(paddle2, Velocity { x: 0.0, y: 0.0 });
+    world.set_kinematic_body(paddle2, KinematicBody::default());
+    world.set_collider(paddle2, paddle_collider);
 
     world.insert_resource(PongState {
         ball,
         paddles: [paddle1, paddle2],
         player_one_score: 0,
         player_two_score: 0,
-        phase: PongPhase::Playing,
         winner: None,
         conceded_by: None,
         winning_score: WINNING_SCORE,
-        serve_delay_remaining: 0.0,
+        ai_target_y: height / 2.0,
     });
-
-    launch_ball(&mut world);
+    world.insert_resource(tuning);
+    world.insert_resource(State::new(PongPhase::Playing));
 
     let schedule = Schedule::new()
-        .with_system_in_phase(SystemPhase::Control, handle_restart)
-        .with_system_in_phase(SystemPhase::Control, apply_input)
-        .with_system_in_phase(SystemPhase::Resolve, resolve_post_integration)
+        .with_phase_after(SystemPhase::Resolve, SCORING_PHASE)
+        .with_startup_system(launch_ball)
+        .with_one_shot("restart", restart)
+        .on_update(SystemPhase::Control, PongPhase::GameOver, handle_restart)
+        .on_update(SystemPhase::Control, PongPhase::Playing, decide_ai)
+        .at_rate(AI_DECISION_HZ)
+        .on_update(SystemPhase::Control, PongPhase::Playing, apply_input)
+        .on_update(SystemPhase::Control, PongPhase::Playing, steer_ai)
+        .after("apply_input")
+        .on_update(
+            SystemPhase::Physics,
+            PongPhase::Playing,
+            clamp_paddles_to_field,
+        )
+        .after("integrate_velocity")
+        .before("update_spatial_hash")
+        .on_update(
+            SystemPhase::Resolve,
+            PongPhase::Playing,
+            resolve_post_integration,
+        )
         .with_system_in_phase(SystemPhase::Resolve, collide_walls)
-        .with_system_in_phase(SystemPhase::Resolve, collide_paddles)
-        .with_system_in_phase(SystemPhase::Resolve, resolve_scoring)
-        .with_system_in_phase(SystemPhase::Resolve, tick_serve);
-
-    fn set_tuning_param(_world: &mut World, _param_id: u32, _value: f32) -> u32 {
-        crate::engine::TUNING_STATUS_REJECTED
-    }
-
-    fn reset_tuning_defaults(_world: &mut World) {}
+        .run_if(ball_visible)
+        .with_system_in_phase(SystemPhase::Physics, collide_paddles)
+        .run_if(ball_visible)
+        .after("detect_contacts")
+        .before("bounce_in_field")
+        // Serving clears the concession that started the countdown, so it
+        // must happen before scoring looks at it again.
+        .with_system_in_phase(SCORING_PHASE, launch_ball)
+        .run_if(serve_due)
+        .with_system_in_phase(SCORING_PHASE, resolve_scoring)
+        .run_if(ball_visible)
+        .on_enter(PongPhase::Playing, reset_game);
 
     (
         world,
         schedule,
         Snapshot::new(write_snapshot, vec![0.0; SnapshotField::Count as usize]),
         TuningApi::new(set_tuning_param, get_tuning_param, reset_tuning_defaults),
+        SaveApi::new(write_save, read_save),
     )
 }
 
@@ -469,25 +705,26 @@ fn write_snapshot(world: &World, snapshot: &mut [f32]) {
     use SnapshotField::*;
 
     let pong = world.resource::<PongState>();
+    let phase = world.resource::<State<PongPhase>>();
     let ball = pong.ball;
-    let ball_visible = pong.ball_visible();
+    let ball_visible = ball_visible(world);
 
     let ball_transform = world.transform(ball);
     let ball_velocity = world.velocity(ball);
 
-    snapshot[BallX.idx()] = ball_transform.x;
-    snapshot[BallY.idx()] = ball_transform.y;
-    snapshot[BallVx.idx()] = ball_velocity.x;
-    snapshot[BallVy.idx()] = ball_velocity.y;
+    snapshot[BallX.idx()] = ball_transform.position.x();
+    snapshot[BallY.idx()] = ball_transform.position.y();
+    snapshot[BallVx.idx()] = ball_velocity.x();
+    snapshot[BallVy.idx()] = ball_velocity.y();
     snapshot[Paddle1X.idx()] = PADDLE1_X;
-    snapshot[Paddle1Y.idx()] = world.transform(pong.paddles[0]).y;
+    snapshot[Paddle1Y.idx()] = world.transform(pong.paddles[0]).position.y();
     snapshot[Paddle2X.idx()] = PADDLE2_X;
-    snapshot[Paddle2Y.idx()] = world.transform(pong.paddles[1]).y;
+    snapshot[Paddle2Y.idx()] = world.transform(pong.paddles[1]).position.y();
     snapshot[PlayerOneScore.idx()] = pong.player_one_score as f32;
     snapshot[PlayerTwoScore.idx()] = pong.player_two_score as f32;
     snapshot[FieldWidth.idx()] = world.field.width;
     snapshot[FieldHeight.idx()] = world.field.height;
-    snapshot[GamePhase.idx()] = match pong.phase {
+    snapshot[GamePhase.idx()] = match phase.current() {
         PongPhase::Playing => 0.0,
         PongPhase::GameOver => 1.0,
     };
@@ -499,6 +736,16 @@ fn write_snapshot(world: &World, snapshot: &mut [f32]) {
     snapshot[BallVisible.idx()] = if ball_visible { 1.0 } else { 0.0 };
     snapshot[PaddleWidth.idx()] = PADDLE_WIDTH;
     snapshot[PaddleHeight.idx()] = PADDLE_HEIGHT;
+    snapshot[PhaseElapsed.idx()] = phase.elapsed();
+    let ghosts = &mut snapshot[BallGhosts.idx()..];
+    if ball_visible {
+        let position = ball_transform.position;
+        world
+            .field
+            .write_ghosts(position.x(), position.y(), BALL_RADIUS, BALL_RADIUS, ghosts);
+    } else {
+        ghosts[..GHOST_SNAPSHOT_LEN].fill(0.0);
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
 use crate::engine::ecs::entity::EntityId;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PongPhase {
     Playing,
     GameOver,
@@ -18,15 +18,17 @@ pub struct PongState {
     pub paddles: [EntityId; 2],
     pub player_one_score: u32,
     pub player_two_score: u32,
-    pub phase: PongPhase,
     pub winner: Option<PongPlayer>,
     pub conceded_by: Option<PongPlayer>,
     pub winning_score: u32,
-    pub serve_delay_remaining: f32,
+    /// Ball height the AI paddle is heading for; only refreshed at
+    /// `AI_DECISION_HZ`.
+    pub ai_target_y: f32,
 }
 
-impl PongState {
-    pub fn ball_visible(&self) -> bool {
-        self.phase == PongPhase::Playing && self.serve_delay_remaining <= 0.0
-    }
+/// Paddle handling the tuning panel can change. Both paddles share it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PongTuning {
+    pub paddle_acceleration: f32,
+    pub paddle_drag: f32,
 }
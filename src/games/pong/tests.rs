@@ -1,20 +1,21 @@
 use super::*;
-use crate::engine::ecs::schedule::SystemPhase;
-use crate::engine::ecs::systems::{bounce_in_field, integrate_velocity};
+use crate::engine::ecs::systems::physics_substeps;
+use crate::engine::{with_engine_systems, MAX_DT};
 
 const DT: f32 = 1.0 / 60.0;
 const FIELD_WIDTH: f32 = 800.0;
 const FIELD_HEIGHT: f32 = 600.0;
 
 fn new_game() -> (World, Schedule) {
-    let (world, schedule, _snapshot, _tuning_api) = build_world(FIELD_WIDTH, FIELD_HEIGHT);
-    let schedule = schedule
-        .with_system_in_phase(SystemPhase::Physics, integrate_velocity)
-        .with_system_in_phase(SystemPhase::Physics, bounce_in_field);
+    let (world, schedule, _snapshot, _tuning_api, _save_api) =
+        build_world(FIELD_WIDTH, FIELD_HEIGHT);
+    let mut schedule = with_engine_systems(schedule).build().unwrap();
+    let mut world = world;
+    schedule.run_startup(&mut world);
     (world, schedule)
 }
 
-fn step(world: &mut World, schedule: &Schedule, dt: f32, input_bits: u32) {
+fn step(world: &mut World, schedule: &mut Schedule, dt: f32, input_bits: u32) {
     world.input.bits = input_bits;
     schedule.run(world, dt);
 }
@@ -35,43 +36,132 @@ fn pong_mut(world: &mut World) -> &mut PongState {
     world.resource_mut::<PongState>()
 }
 
+fn current_phase(world: &World) -> PongPhase {
+    world.resource::<State<PongPhase>>().current()
+}
+
+fn set_phase(world: &mut World, phase: PongPhase) {
+    world.insert_resource(State::new(phase));
+}
+
 mod paddle_collisions {
     use super::*;
 
     #[test]
     fn bounces_off_left_paddle() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = -200.0;
-        world.velocity_mut(ball).y = 0.0;
+        world
+            .transform_mut(ball)
+            .position
+            .set_x(PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(-200.0);
+        world.velocity_mut(ball).set_y(0.0);
         let paddle = paddle_entity(&world, 0);
-        world.transform_mut(paddle).y = FIELD_HEIGHT / 2.0;
+        world
+            .transform_mut(paddle)
+            .position
+            .set_y(FIELD_HEIGHT / 2.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
-        let vx = world.velocity(ball).x;
+        let vx = world.velocity(ball).x();
         assert!(
             vx > 0.0,
             "ball should bounce right after hitting left paddle"
         );
     }
 
+    #[test]
+    fn fast_ball_cannot_tunnel_through_paddle_on_long_frames() {
+        let (mut world, mut schedule) = new_game();
+        let ball = ball_entity(&world);
+        let paddle = paddle_entity(&world, 1);
+        world.transform_mut(ball).position.set_x(700.0);
+        let paddle_y = world.transform(paddle).position.y();
+        world.transform_mut(ball).position.set_y(paddle_y);
+        *world.velocity_mut(ball) = Velocity::new(BALL_MAX_SPEED, 0.0);
+
+        // 45 px per frame would carry the ball across the 10 px paddle
+        // between two frames without sub-stepping.
+        step(&mut world, &mut schedule, crate::engine::MAX_DT, 0);
+        step(&mut world, &mut schedule, crate::engine::MAX_DT, 0);
+
+        assert!(
+            world.velocity(ball).x() < 0.0,
+            "ball tunneled through paddle"
+        );
+        assert!(world.transform(ball).position.x() < PADDLE2_X);
+    }
+
+    #[test]
+    fn hit_resolves_at_contact_point_and_keeps_remaining_motion() {
+        let (mut world, mut schedule) = new_game();
+        world.substeps.max_substeps = 1;
+        let ball = ball_entity(&world);
+        let paddle = paddle_entity(&world, 1);
+        let dt = 0.05;
+        world.transform_mut(ball).position.set_x(740.0);
+        let paddle_y = world.transform(paddle).position.y();
+        world.transform_mut(ball).position.set_y(paddle_y);
+        *world.velocity_mut(ball) = Velocity::new(600.0, 0.0);
+
+        step(&mut world, &mut schedule, dt, 0);
+
+        // Contact when the ball's edge reaches the paddle face at 765.
+        let contact_x = PADDLE2_X - PADDLE_WIDTH / 2.0 - BALL_RADIUS;
+        let time_of_impact = (contact_x - 740.0) / (600.0 * dt);
+        let bounced_speed = 600.0 * BALL_SPEED_ACCEL_FACTOR;
+        let expected_x = contact_x - bounced_speed * (1.0 - time_of_impact) * dt;
+        assert!(world.velocity(ball).x() < 0.0);
+        assert!(
+            (world.transform(ball).position.x() - expected_x).abs() < 0.01,
+            "expected x {} but got {}",
+            expected_x,
+            world.transform(ball).position.x()
+        );
+    }
+
+    #[test]
+    fn sweep_alone_stops_tunneling_without_sub_steps() {
+        let (mut world, mut schedule) = new_game();
+        world.substeps.max_substeps = 1;
+        let ball = ball_entity(&world);
+        let paddle = paddle_entity(&world, 1);
+        world.transform_mut(ball).position.set_x(745.0);
+        let paddle_y = world.transform(paddle).position.y();
+        world.transform_mut(ball).position.set_y(paddle_y);
+        *world.velocity_mut(ball) = Velocity::new(BALL_MAX_SPEED, 0.0);
+
+        step(&mut world, &mut schedule, crate::engine::MAX_DT, 0);
+
+        assert!(
+            world.velocity(ball).x() < 0.0,
+            "ball tunneled through paddle"
+        );
+    }
+
     #[test]
     fn bounces_off_right_paddle() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = PADDLE2_X - PADDLE_WIDTH / 2.0 - BALL_RADIUS - 2.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = 200.0;
-        world.velocity_mut(ball).y = 0.0;
+        world
+            .transform_mut(ball)
+            .position
+            .set_x(PADDLE2_X - PADDLE_WIDTH / 2.0 - BALL_RADIUS - 2.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(200.0);
+        world.velocity_mut(ball).set_y(0.0);
         let paddle = paddle_entity(&world, 1);
-        world.transform_mut(paddle).y = FIELD_HEIGHT / 2.0;
+        world
+            .transform_mut(paddle)
+            .position
+            .set_y(FIELD_HEIGHT / 2.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
-        let vx = world.velocity(ball).x;
+        let vx = world.velocity(ball).x();
         assert!(
             vx < 0.0,
             "ball should bounce left after hitting right paddle"
@@ -80,54 +170,63 @@ mod paddle_collisions {
 
     #[test]
     fn center_hit_produces_horizontal_bounce() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let paddle = paddle_entity(&world, 0);
-        world.transform_mut(paddle).y = FIELD_HEIGHT / 2.0;
+        world
+            .transform_mut(paddle)
+            .position
+            .set_y(FIELD_HEIGHT / 2.0);
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = -300.0;
-        world.velocity_mut(ball).y = 0.0;
+        world
+            .transform_mut(ball)
+            .position
+            .set_x(PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(-300.0);
+        world.velocity_mut(ball).set_y(0.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         let velocity = world.velocity(ball);
-        assert!(velocity.x > 0.0, "ball should bounce right");
+        assert!(velocity.x() > 0.0, "ball should bounce right");
         assert!(
-            velocity.y.abs() < 0.01,
+            velocity.y().abs() < 0.01,
             "center hit should produce near-zero vy, got {}",
-            velocity.y
+            velocity.y()
         );
     }
 
     #[test]
     fn edge_hit_top_bounces_upward_at_max_angle() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let paddle_y = FIELD_HEIGHT / 2.0;
         let paddle_top = paddle_y - PADDLE_HEIGHT / 2.0;
         let paddle = paddle_entity(&world, 0);
-        world.transform_mut(paddle).y = paddle_y;
+        world.transform_mut(paddle).position.set_y(paddle_y);
 
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = PADDLE1_X;
-        world.transform_mut(ball).y = paddle_top - BALL_RADIUS - 1.0;
-        world.velocity_mut(ball).x = 0.0;
-        world.velocity_mut(ball).y = 200.0;
+        world.transform_mut(ball).position.set_x(PADDLE1_X);
+        world
+            .transform_mut(ball)
+            .position
+            .set_y(paddle_top - BALL_RADIUS - 1.0);
+        world.velocity_mut(ball).set_x(0.0);
+        world.velocity_mut(ball).set_y(200.0);
         let speed_before = 200.0_f32;
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         let velocity = world.velocity(ball);
-        let speed_after = (velocity.x.powi(2) + velocity.y.powi(2)).sqrt();
+        let speed_after = (velocity.x().powi(2) + velocity.y().powi(2)).sqrt();
         assert!(
-            velocity.y < 0.0,
+            velocity.y() < 0.0,
             "ball should bounce upward, got vy={}",
-            velocity.y
+            velocity.y()
         );
         assert!(
-            velocity.x > 0.0,
+            velocity.x() > 0.0,
             "ball should bounce right (away from left paddle), got vx={}",
-            velocity.x
+            velocity.x()
         );
         assert!(
             speed_after >= speed_before,
@@ -139,32 +238,35 @@ mod paddle_collisions {
 
     #[test]
     fn edge_hit_bottom_bounces_downward_at_max_angle() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let paddle_y = FIELD_HEIGHT / 2.0;
         let paddle_bottom = paddle_y + PADDLE_HEIGHT / 2.0;
         let paddle = paddle_entity(&world, 0);
-        world.transform_mut(paddle).y = paddle_y;
+        world.transform_mut(paddle).position.set_y(paddle_y);
 
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = PADDLE1_X;
-        world.transform_mut(ball).y = paddle_bottom + BALL_RADIUS + 1.0;
-        world.velocity_mut(ball).x = 0.0;
-        world.velocity_mut(ball).y = -200.0;
+        world.transform_mut(ball).position.set_x(PADDLE1_X);
+        world
+            .transform_mut(ball)
+            .position
+            .set_y(paddle_bottom + BALL_RADIUS + 1.0);
+        world.velocity_mut(ball).set_x(0.0);
+        world.velocity_mut(ball).set_y(-200.0);
         let speed_before = 200.0_f32;
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         let velocity = world.velocity(ball);
-        let speed_after = (velocity.x.powi(2) + velocity.y.powi(2)).sqrt();
+        let speed_after = (velocity.x().powi(2) + velocity.y().powi(2)).sqrt();
         assert!(
-            velocity.y > 0.0,
+            velocity.y() > 0.0,
             "ball should bounce downward, got vy={}",
-            velocity.y
+            velocity.y()
         );
         assert!(
-            velocity.x > 0.0,
+            velocity.x() > 0.0,
             "ball should bounce right (away from left paddle), got vx={}",
-            velocity.x
+            velocity.x()
         );
         assert!(
             speed_after >= speed_before,
@@ -176,30 +278,36 @@ mod paddle_collisions {
 
     #[test]
     fn corner_hit_resolves_cleanly() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let paddle_y = FIELD_HEIGHT / 2.0;
         let paddle = paddle_entity(&world, 0);
-        world.transform_mut(paddle).y = paddle_y;
+        world.transform_mut(paddle).position.set_y(paddle_y);
 
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = 26.0;
-        world.transform_mut(ball).y = paddle_y - PADDLE_HEIGHT / 2.0 - BALL_RADIUS - 0.5;
-        world.velocity_mut(ball).x = -200.0;
-        world.velocity_mut(ball).y = 200.0;
+        world.transform_mut(ball).position.set_x(26.0);
+        world
+            .transform_mut(ball)
+            .position
+            .set_y(paddle_y - PADDLE_HEIGHT / 2.0 - BALL_RADIUS - 0.5);
+        world.velocity_mut(ball).set_x(-200.0);
+        world.velocity_mut(ball).set_y(200.0);
         let speed_before = (200.0_f32.powi(2) + 200.0_f32.powi(2)).sqrt();
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         let velocity = world.velocity(ball);
-        let speed_after = (velocity.x.powi(2) + velocity.y.powi(2)).sqrt();
-        assert!(velocity.x > 0.0, "ball should bounce right");
-        assert!(velocity.y < 0.0, "ball should bounce upward from top edge");
+        let speed_after = (velocity.x().powi(2) + velocity.y().powi(2)).sqrt();
+        assert!(velocity.x() > 0.0, "ball should bounce right");
+        assert!(
+            velocity.y() < 0.0,
+            "ball should bounce upward from top edge"
+        );
         assert!(
             speed_after >= speed_before,
             "speed should not decrease after paddle hit"
         );
         let paddle_top = paddle_y - PADDLE_HEIGHT / 2.0;
-        let ball_y = world.transform(ball).y;
+        let ball_y = world.transform(ball).position.y();
         assert!(
             ball_y <= paddle_top - BALL_RADIUS,
             "ball should be pushed clear of paddle top edge, got {}",
@@ -209,13 +317,16 @@ mod paddle_collisions {
 
     #[test]
     fn paddle_cannot_move_above_top_edge() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let paddle = paddle_entity(&world, 0);
-        world.transform_mut(paddle).y = PADDLE_HEIGHT / 2.0 + 10.0;
+        world
+            .transform_mut(paddle)
+            .position
+            .set_y(PADDLE_HEIGHT / 2.0 + 10.0);
 
-        step(&mut world, &schedule, DT, INPUT_UP);
+        step(&mut world, &mut schedule, DT, INPUT_UP);
 
-        let y = world.transform(paddle).y;
+        let y = world.transform(paddle).position.y();
         assert!(
             y >= PADDLE_HEIGHT / 2.0,
             "paddle should not move above top edge"
@@ -224,13 +335,16 @@ mod paddle_collisions {
 
     #[test]
     fn paddle_cannot_move_below_bottom_edge() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let paddle = paddle_entity(&world, 0);
-        world.transform_mut(paddle).y = FIELD_HEIGHT - PADDLE_HEIGHT / 2.0 - 10.0;
+        world
+            .transform_mut(paddle)
+            .position
+            .set_y(FIELD_HEIGHT - PADDLE_HEIGHT / 2.0 - 10.0);
 
-        step(&mut world, &schedule, DT, INPUT_DOWN);
+        step(&mut world, &mut schedule, DT, INPUT_DOWN);
 
-        let y = world.transform(paddle).y;
+        let y = world.transform(paddle).position.y();
         assert!(
             y <= FIELD_HEIGHT - PADDLE_HEIGHT / 2.0,
             "paddle should not move below bottom edge"
@@ -238,10 +352,203 @@ mod paddle_collisions {
     }
 }
 
+mod paddle_movement {
+    use super::*;
+
+    fn hold(world: &mut World, schedule: &mut Schedule, input_bits: u32, frames: usize) {
+        for _ in 0..frames {
+            step(world, schedule, DT, input_bits);
+        }
+    }
+
+    #[test]
+    fn paddle_speeds_up_to_its_max_speed() {
+        let (mut world, mut schedule) = new_game();
+        let paddle = paddle_entity(&world, 0);
+
+        step(&mut world, &mut schedule, DT, INPUT_DOWN);
+        let first = world.velocity(paddle).y();
+        assert!(first > 0.0 && first < PADDLE_SPEED);
+
+        hold(&mut world, &mut schedule, INPUT_DOWN, 10);
+        assert_eq!(world.velocity(paddle).y(), PADDLE_SPEED);
+    }
+
+    #[test]
+    fn paddle_pinned_at_the_edge_tracks_no_velocity() {
+        let (mut world, mut schedule) = new_game();
+        let paddle = paddle_entity(&world, 0);
+
+        hold(&mut world, &mut schedule, INPUT_UP, 120);
+
+        assert!(world.kinematic_body(paddle).velocity.y.abs() < 1.0);
+    }
+
+    #[test]
+    fn spin_comes_from_the_paddles_tracked_motion() {
+        let (mut world, mut schedule) = new_game();
+        world.substeps.max_substeps = 1;
+        let ball = ball_entity(&world);
+        let paddle = paddle_entity(&world, 0);
+        hold(&mut world, &mut schedule, INPUT_UP, 5);
+
+        let paddle_y = world.transform(paddle).position.y();
+        world.transform_mut(ball).position =
+            SimVec2::new(PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0, paddle_y);
+        *world.velocity_mut(ball) = Velocity::new(-200.0, 0.0);
+        world.spin_mut(ball).value = 0.0;
+        step(&mut world, &mut schedule, DT, INPUT_UP);
+
+        let tracked = world.kinematic_body(paddle).velocity.y;
+        assert!(tracked < 0.0);
+        let expected = -tracked * SPIN_TRANSFER_RATE * math::exp_neg(SPIN_DECAY_RATE * DT);
+        assert!((world.spin(ball).value - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn a_paddle_stopped_by_the_wall_in_the_hit_frame_passes_no_spin() {
+        let (mut world, mut schedule) = new_game();
+        let ball = ball_entity(&world);
+        let paddle = paddle_entity(&world, 0);
+        // Moving up and two pixels short of the wall, so it gets there this frame.
+        world
+            .transform_mut(paddle)
+            .position
+            .set_y(PADDLE_HEIGHT / 2.0 + 2.0);
+        world.velocity_mut(paddle).set_y(-PADDLE_SPEED);
+        world.kinematic_body_mut(paddle).teleported();
+        step(&mut world, &mut schedule, DT, INPUT_UP);
+        assert_eq!(world.transform(paddle).position.y(), PADDLE_HEIGHT / 2.0);
+
+        world.transform_mut(ball).position = SimVec2::new(
+            PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0,
+            PADDLE_HEIGHT / 2.0,
+        );
+        *world.velocity_mut(ball) = Velocity::new(-200.0, 0.0);
+        world.spin_mut(ball).value = 0.0;
+        step(&mut world, &mut schedule, DT, INPUT_UP);
+
+        assert!(world.velocity(ball).x() > 0.0, "ball should bounce");
+        assert_eq!(world.kinematic_body(paddle).velocity.y, 0.0);
+        assert_eq!(world.spin(ball).value, 0.0);
+    }
+
+    #[test]
+    fn a_tap_nudges_the_paddle_and_drag_stops_it() {
+        let (mut world, mut schedule) = new_game();
+        let paddle = paddle_entity(&world, 0);
+        let start = world.transform(paddle).position.y();
+
+        step(&mut world, &mut schedule, DT, INPUT_DOWN);
+        hold(&mut world, &mut schedule, 0, 120);
+
+        let moved = world.transform(paddle).position.y() - start;
+        assert!(moved > 1.0 && moved < 20.0, "tap moved {}", moved);
+        assert!(world.velocity(paddle).y().abs() < 1.0);
+    }
+
+    #[test]
+    fn precision_key_slows_the_paddle() {
+        let (mut world, mut schedule) = new_game();
+        let paddle = paddle_entity(&world, 0);
+
+        hold(&mut world, &mut schedule, INPUT_DOWN | INPUT_PRECISION, 30);
+
+        let speed = world.velocity(paddle).y();
+        let terminal = PADDLE_ACCELERATION * PADDLE_PRECISION_SCALE / PADDLE_DRAG;
+        assert!(
+            speed > 0.0 && speed <= terminal,
+            "precision speed {}",
+            speed
+        );
+    }
+}
+
+mod ai {
+    use super::*;
+
+    #[test]
+    fn ai_reacts_at_its_decision_rate_not_every_frame() {
+        let (mut world, mut schedule) = new_game();
+        let ball = ball_entity(&world);
+        let paddle = paddle_entity(&world, 1);
+        *world.velocity_mut(ball) = Velocity::new(0.0, 0.0);
+        let paddle_y = world.transform(paddle).position.y();
+        world.transform_mut(ball).position.set_y(paddle_y + 200.0);
+
+        step(&mut world, &mut schedule, DT, 0);
+        assert_eq!(world.velocity(paddle).y(), 0.0);
+
+        for _ in 0..(60.0 / AI_DECISION_HZ) as usize {
+            step(&mut world, &mut schedule, DT, 0);
+        }
+        assert_eq!(world.acceleration(paddle).y, PADDLE_ACCELERATION);
+        assert!(world.velocity(paddle).y() > 0.0);
+    }
+
+    #[test]
+    fn disabling_decide_ai_freezes_the_ai_paddle() {
+        let (mut world, mut schedule) = new_game();
+        let ball = ball_entity(&world);
+        let paddle = paddle_entity(&world, 1);
+        *world.velocity_mut(ball) = Velocity::new(0.0, 0.0);
+        let paddle_y = world.transform(paddle).position.y();
+        world.transform_mut(ball).position.set_y(paddle_y + 200.0);
+
+        assert!(schedule.set_system_enabled("decide_ai", false));
+        for _ in 0..30 {
+            step(&mut world, &mut schedule, DT, 0);
+        }
+
+        assert_eq!(world.velocity(paddle).y(), 0.0);
+        assert_eq!(world.transform(paddle).position.y(), FIELD_HEIGHT / 2.0);
+    }
+
+    #[test]
+    fn disabling_steer_ai_stops_the_paddle_chasing_a_stale_target() {
+        let (mut world, mut schedule) = new_game();
+        let ball = ball_entity(&world);
+        let paddle = paddle_entity(&world, 1);
+        *world.velocity_mut(ball) = Velocity::new(0.0, 0.0);
+        let paddle_y = world.transform(paddle).position.y();
+        world.resource_mut::<PongState>().ai_target_y = paddle_y + 200.0;
+
+        assert!(schedule.set_system_enabled("decide_ai", false));
+        assert!(schedule.set_system_enabled("steer_ai", false));
+        for _ in 0..30 {
+            step(&mut world, &mut schedule, DT, 0);
+        }
+
+        assert_eq!(world.acceleration(paddle).y, 0.0);
+        assert_eq!(world.velocity(paddle).y(), 0.0);
+        assert_eq!(world.transform(paddle).position.y(), paddle_y);
+    }
+
+    #[test]
+    fn disabling_steer_ai_lets_a_moving_ai_paddle_coast_to_a_stop() {
+        let (mut world, mut schedule) = new_game();
+        let paddle = paddle_entity(&world, 1);
+        let paddle_y = world.transform(paddle).position.y();
+        world.resource_mut::<PongState>().ai_target_y = paddle_y + 200.0;
+        assert!(schedule.set_system_enabled("decide_ai", false));
+        step(&mut world, &mut schedule, DT, 0);
+        assert_eq!(world.acceleration(paddle).y, PADDLE_ACCELERATION);
+
+        assert!(schedule.set_system_enabled("steer_ai", false));
+        step(&mut world, &mut schedule, DT, 0);
+        assert_eq!(world.acceleration(paddle).y, 0.0);
+        let speed_after_release = world.velocity(paddle).y();
+        for _ in 0..120 {
+            step(&mut world, &mut schedule, DT, 0);
+        }
+        assert!(world.velocity(paddle).y().abs() < speed_after_release.abs());
+    }
+}
+
 mod scoring {
     use super::*;
 
-    fn wait_for_serve(world: &mut World, schedule: &Schedule) {
+    fn wait_for_serve(world: &mut World, schedule: &mut Schedule) {
         let mut ticks = 0;
         while !ball_visible(world) {
             step(world, schedule, DT, 0);
@@ -252,14 +559,14 @@ mod scoring {
 
     #[test]
     fn ball_exiting_left_scores_for_player_two() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = 5.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = -400.0;
-        world.velocity_mut(ball).y = 0.0;
+        world.transform_mut(ball).position.set_x(5.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(-400.0);
+        world.velocity_mut(ball).set_y(0.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         let pong = pong_ref(&world);
         assert_eq!(pong.player_two_score, 1);
@@ -268,14 +575,14 @@ mod scoring {
 
     #[test]
     fn ball_exiting_right_scores_for_player_one() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = FIELD_WIDTH - 5.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = 400.0;
-        world.velocity_mut(ball).y = 0.0;
+        world.transform_mut(ball).position.set_x(FIELD_WIDTH - 5.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(400.0);
+        world.velocity_mut(ball).set_y(0.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         let pong = pong_ref(&world);
         assert_eq!(pong.player_one_score, 1);
@@ -284,27 +591,27 @@ mod scoring {
 
     #[test]
     fn scores_accumulate_across_rounds() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let ball = ball_entity(&world);
 
         for round in 1..=4 {
-            world.transform_mut(ball).x = FIELD_WIDTH - 5.0;
-            world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-            world.velocity_mut(ball).x = 400.0;
-            world.velocity_mut(ball).y = 0.0;
-            step(&mut world, &schedule, DT, 0);
+            world.transform_mut(ball).position.set_x(FIELD_WIDTH - 5.0);
+            world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+            world.velocity_mut(ball).set_x(400.0);
+            world.velocity_mut(ball).set_y(0.0);
+            step(&mut world, &mut schedule, DT, 0);
             assert_eq!(pong_ref(&world).player_one_score, round);
-            wait_for_serve(&mut world, &schedule);
+            wait_for_serve(&mut world, &mut schedule);
         }
 
         for round in 1..=3 {
-            world.transform_mut(ball).x = 5.0;
-            world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-            world.velocity_mut(ball).x = -400.0;
-            world.velocity_mut(ball).y = 0.0;
-            step(&mut world, &schedule, DT, 0);
+            world.transform_mut(ball).position.set_x(5.0);
+            world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+            world.velocity_mut(ball).set_x(-400.0);
+            world.velocity_mut(ball).set_y(0.0);
+            step(&mut world, &mut schedule, DT, 0);
             assert_eq!(pong_ref(&world).player_two_score, round);
-            wait_for_serve(&mut world, &schedule);
+            wait_for_serve(&mut world, &mut schedule);
         }
 
         assert_eq!(pong_ref(&world).player_one_score, 4);
@@ -313,24 +620,24 @@ mod scoring {
 
     #[test]
     fn game_ends_at_winning_score() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         pong_mut(&mut world).winning_score = 3;
         pong_mut(&mut world).player_one_score = 2;
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = FIELD_WIDTH - 5.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = 400.0;
-        world.velocity_mut(ball).y = 0.0;
+        world.transform_mut(ball).position.set_x(FIELD_WIDTH - 5.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(400.0);
+        world.velocity_mut(ball).set_y(0.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         let pong = pong_ref(&world);
         assert_eq!(pong.player_one_score, 3);
-        assert_eq!(pong.phase, PongPhase::GameOver);
+        assert_eq!(current_phase(&world), PongPhase::GameOver);
 
-        let ball_x_before = world.transform(ball).x;
-        step(&mut world, &schedule, DT, 0);
-        let ball_x_after = world.transform(ball).x;
+        let ball_x_before = world.transform(ball).position.x();
+        step(&mut world, &mut schedule, DT, 0);
+        let ball_x_after = world.transform(ball).position.x();
         assert_eq!(
             ball_x_after, ball_x_before,
             "step should be a no-op after game over"
@@ -339,19 +646,18 @@ mod scoring {
 
     #[test]
     fn ball_stays_hidden_during_game_over() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         pong_mut(&mut world).winning_score = 2;
         pong_mut(&mut world).player_one_score = 1;
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = FIELD_WIDTH - 5.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = 400.0;
-        world.velocity_mut(ball).y = 0.0;
+        world.transform_mut(ball).position.set_x(FIELD_WIDTH - 5.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(400.0);
+        world.velocity_mut(ball).set_y(0.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
-        let pong = pong_ref(&world);
-        assert_eq!(pong.phase, PongPhase::GameOver);
+        assert_eq!(current_phase(&world), PongPhase::GameOver);
         assert!(
             !ball_visible(&world),
             "ball should stay hidden once the game is over"
@@ -360,15 +666,15 @@ mod scoring {
 
     #[test]
     fn action_input_restarts_game_when_over() {
-        let (mut world, schedule) = new_game();
-        pong_mut(&mut world).phase = PongPhase::GameOver;
+        let (mut world, mut schedule) = new_game();
+        set_phase(&mut world, PongPhase::GameOver);
         pong_mut(&mut world).winner = Some(PongPlayer::One);
         pong_mut(&mut world).player_one_score = 11;
 
-        step(&mut world, &schedule, DT, INPUT_ACTION);
+        step(&mut world, &mut schedule, DT, INPUT_ACTION);
 
         let pong = pong_ref(&world);
-        assert_eq!(pong.phase, PongPhase::Playing);
+        assert_eq!(current_phase(&world), PongPhase::Playing);
         assert_eq!(pong.winner, None);
         assert_eq!(pong.player_one_score, 0);
         assert_eq!(pong.player_two_score, 0);
@@ -376,32 +682,32 @@ mod scoring {
 
     #[test]
     fn action_input_ignored_during_play() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         pong_mut(&mut world).player_one_score = 5;
 
-        step(&mut world, &schedule, DT, INPUT_ACTION);
+        step(&mut world, &mut schedule, DT, INPUT_ACTION);
 
         let pong = pong_ref(&world);
-        assert_eq!(pong.phase, PongPhase::Playing);
+        assert_eq!(current_phase(&world), PongPhase::Playing);
         assert_eq!(pong.player_one_score, 5);
     }
 
     #[test]
     fn serve_delay_hides_ball_after_score() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = 5.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = -400.0;
-        world.velocity_mut(ball).y = 0.0;
+        world.transform_mut(ball).position.set_x(5.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(-400.0);
+        world.velocity_mut(ball).set_y(0.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         assert!(
             !ball_visible(&world),
             "ball should go invisible once a score happens"
         );
-        let delay = pong_ref(&world).serve_delay_remaining;
+        let delay = world.timer(ball).remaining();
         assert!(
             (SERVE_DELAY_MIN..SERVE_DELAY_MAX).contains(&delay),
             "delay should be between {} and {} but was {}",
@@ -410,20 +716,20 @@ mod scoring {
             delay
         );
 
-        let prev_delay = pong_ref(&world).serve_delay_remaining;
-        step(&mut world, &schedule, DT, 0);
+        let prev_delay = world.timer(ball).remaining();
+        step(&mut world, &mut schedule, DT, 0);
         assert!(!ball_visible(&world));
-        assert!(pong_ref(&world).serve_delay_remaining < prev_delay);
+        assert!(world.timer(ball).remaining() < prev_delay);
 
         let mut iterations = 0;
         while !ball_visible(&world) && iterations < 200 {
-            step(&mut world, &schedule, DT, 0);
+            step(&mut world, &mut schedule, DT, 0);
             iterations += 1;
         }
         assert!(ball_visible(&world), "serve should eventually launch");
-        assert_eq!(pong_ref(&world).serve_delay_remaining, 0.0);
+        assert_eq!(world.timer(ball).remaining(), 0.0);
         let velocity = world.velocity(ball);
-        let speed = (velocity.x.powi(2) + velocity.y.powi(2)).sqrt();
+        let speed = (velocity.x().powi(2) + velocity.y().powi(2)).sqrt();
         assert!(
             speed > 0.0,
             "ball should have non-zero velocity after launch"
@@ -433,6 +739,37 @@ mod scoring {
             "conceded_by should be cleared on launch"
         );
     }
+
+    #[test]
+    fn serve_launches_while_a_paddle_forces_sub_steps() {
+        let (mut world, mut schedule) = new_game();
+        let ball = ball_entity(&world);
+        world.transform_mut(ball).position.set_x(5.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(-400.0);
+        world.velocity_mut(ball).set_y(0.0);
+        step(&mut world, &mut schedule, MAX_DT, 0);
+        assert!(!ball_visible(&world));
+
+        for _ in 0..3 {
+            step(&mut world, &mut schedule, MAX_DT, INPUT_UP);
+        }
+        assert!(
+            physics_substeps(&world, MAX_DT) > 1,
+            "the moving paddle should sub-step the frame"
+        );
+        // Run out in the first sub-step, so later ones tick a finished timer.
+        *world.timer_mut(ball) = Timer::once(MAX_DT / 4.0);
+        step(&mut world, &mut schedule, MAX_DT, INPUT_UP);
+
+        assert!(ball_visible(&world));
+        let velocity = world.velocity(ball);
+        assert!(
+            velocity.x().hypot(velocity.y()) > 0.0,
+            "ball should launch when the serve timer runs out"
+        );
+        assert!(pong_ref(&world).conceded_by.is_none());
+    }
 }
 
 mod snapshot {
@@ -443,7 +780,7 @@ mod snapshot {
         let (mut world, _schedule) = new_game();
         pong_mut(&mut world).player_one_score = 7;
         pong_mut(&mut world).player_two_score = 4;
-        pong_mut(&mut world).phase = PongPhase::GameOver;
+        set_phase(&mut world, PongPhase::GameOver);
         pong_mut(&mut world).winner = Some(PongPlayer::One);
 
         let mut snapshot = vec![0.0; SnapshotField::Count as usize];
@@ -475,25 +812,161 @@ mod snapshot {
     }
 }
 
+mod wrap_walls {
+    use super::*;
+
+    #[test]
+    fn wrapping_walls_send_the_ball_over_the_seam_and_snapshot_ghosts() {
+        let (mut world, mut schedule) = new_game();
+        assert_eq!(
+            set_tuning_param(&mut world, PongTuningParam::WrapWalls as u32, 1.0),
+            TUNING_STATUS_APPLIED
+        );
+        assert_eq!(
+            get_tuning_param(&world, PongTuningParam::WrapWalls as u32),
+            Some(1.0)
+        );
+        let ball = ball_entity(&world);
+        world.transform_mut(ball).position = SimVec2::new(FIELD_WIDTH / 2.0, 4.0);
+        *world.velocity_mut(ball) = Velocity::new(0.0, -120.0);
+
+        let mut snapshot = vec![0.0; SnapshotField::Count as usize];
+        write_snapshot(&world, &mut snapshot);
+        let ghosts = &snapshot[SnapshotField::BallGhosts.idx()..];
+        assert_eq!(&ghosts[..3], &[1.0, FIELD_WIDTH / 2.0, FIELD_HEIGHT + 4.0]);
+
+        step(&mut world, &mut schedule, 0.1, 0);
+        let position = world.transform(ball).position;
+        assert!((position.y() - (FIELD_HEIGHT - 8.0)).abs() < 1e-3);
+        assert!(world.velocity(ball).y() < 0.0, "wrapping should not bounce");
+    }
+
+    #[test]
+    fn reset_restores_reflecting_walls() {
+        let (mut world, _schedule) = new_game();
+        set_tuning_param(&mut world, PongTuningParam::WrapWalls as u32, 1.0);
+
+        reset_tuning_defaults(&mut world);
+
+        assert_eq!(world.field.edges.top, EdgeBehavior::Reflect);
+        assert_eq!(world.field.edges.bottom, EdgeBehavior::Reflect);
+        assert_eq!(world.field.edges.left, EdgeBehavior::Exit);
+    }
+
+    #[test]
+    fn ball_readouts_stay_read_only() {
+        let (mut world, _schedule) = new_game();
+
+        assert_eq!(
+            set_tuning_param(&mut world, PongTuningParam::BallX as u32, 10.0),
+            TUNING_STATUS_REJECTED
+        );
+        assert_eq!(
+            set_tuning_param(&mut world, 99, 1.0),
+            TUNING_STATUS_UNKNOWN_PARAM
+        );
+    }
+}
+
+mod paddle_tuning {
+    use super::*;
+
+    #[test]
+    fn drag_tuning_reaches_both_paddles_and_clamps() {
+        let (mut world, _schedule) = new_game();
+
+        set_tuning_param(&mut world, PongTuningParam::PaddleDrag as u32, 100.0);
+
+        for index in 0..2 {
+            let paddle = paddle_entity(&world, index);
+            assert_eq!(world.drag(paddle).rate, MAX_PADDLE_DRAG);
+        }
+        assert_eq!(
+            get_tuning_param(&world, PongTuningParam::PaddleDrag as u32),
+            Some(MAX_PADDLE_DRAG)
+        );
+    }
+
+    #[test]
+    fn acceleration_tuning_drives_held_input() {
+        let (mut world, mut schedule) = new_game();
+        set_tuning_param(
+            &mut world,
+            PongTuningParam::PaddleAcceleration as u32,
+            1000.0,
+        );
+
+        step(&mut world, &mut schedule, DT, INPUT_DOWN);
+
+        let paddle = paddle_entity(&world, 0);
+        assert_eq!(world.acceleration(paddle).y, 1000.0);
+    }
+
+    #[test]
+    fn reset_restores_default_handling() {
+        let (mut world, _schedule) = new_game();
+        set_tuning_param(
+            &mut world,
+            PongTuningParam::PaddleAcceleration as u32,
+            900.0,
+        );
+        set_tuning_param(&mut world, PongTuningParam::PaddleDrag as u32, 2.0);
+
+        reset_tuning_defaults(&mut world);
+
+        assert_eq!(*world.resource::<PongTuning>(), PongTuning::default());
+        assert_eq!(world.drag(paddle_entity(&world, 1)).rate, PADDLE_DRAG);
+    }
+}
+
+mod phase_timing {
+    use super::*;
+
+    #[test]
+    fn snapshot_reports_time_in_phase_and_resets_on_transition() {
+        let (mut world, mut schedule) = new_game();
+        let mut snapshot = vec![0.0; SnapshotField::Count as usize];
+
+        step(&mut world, &mut schedule, 0.25, 0);
+        step(&mut world, &mut schedule, 0.25, 0);
+        write_snapshot(&world, &mut snapshot);
+        assert_eq!(snapshot[SnapshotField::PhaseElapsed.idx()], 0.5);
+
+        world
+            .resource_mut::<State<PongPhase>>()
+            .queue(PongPhase::GameOver);
+        step(&mut world, &mut schedule, 0.25, 0);
+        write_snapshot(&world, &mut snapshot);
+        assert_eq!(snapshot[SnapshotField::GamePhase.idx()], 1.0);
+        assert_eq!(snapshot[SnapshotField::PhaseElapsed.idx()], 0.0);
+    }
+}
+
 mod speed_progression {
     use super::*;
 
     #[test]
     fn speed_increases_after_paddle_hit() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let paddle = paddle_entity(&world, 0);
-        world.transform_mut(paddle).y = FIELD_HEIGHT / 2.0;
+        world
+            .transform_mut(paddle)
+            .position
+            .set_y(FIELD_HEIGHT / 2.0);
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = -300.0;
-        world.velocity_mut(ball).y = 0.0;
-        let speed_before = world.velocity(ball).x.hypot(world.velocity(ball).y);
+        world
+            .transform_mut(ball)
+            .position
+            .set_x(PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(-300.0);
+        world.velocity_mut(ball).set_y(0.0);
+        let speed_before = world.velocity(ball).x().hypot(world.velocity(ball).y());
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         let velocity = world.velocity(ball);
-        let speed_after = velocity.x.hypot(velocity.y);
+        let speed_after = velocity.x().hypot(velocity.y());
         assert!(
             speed_after > speed_before,
             "speed should increase after paddle hit: before={speed_before}, after={speed_after}"
@@ -502,19 +975,25 @@ mod speed_progression {
 
     #[test]
     fn speed_does_not_exceed_cap() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let paddle = paddle_entity(&world, 0);
-        world.transform_mut(paddle).y = FIELD_HEIGHT / 2.0;
+        world
+            .transform_mut(paddle)
+            .position
+            .set_y(FIELD_HEIGHT / 2.0);
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = -BALL_MAX_SPEED;
-        world.velocity_mut(ball).y = 0.0;
+        world
+            .transform_mut(ball)
+            .position
+            .set_x(PADDLE1_X + PADDLE_WIDTH / 2.0 + BALL_RADIUS + 2.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(-BALL_MAX_SPEED);
+        world.velocity_mut(ball).set_y(0.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
 
         let velocity = world.velocity(ball);
-        let speed_after = velocity.x.hypot(velocity.y);
+        let speed_after = velocity.x().hypot(velocity.y());
         assert!(
             speed_after <= BALL_MAX_SPEED,
             "speed must not exceed cap: got {speed_after}"
@@ -523,28 +1002,262 @@ mod speed_progression {
 
     #[test]
     fn speed_resets_on_new_serve() {
-        let (mut world, schedule) = new_game();
+        let (mut world, mut schedule) = new_game();
         let ball = ball_entity(&world);
-        world.transform_mut(ball).x = FIELD_WIDTH - 5.0;
-        world.transform_mut(ball).y = FIELD_HEIGHT / 2.0;
-        world.velocity_mut(ball).x = BALL_MAX_SPEED;
-        world.velocity_mut(ball).y = 0.0;
+        world.transform_mut(ball).position.set_x(FIELD_WIDTH - 5.0);
+        world.transform_mut(ball).position.set_y(FIELD_HEIGHT / 2.0);
+        world.velocity_mut(ball).set_x(BALL_MAX_SPEED);
+        world.velocity_mut(ball).set_y(0.0);
 
-        step(&mut world, &schedule, DT, 0);
+        step(&mut world, &mut schedule, DT, 0);
         assert!(!ball_visible(&world));
 
         let mut ticks = 0;
         while !ball_visible(&world) {
-            step(&mut world, &schedule, DT, 0);
+            step(&mut world, &mut schedule, DT, 0);
             ticks += 1;
             assert!(ticks < 300, "serve never launched");
         }
 
         let velocity = world.velocity(ball);
-        let speed_after_serve = velocity.x.hypot(velocity.y);
+        let speed_after_serve = velocity.x().hypot(velocity.y());
         assert!(
             speed_after_serve <= 500.0,
             "speed should reset to serve range after a point: got {speed_after_serve}"
         );
     }
 }
+
+mod save_state {
+    use super::*;
+    use crate::engine::save::{decode, encode, SaveError, SaveReader, SaveWriter};
+    use crate::engine::GameId;
+
+    const PONG: GameId = GameId(0);
+
+    #[test]
+    fn restores_scores_phase_and_ball() {
+        let (mut world, mut schedule) = new_game();
+        let (_, _, _, _, save_api) = build_world(FIELD_WIDTH, FIELD_HEIGHT);
+        pong_mut(&mut world).player_one_score = 6;
+        pong_mut(&mut world).player_two_score = 9;
+        pong_mut(&mut world).conceded_by = Some(PongPlayer::Two);
+        let ball = ball_entity(&world);
+        let ball_before = *world.transform(ball);
+        let bytes = encode(PONG, &world, &save_api);
+
+        for _ in 0..30 {
+            step(&mut world, &mut schedule, DT, INPUT_UP);
+        }
+        set_phase(&mut world, PongPhase::GameOver);
+        decode(PONG, &mut world, &save_api, &bytes).unwrap();
+
+        let pong = pong_ref(&world);
+        assert_eq!(pong.player_one_score, 6);
+        assert_eq!(pong.player_two_score, 9);
+        assert_eq!(current_phase(&world), PongPhase::Playing);
+        assert_eq!(pong.conceded_by, Some(PongPlayer::Two));
+        assert_eq!(*world.transform(ball), ball_before);
+    }
+
+    #[test]
+    fn restores_paddle_tuning() {
+        let (mut world, _schedule, _snapshot, _tuning_api, save_api) =
+            build_world(FIELD_WIDTH, FIELD_HEIGHT);
+        set_tuning_param(&mut world, PongTuningParam::PaddleDrag as u32, 4.0);
+        let bytes = encode(PONG, &world, &save_api);
+
+        reset_tuning_defaults(&mut world);
+        decode(PONG, &mut world, &save_api, &bytes).unwrap();
+
+        assert_eq!(world.resource::<PongTuning>().paddle_drag, 4.0);
+        assert_eq!(world.drag(paddle_entity(&world, 0)).rate, 4.0);
+    }
+
+    #[test]
+    fn rejects_unknown_phase_tag() {
+        let (mut world, _schedule) = new_game();
+        let pong = pong_ref(&world);
+        let mut writer = SaveWriter::new();
+        writer.write_entity(pong.ball);
+        writer.write_entity(pong.paddles[0]);
+        writer.write_entity(pong.paddles[1]);
+        writer.write_u32(0);
+        writer.write_u32(0);
+        write_player(&mut writer, None);
+        write_player(&mut writer, None);
+        writer.write_u32(WINNING_SCORE);
+        writer.write_f32(0.0);
+        writer.write_u8(9);
+        write_phase(&mut writer, None);
+        writer.write_f32(0.0);
+        let bytes = writer.into_bytes();
+
+        let result = read_save(&mut world, &mut SaveReader::new(&bytes));
+
+        assert_eq!(result, Err(SaveError::Invalid("unknown pong phase")));
+    }
+}
+
+mod determinism {
+    use super::*;
+    use crate::engine::{Engine, GameId};
+
+    fn new_engine() -> Engine {
+        let (world, schedule, snapshot, tuning_api, save_api) =
+            build_world(FIELD_WIDTH, FIELD_HEIGHT);
+        Engine::new(GameId(0), world, schedule, snapshot, tuning_api, save_api)
+    }
+
+    fn input_for_frame(frame: u32) -> u32 {
+        match frame % 40 {
+            0..=14 => INPUT_UP,
+            20..=34 => INPUT_DOWN,
+            _ => 0,
+        }
+    }
+
+    /// Serves and paddle bounces go through `math::sin_cos` and spin decay
+    /// through `math::exp_neg`, so a match on native and on wasm covers the
+    /// game's own math as well as the engine's.
+    #[cfg(feature = "fixed-point")]
+    #[test]
+    fn scripted_match_matches_the_golden_checksum() {
+        const GOLDEN_CHECKSUM: u64 = 0x68d0_d32f_abc8_4fbb;
+
+        let (mut world, schedule, snapshot, tuning_api, save_api) =
+            build_world(FIELD_WIDTH, FIELD_HEIGHT);
+        world.rng = crate::engine::ecs::resources::SimRng::new(7);
+        let mut engine = Engine::new(GameId(0), world, schedule, snapshot, tuning_api, save_api);
+        for frame in 0..1200 {
+            engine.step(DT, input_for_frame(frame));
+        }
+
+        assert_eq!(engine.checksum(), GOLDEN_CHECKSUM);
+    }
+
+    #[test]
+    fn replay_from_save_reproduces_checksums() {
+        let mut recorded = new_engine();
+        let start = recorded.save_state();
+        let mut checksums = Vec::new();
+        for frame in 0..600 {
+            recorded.step(DT, input_for_frame(frame));
+            checksums.push(recorded.checksum());
+        }
+
+        let mut replay = new_engine();
+        replay.load_state(&start).unwrap();
+        for (frame, expected) in checksums.iter().enumerate() {
+            replay.step(DT, input_for_frame(frame as u32));
+            assert_eq!(replay.checksum(), *expected, "diverged at frame {frame}");
+        }
+    }
+
+    #[test]
+    fn replay_from_a_mid_run_save_keeps_the_ai_tick_phase() {
+        let mut recorded = new_engine();
+        // Three frames in, the 12 Hz AI phase has time banked toward its
+        // next tick.
+        for frame in 0..3 {
+            recorded.step(DT, input_for_frame(frame));
+        }
+        let midway = recorded.save_state();
+        let mut checksums = Vec::new();
+        for frame in 3..120 {
+            recorded.step(DT, input_for_frame(frame));
+            checksums.push(recorded.checksum());
+        }
+
+        let mut replay = new_engine();
+        replay.load_state(&midway).unwrap();
+        for (offset, expected) in checksums.iter().enumerate() {
+            replay.step(DT, input_for_frame(offset as u32 + 3));
+            assert_eq!(
+                replay.checksum(),
+                *expected,
+                "diverged at frame {}",
+                offset + 3
+            );
+        }
+    }
+
+    #[test]
+    fn pause_freezes_the_simulation_and_step_frame_advances_one_frame() {
+        let mut paused = new_engine();
+        let mut reference = new_engine();
+        reference.load_state(&paused.save_state()).unwrap();
+
+        paused.pause();
+        for _ in 0..10 {
+            paused.step(DT, INPUT_UP);
+        }
+        assert_eq!(paused.checksum(), reference.checksum());
+
+        paused.step_frame();
+        paused.step(DT, INPUT_UP);
+        paused.step(DT, INPUT_UP);
+        reference.step(DT, INPUT_UP);
+        assert_eq!(paused.checksum(), reference.checksum());
+    }
+
+    #[test]
+    fn time_scale_scales_simulated_time() {
+        let mut engine = new_engine();
+        assert_eq!(engine.set_time_scale(0.5), 0.5);
+
+        for _ in 0..4 {
+            engine.step(0.05, 0);
+        }
+
+        assert_eq!(engine.time().delta(), 0.025);
+        assert_eq!(engine.time().unscaled_delta(), 0.05);
+        assert!((engine.time().elapsed() - 0.1).abs() < 1e-6);
+    }
+}
+
+mod schedule_order {
+    use super::*;
+
+    #[test]
+    fn scoring_runs_after_wall_checks() {
+        let (_world, schedule) = new_game();
+        let order = schedule.describe();
+
+        let walls = order.find("collide_walls").expect("collide_walls missing");
+        let scoring = order
+            .find("resolve_scoring")
+            .expect("resolve_scoring missing");
+        assert!(walls < scoring, "unexpected order:\n{order}");
+    }
+}
+
+mod one_shots {
+    use super::*;
+
+    #[test]
+    fn restart_resets_scores_mid_match() {
+        let (mut world, mut schedule) = new_game();
+        pong_mut(&mut world).player_one_score = 4;
+        pong_mut(&mut world).player_two_score = 7;
+
+        assert!(schedule.run_one_shot("restart", &mut world));
+
+        assert_eq!(pong_ref(&world).player_one_score, 0);
+        assert_eq!(pong_ref(&world).player_two_score, 0);
+        assert!(ball_visible(&world));
+    }
+
+    #[test]
+    fn restart_leaves_game_over_on_next_step() {
+        let (mut world, mut schedule) = new_game();
+        set_phase(&mut world, PongPhase::GameOver);
+        pong_mut(&mut world).player_one_score = 11;
+
+        schedule.run_one_shot("restart", &mut world);
+        step(&mut world, &mut schedule, DT, 0);
+
+        assert_eq!(current_phase(&world), PongPhase::Playing);
+        assert_eq!(pong_ref(&world).player_one_score, 0);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::engine::save::{SAVE_STATUS_LOADED, SAVE_STATUS_NO_ENGINE};
 use crate::engine::Engine;
 use crate::engine::TUNING_STATUS_UNKNOWN_PARAM;
 use crate::games::build_game;
@@ -8,11 +9,15 @@ thread_local! {
     static ENGINE: RefCell<Option<Engine>> = const { RefCell::new(None) };
 }
 
+/// Without a `seed` the simulation RNG is seeded from entropy.
 #[wasm_bindgen]
-pub fn engine_init(game_name: &str, width: f32, height: f32) {
+pub fn engine_init(game_name: &str, width: f32, height: f32, seed: Option<u64>) {
     ENGINE.with(|engine| {
-        let (world, schedule, snapshot, tuning_api) = build_game(game_name, width, height);
-        *engine.borrow_mut() = Some(Engine::new(world, schedule, snapshot, tuning_api));
+        let (game_id, world, schedule, snapshot, tuning_api, save_api) =
+            build_game(game_name, width, height, seed);
+        *engine.borrow_mut() = Some(Engine::new(
+            game_id, world, schedule, snapshot, tuning_api, save_api,
+        ));
     });
 }
 
@@ -77,3 +82,191 @@ pub fn engine_reset_tuning_defaults() {
         }
     });
 }
+
+#[wasm_bindgen]
+pub fn engine_save_state() -> Vec<u8> {
+    ENGINE.with(|engine| {
+        engine
+            .borrow()
+            .as_ref()
+            .map(|engine| engine.save_state())
+            .unwrap_or_default()
+    })
+}
+
+#[wasm_bindgen]
+pub fn engine_load_state(bytes: &[u8]) -> u32 {
+    ENGINE.with(|engine| {
+        if let Some(engine) = engine.borrow_mut().as_mut() {
+            match engine.load_state(bytes) {
+                Ok(()) => SAVE_STATUS_LOADED,
+                Err(err) => err.status(),
+            }
+        } else {
+            SAVE_STATUS_NO_ENGINE
+        }
+    })
+}
+
+#[wasm_bindgen]
+pub fn engine_describe_schedule() -> String {
+    ENGINE.with(|engine| {
+        engine
+            .borrow()
+            .as_ref()
+            .map(|engine| engine.describe_schedule())
+            .unwrap_or_default()
+    })
+}
+
+#[wasm_bindgen]
+pub fn engine_run_one_shot(name: &str) -> bool {
+    ENGINE.with(|engine| {
+        engine
+            .borrow_mut()
+            .as_mut()
+            .map(|engine| engine.run_one_shot(name))
+            .unwrap_or(false)
+    })
+}
+
+#[wasm_bindgen]
+pub fn engine_pause() {
+    ENGINE.with(|engine| {
+        if let Some(engine) = engine.borrow_mut().as_mut() {
+            engine.pause();
+        }
+    });
+}
+
+#[wasm_bindgen]
+pub fn engine_resume() {
+    ENGINE.with(|engine| {
+        if let Some(engine) = engine.borrow_mut().as_mut() {
+            engine.resume();
+        }
+    });
+}
+
+#[wasm_bindgen]
+pub fn engine_is_paused() -> bool {
+    ENGINE.with(|engine| {
+        engine
+            .borrow()
+            .as_ref()
+            .map(|engine| engine.is_paused())
+            .unwrap_or(false)
+    })
+}
+
+/// While paused, the next `engine_step` advances exactly one frame.
+#[wasm_bindgen]
+pub fn engine_step_frame() {
+    ENGINE.with(|engine| {
+        if let Some(engine) = engine.borrow_mut().as_mut() {
+            engine.step_frame();
+        }
+    });
+}
+
+/// Returns the scale actually applied after clamping to 0.1–4.
+#[wasm_bindgen]
+pub fn engine_set_time_scale(scale: f32) -> f32 {
+    ENGINE.with(|engine| {
+        engine
+            .borrow_mut()
+            .as_mut()
+            .map(|engine| engine.set_time_scale(scale))
+            .unwrap_or(1.0)
+    })
+}
+
+#[wasm_bindgen]
+pub fn engine_time_scale() -> f32 {
+    ENGINE.with(|engine| {
+        engine
+            .borrow()
+            .as_ref()
+            .map(|engine| engine.time_scale())
+            .unwrap_or(1.0)
+    })
+}
+
+/// Returns the sub-step cap actually applied after clamping.
+#[wasm_bindgen]
+pub fn engine_set_max_substeps(max_substeps: u32) -> u32 {
+    ENGINE.with(|engine| {
+        engine
+            .borrow_mut()
+            .as_mut()
+            .map(|engine| engine.set_max_substeps(max_substeps))
+            .unwrap_or(0)
+    })
+}
+
+#[wasm_bindgen]
+pub fn engine_max_substeps() -> u32 {
+    ENGINE.with(|engine| {
+        engine
+            .borrow()
+            .as_ref()
+            .map(|engine| engine.max_substeps())
+            .unwrap_or(0)
+    })
+}
+
+#[wasm_bindgen]
+pub fn engine_set_system_enabled(name: &str, enabled: bool) -> bool {
+    ENGINE.with(|engine| {
+        engine
+            .borrow_mut()
+            .as_mut()
+            .map(|engine| engine.set_system_enabled(name, enabled))
+            .unwrap_or(false)
+    })
+}
+
+/// JSON array of `{phase, name, enabled}` rows in run order.
+#[wasm_bindgen]
+pub fn engine_list_systems() -> String {
+    ENGINE.with(|engine| {
+        engine
+            .borrow()
+            .as_ref()
+            .map(|engine| engine.systems_json())
+            .unwrap_or_else(|| "[]".to_string())
+    })
+}
+
+#[wasm_bindgen]
+pub fn engine_set_profiling(enabled: bool) {
+    ENGINE.with(|engine| {
+        if let Some(engine) = engine.borrow_mut().as_mut() {
+            engine.set_profiling(enabled);
+        }
+    });
+}
+
+/// JSON array of `{phase, system, samples, min, avg, max}` rows in milliseconds.
+#[wasm_bindgen]
+pub fn engine_profile_report() -> String {
+    ENGINE.with(|engine| {
+        engine
+            .borrow()
+            .as_ref()
+            .and_then(|engine| engine.profiler())
+            .map(|profiler| profiler.report_json())
+            .unwrap_or_else(|| "[]".to_string())
+    })
+}
+
+#[wasm_bindgen]
+pub fn engine_checksum() -> u64 {
+    ENGINE.with(|engine| {
+        engine
+            .borrow()
+            .as_ref()
+            .map(|engine| engine.checksum())
+            .unwrap_or(0)
+    })
+}
@@ -12,6 +12,9 @@ export const PARAM = Object.freeze({
   BOUNCE_ZONE_5: 10,
   BOUNCE_ZONE_6: 11,
   BOUNCE_ZONE_7: 12,
+  PADDLE_ACCELERATION: 13,
+  PADDLE_DRAG: 14,
+  PADDLE_LAUNCH_BIAS: 15,
 });
 
 export const controls = [
@@ -39,6 +42,30 @@ export const controls = [
     step: 1,
     decimals: 1,
   },
+  {
+    label: "Paddle Acceleration",
+    paramId: PARAM.PADDLE_ACCELERATION,
+    min: 500,
+    max: 20000,
+    step: 50,
+    decimals: 0,
+  },
+  {
+    label: "Paddle Drag",
+    paramId: PARAM.PADDLE_DRAG,
+    min: 0,
+    max: 40,
+    step: 0.5,
+    decimals: 1,
+  },
+  {
+    label: "Paddle Launch Bias",
+    paramId: PARAM.PADDLE_LAUNCH_BIAS,
+    min: 0,
+    max: 1,
+    step: 0.05,
+    decimals: 2,
+  },
   {
     label: "Ball Radius",
     paramId: PARAM.BALL_RADIUS,
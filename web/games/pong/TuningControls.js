@@ -3,6 +3,9 @@ export const PARAM = Object.freeze({
   BALL_Y: 1,
   BALL_VX: 2,
   BALL_VY: 3,
+  WRAP_WALLS: 4,
+  PADDLE_ACCELERATION: 5,
+  PADDLE_DRAG: 6,
 });
 
 export const controls = [
@@ -30,4 +33,28 @@ export const controls = [
     decimals: 1,
     readOnly: true,
   },
+  {
+    label: "Wrap Top/Bottom",
+    paramId: PARAM.WRAP_WALLS,
+    min: 0,
+    max: 1,
+    step: 1,
+    decimals: 0,
+  },
+  {
+    label: "Paddle Acceleration",
+    paramId: PARAM.PADDLE_ACCELERATION,
+    min: 500,
+    max: 20000,
+    step: 50,
+    decimals: 0,
+  },
+  {
+    label: "Paddle Drag",
+    paramId: PARAM.PADDLE_DRAG,
+    min: 0,
+    max: 40,
+    step: 0.5,
+    decimals: 1,
+  },
 ];
@@ -0,0 +1,50 @@
+import { html, useState, useEffect } from "preact";
+
+const formatMs = (value) => value.toFixed(3);
+
+const PerfPanel = ({ engine_set_profiling, engine_profile_report }) => {
+  const [enabled, setEnabled] = useState(false);
+  const [rows, setRows] = useState([]);
+
+  useEffect(() => {
+    engine_set_profiling(enabled);
+    if (!enabled) {
+      setRows([]);
+      return undefined;
+    }
+
+    const interval = setInterval(() => {
+      setRows(JSON.parse(engine_profile_report()));
+    }, 250);
+
+    return () => clearInterval(interval);
+  }, [enabled, engine_set_profiling, engine_profile_report]);
+
+  return html`
+    <div class="debug__section">
+      <div class="debug__title">Profiler (ms min / avg / max)</div>
+      <button
+        onClick=${() => setEnabled(!enabled)}
+        type="button"
+        class="debug__button"
+      >
+        ${enabled ? "Stop Profiling" : "Start Profiling"}
+      </button>
+      ${rows.map(
+        (row) => html`
+          <div class="debug__row" key=${row.phase + (row.system ?? "")}>
+            <span class="debug__label">
+              ${row.system === null ? row.phase : "  " + row.system}
+            </span>
+            <span class="debug__value">
+              ${formatMs(row.min)} / ${formatMs(row.avg)} /
+              ${formatMs(row.max)}
+            </span>
+          </div>
+        `,
+      )}
+    </div>
+  `;
+};
+
+export default PerfPanel;
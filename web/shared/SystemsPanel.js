@@ -0,0 +1,36 @@
+import { html, useState, useEffect } from "preact";
+
+const SystemsPanel = ({ engine_list_systems, engine_set_system_enabled }) => {
+  const [systems, setSystems] = useState([]);
+
+  const refresh = () => setSystems(JSON.parse(engine_list_systems()));
+
+  useEffect(() => {
+    refresh();
+  }, [engine_list_systems]);
+
+  const toggle = (name, enabled) => {
+    engine_set_system_enabled(name, enabled);
+    refresh();
+  };
+
+  return html`
+    <div class="debug__section">
+      <div class="debug__title">Systems</div>
+      ${systems.map(
+        (system) => html`
+          <label class="debug__row" key=${system.phase + system.name}>
+            <span class="debug__label">${system.phase} / ${system.name}</span>
+            <input
+              type="checkbox"
+              checked=${system.enabled}
+              onChange=${(event) => toggle(system.name, event.target.checked)}
+            />
+          </label>
+        `,
+      )}
+    </div>
+  `;
+};
+
+export default SystemsPanel;
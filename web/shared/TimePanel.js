@@ -0,0 +1,68 @@
+import { html, useState } from "preact";
+
+const TimePanel = ({
+  engine_pause,
+  engine_resume,
+  engine_step_frame,
+  engine_set_time_scale,
+  engine_max_substeps,
+  engine_set_max_substeps,
+}) => {
+  const [paused, setPaused] = useState(false);
+  const [scale, setScale] = useState(1);
+  const [maxSubsteps, setMaxSubsteps] = useState(() => engine_max_substeps());
+
+  const togglePause = () => {
+    if (paused) {
+      engine_resume();
+    } else {
+      engine_pause();
+    }
+    setPaused(!paused);
+  };
+
+  return html`
+    <div class="debug__section">
+      <div class="debug__title">Time</div>
+      <button onClick=${togglePause} type="button" class="debug__button">
+        ${paused ? "Resume" : "Pause"}
+      </button>
+      <button
+        onClick=${() => engine_step_frame()}
+        type="button"
+        class="debug__button"
+        disabled=${!paused}
+      >
+        Step Frame
+      </button>
+      <label class="debug__row">
+        <span class="debug__label">Scale</span>
+        <input
+          type="range"
+          min="0.1"
+          max="4"
+          step="0.1"
+          value=${scale}
+          onInput=${(event) =>
+            setScale(engine_set_time_scale(parseFloat(event.target.value)))}
+        />
+        <span class="debug__value">${scale.toFixed(1)}×</span>
+      </label>
+      <label class="debug__row">
+        <span class="debug__label">Max sub-steps</span>
+        <input
+          type="number"
+          min="1"
+          max="64"
+          value=${maxSubsteps}
+          onChange=${(event) =>
+            setMaxSubsteps(
+              engine_set_max_substeps(parseInt(event.target.value, 10) || 1),
+            )}
+        />
+      </label>
+    </div>
+  `;
+};
+
+export default TimePanel;
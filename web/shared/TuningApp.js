@@ -1,9 +1,22 @@
 import { html } from "preact";
 import {
   engine_get_tuning_param,
+  engine_list_systems,
+  engine_max_substeps,
+  engine_pause,
+  engine_profile_report,
   engine_reset_tuning_defaults,
+  engine_resume,
+  engine_set_max_substeps,
+  engine_set_profiling,
+  engine_set_system_enabled,
+  engine_set_time_scale,
   engine_set_tuning_param,
+  engine_step_frame,
 } from "../dist/gamedev_wasm_hello.js";
+import PerfPanel from "./PerfPanel.js";
+import SystemsPanel from "./SystemsPanel.js";
+import TimePanel from "./TimePanel.js";
 import TuningPanel from "./TuningPanel.js";
 
 const TuningApp = ({ controls }) => {
@@ -12,7 +25,24 @@ const TuningApp = ({ controls }) => {
     engine_get_tuning_param=${engine_get_tuning_param}
     engine_set_tuning_param=${engine_set_tuning_param}
     engine_reset_tuning_defaults=${engine_reset_tuning_defaults}
-  />`;
+  >
+    <${TimePanel}
+      engine_pause=${engine_pause}
+      engine_resume=${engine_resume}
+      engine_step_frame=${engine_step_frame}
+      engine_set_time_scale=${engine_set_time_scale}
+      engine_max_substeps=${engine_max_substeps}
+      engine_set_max_substeps=${engine_set_max_substeps}
+    />
+    <${SystemsPanel}
+      engine_list_systems=${engine_list_systems}
+      engine_set_system_enabled=${engine_set_system_enabled}
+    />
+    <${PerfPanel}
+      engine_set_profiling=${engine_set_profiling}
+      engine_profile_report=${engine_profile_report}
+    />
+  <//>`;
 };
 
 export default TuningApp;
@@ -41,6 +41,7 @@ const TuningPanel = ({
   engine_get_tuning_param,
   engine_set_tuning_param,
   engine_reset_tuning_defaults,
+  children,
 }) => {
   const fetchControlValues = useCallback(() => {
     const values = {};
@@ -88,6 +89,7 @@ const TuningPanel = ({
           </button>
         </div>
       `}
+      ${children}
     </div>
   `;
 };